@@ -0,0 +1,294 @@
+//! Runs `pallet_algorithms` modules off-chain against fixtures, so algorithms can be tried
+//! without deploying them to a node.
+//!
+//! Modules are validated and run by [`pallet_algorithms::Pallet`] itself, configured as in the
+//! runtime, so their memory layout, host functions and gas match a `run_algo_for` call.
+//!
+//! The schema set is a JSON object mapping schema names to their fields:
+//!
+//! ```json
+//! { "repos": [["no_of_repos", "U32"], ["no_of_private_repos", "U64"]] }
+//! ```
+//!
+//! A fixture lists the attestation read for each algorithm input, in declaration order, and
+//! optionally the parameters of the run. An input without `values` is missing, as in a
+//! best-effort run:
+//!
+//! ```json
+//! {
+//!   "inputs": [{ "schema": "repos", "values": [12, 3] }, { "schema": "repos" }],
+//!   "params": { "schema": "weights", "values": [2] }
+//! }
+//! ```
+//!
+//! Values are JSON numbers for numeric fields, booleans for `Boolean` and strings for `Text`
+//! and `Char`. `0x`-prefixed hex gives the raw bytes of any field, and is the only way to give
+//! a `Hash`.
+
+use std::{
+	collections::BTreeMap,
+	fs,
+	path::{Path, PathBuf},
+};
+
+use frame_support::{traits::Get, BoundedVec};
+use pallet_algorithms::{RunFailure, RunOutput};
+use pallet_credentials::{CredAttestation, CredSchema, CredType};
+use serde_json::Value;
+use solochain_template_runtime::Runtime;
+
+#[cfg(test)]
+mod tests;
+
+type Algorithms = pallet_algorithms::Pallet<Runtime>;
+type Credentials = pallet_credentials::Pallet<Runtime>;
+
+/// Schemas by name.
+pub type Schemas = BTreeMap<String, CredSchema<Runtime>>;
+
+/// Run an algorithm module against fixtures, as `run_algo_for` would on chain.
+#[derive(Debug, clap::Parser)]
+pub struct RunAlgoCmd {
+	/// Compiled algorithm module.
+	#[arg(long)]
+	pub wasm: PathBuf,
+
+	/// JSON object mapping schema names to their `[name, type]` fields.
+	#[arg(long)]
+	pub schemas: PathBuf,
+
+	/// JSON fixture with the attestation read for each input, and the parameters.
+	#[arg(long)]
+	pub fixture: PathBuf,
+
+	/// Schema of the structured result. Runs `calc_output` instead of `calc`.
+	#[arg(long)]
+	pub output_schema: Option<String>,
+
+	/// Gas limit. Defaults to the runtime's default gas limit.
+	#[arg(long)]
+	pub gas_limit: Option<u64>,
+}
+
+impl RunAlgoCmd {
+	pub fn run(&self) -> Result<(), String> {
+		let code = fs::read(&self.wasm)
+			.map_err(|e| format!("Failed to read {}: {e}", self.wasm.display()))?;
+		let schemas = parse_schemas(&read_json(&self.schemas)?)?;
+		let fixture = read_json(&self.fixture)?;
+		let gas_limit = self
+			.gas_limit
+			.unwrap_or_else(<Runtime as pallet_algorithms::Config>::DefaultGasLimit::get);
+
+		match run(&code, &schemas, &fixture, self.output_schema.as_deref(), gas_limit)? {
+			Ok(output) => {
+				println!("result: {}", output.result);
+				if let Some(data) = output.data {
+					println!("return data: 0x{}", hex::encode(data));
+				}
+				println!("gas used: {} of {}", output.gas_used, gas_limit);
+			},
+			Err(failure) => {
+				println!("failed: {:?}", failure.error);
+				if let Some(abort) = failure.abort {
+					println!(
+						"abort: {} at {}:{}:{}",
+						String::from_utf8_lossy(&abort.message),
+						String::from_utf8_lossy(&abort.file),
+						abort.line,
+						abort.column,
+					);
+				}
+				println!("gas used: {} of {}", failure.gas_used, gas_limit);
+			},
+		}
+
+		Ok(())
+	}
+}
+
+/// Validate `code` as `upload_code` does, and run it over the inputs and parameters of
+/// `fixture`. Errors if the fixture is invalid; a module failing is returned as a
+/// [`RunFailure`].
+pub fn run(
+	code: &[u8],
+	schemas: &Schemas,
+	fixture: &Value,
+	output_schema: Option<&str>,
+	gas_limit: u64,
+) -> Result<Result<RunOutput, RunFailure<Runtime>>, String> {
+	let inputs = fixture
+		.get("inputs")
+		.and_then(Value::as_array)
+		.ok_or("Fixture has no `inputs` array")?;
+
+	let mut attestations = Vec::with_capacity(inputs.len());
+	let mut present = Vec::with_capacity(inputs.len());
+	for (index, input) in inputs.iter().enumerate() {
+		let schema = schema_of(schemas, input).map_err(|e| format!("Input {index}: {e}"))?;
+
+		match input.get("values") {
+			None | Some(Value::Null) => {
+				attestations.push(Algorithms::missing_input(schema));
+				present.push(false);
+			},
+			Some(values) => {
+				let attestation =
+					attestation(schema, values).map_err(|e| format!("Input {index}: {e}"))?;
+				attestations.push(Algorithms::input_layout(schema, attestation));
+				present.push(true);
+			},
+		}
+	}
+
+	let params = match fixture.get("params") {
+		None | Some(Value::Null) => Vec::new(),
+		Some(params) => {
+			let schema = schema_of(schemas, params).map_err(|e| format!("Params: {e}"))?;
+			Algorithms::layout_size(schema).map_err(|e| format!("Params: {e:?}"))?;
+			let values = params.get("values").ok_or("Params have no `values`")?;
+			attestation(schema, values)
+				.map_err(|e| format!("Params: {e}"))?
+				.into_iter()
+				.flatten()
+				.collect()
+		},
+	};
+
+	let output_size = output_schema
+		.map(|name| {
+			let schema =
+				schemas.get(name).ok_or_else(|| format!("Unknown output schema `{name}`"))?;
+			Algorithms::layout_size(schema).map_err(|e| format!("Output schema `{name}`: {e:?}"))
+		})
+		.transpose()?;
+
+	Ok(sp_io::TestExternalities::default().execute_with(|| {
+		Algorithms::validate_code(code)?;
+		Algorithms::run_code(code.to_vec(), attestations, present, params, gas_limit, output_size)
+	}))
+}
+
+/// Parse a schema set, a JSON object mapping schema names to their `[name, type]` fields.
+pub fn parse_schemas(value: &Value) -> Result<Schemas, String> {
+	let object = value.as_object().ok_or("Schema set must be a JSON object")?;
+
+	object
+		.iter()
+		.map(|(name, fields)| {
+			let fields = fields
+				.as_array()
+				.ok_or_else(|| format!("Schema `{name}` must be an array of fields"))?
+				.iter()
+				.map(|field| {
+					let (Some(field_name), Some(cred_type)) = (
+						field.get(0).and_then(Value::as_str),
+						field.get(1).and_then(Value::as_str),
+					) else {
+						return Err(format!("Fields of `{name}` must be `[name, type]` pairs"));
+					};
+					let field_name = BoundedVec::try_from(field_name.as_bytes().to_vec())
+						.map_err(|_| format!("Field name `{field_name}` is too long"))?;
+					Ok((field_name, parse_cred_type(cred_type)?))
+				})
+				.collect::<Result<Vec<_>, String>>()?;
+
+			let schema = BoundedVec::try_from(fields)
+				.map_err(|_| format!("Schema `{name}` has too many fields"))?;
+			Ok((name.clone(), schema))
+		})
+		.collect()
+}
+
+fn read_json(path: &Path) -> Result<Value, String> {
+	let contents =
+		fs::read(path).map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+	serde_json::from_slice(&contents).map_err(|e| format!("Invalid JSON in {}: {e}", path.display()))
+}
+
+/// Schema named by the `schema` of an input or the params.
+fn schema_of<'a>(schemas: &'a Schemas, value: &Value) -> Result<&'a CredSchema<Runtime>, String> {
+	let name = value.get("schema").and_then(Value::as_str).ok_or("No `schema` given")?;
+	schemas.get(name).ok_or_else(|| format!("Unknown schema `{name}`"))
+}
+
+/// Attestation of `schema` with `values`, checked as `attest` checks it.
+fn attestation(schema: &CredSchema<Runtime>, values: &Value) -> Result<CredAttestation<Runtime>, String> {
+	let values = values.as_array().ok_or("`values` must be an array")?;
+	if values.len() != schema.len() {
+		return Err(format!("Expected {} values, got {}", schema.len(), values.len()));
+	}
+
+	let raw = schema
+		.iter()
+		.zip(values)
+		.map(|((_, cred_type), value)| encode_value(cred_type, value))
+		.collect::<Result<Vec<_>, String>>()?;
+
+	Credentials::validate_attestation(schema, &raw)
+		.ok_or_else(|| "Values don't fit the schema".into())
+}
+
+/// Bytes of `value` as stored in an attestation field of `cred_type`.
+fn encode_value(cred_type: &CredType, value: &Value) -> Result<Vec<u8>, String> {
+	if let Some(hex) = value.as_str().and_then(|s| s.strip_prefix("0x")) {
+		return hex::decode(hex).map_err(|e| format!("Invalid hex {value}: {e}"));
+	}
+
+	let integer = || {
+		value
+			.as_i64()
+			.map(i128::from)
+			.or_else(|| value.as_u64().map(i128::from))
+			.ok_or_else(|| format!("Expected an integer for {cred_type:?}, got {value}"))
+	};
+	let float = || value.as_f64().ok_or_else(|| format!("Expected a number, got {value}"));
+	let out_of_range = |_| format!("{value} is out of range for {cred_type:?}");
+
+	Ok(match cred_type {
+		CredType::U8 => u8::try_from(integer()?).map_err(out_of_range)?.to_le_bytes().to_vec(),
+		CredType::I8 => i8::try_from(integer()?).map_err(out_of_range)?.to_le_bytes().to_vec(),
+		CredType::U16 => u16::try_from(integer()?).map_err(out_of_range)?.to_le_bytes().to_vec(),
+		CredType::I16 => i16::try_from(integer()?).map_err(out_of_range)?.to_le_bytes().to_vec(),
+		CredType::U32 => u32::try_from(integer()?).map_err(out_of_range)?.to_le_bytes().to_vec(),
+		CredType::I32 => i32::try_from(integer()?).map_err(out_of_range)?.to_le_bytes().to_vec(),
+		CredType::U64 => u64::try_from(integer()?).map_err(out_of_range)?.to_le_bytes().to_vec(),
+		CredType::I64 => i64::try_from(integer()?).map_err(out_of_range)?.to_le_bytes().to_vec(),
+		CredType::F32 => (float()? as f32).to_le_bytes().to_vec(),
+		CredType::F64 => float()?.to_le_bytes().to_vec(),
+		CredType::Boolean => {
+			let flag = value.as_bool().ok_or_else(|| format!("Expected a boolean, got {value}"))?;
+			vec![flag as u8]
+		},
+		CredType::Char => match value.as_str().map(str::as_bytes) {
+			Some([byte]) => vec![*byte],
+			_ => return Err(format!("Expected a single ASCII character, got {value}")),
+		},
+		CredType::Text => value
+			.as_str()
+			.ok_or_else(|| format!("Expected a string, got {value}"))?
+			.as_bytes()
+			.to_vec(),
+		CredType::Hash => return Err(format!("Expected 0x-prefixed hex for a hash, got {value}")),
+	})
+}
+
+fn parse_cred_type(name: &str) -> Result<CredType, String> {
+	Ok(match name {
+		"Char" => CredType::Char,
+		"U8" => CredType::U8,
+		"I8" => CredType::I8,
+		"U16" => CredType::U16,
+		"I16" => CredType::I16,
+		"U32" => CredType::U32,
+		"I32" => CredType::I32,
+		"U64" => CredType::U64,
+		"I64" => CredType::I64,
+		"F32" => CredType::F32,
+		"F64" => CredType::F64,
+		"Hash" => CredType::Hash,
+		"Boolean" => CredType::Boolean,
+		"Text" => CredType::Text,
+		_ => return Err(format!("Unknown field type `{name}`")),
+	})
+}
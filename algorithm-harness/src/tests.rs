@@ -0,0 +1,137 @@
+#![cfg(test)]
+// Tests for the algorithm harness
+
+use super::*;
+use pallet_algorithms::Error;
+use serde_json::json;
+
+fn wasm(text: &str) -> Vec<u8> {
+	wat::parse_str(text).unwrap()
+}
+
+fn schemas() -> Schemas {
+	parse_schemas(&json!({
+		"repos": [["no_of_repos", "U32"], ["no_of_private_repos", "U64"]],
+		"weights": [["weight", "U32"]],
+	}))
+	.unwrap()
+}
+
+fn gas_limit() -> u64 {
+	<Runtime as pallet_algorithms::Config>::DefaultGasLimit::get()
+}
+
+#[test]
+fn runs_modules_over_the_fixture_inputs() {
+	// Inputs are laid out back to back from offset 0: a `U32` then a `U64`
+	let code = wasm(
+		r#"(module
+			(import "env" "memory" (memory 1))
+			(func (export "calc") (result i64)
+				(i64.add (i64.load32_u (i32.const 0)) (i64.load (i32.const 4)))))"#,
+	);
+	let fixture = json!({ "inputs": [{ "schema": "repos", "values": [12, 3] }] });
+
+	let output = run(&code, &schemas(), &fixture, None, gas_limit()).unwrap().unwrap();
+
+	assert_eq!((output.result, output.data), (15, None));
+	assert!(output.gas_used > 0 && output.gas_used <= gas_limit());
+}
+
+#[test]
+fn inputs_without_values_are_missing() {
+	let code = wasm(
+		r#"(module
+			(import "host" "input_present" (func $present (param i32) (result i32)))
+			(import "env" "memory" (memory 1))
+			(func (export "calc") (result i64)
+				(i64.extend_i32_u (i32.add
+					(i32.mul (call $present (i32.const 0)) (i32.const 10))
+					(call $present (i32.const 1))))))"#,
+	);
+	let fixture = json!({ "inputs": [{ "schema": "repos", "values": [1, 2] }, { "schema": "repos" }] });
+
+	let output = run(&code, &schemas(), &fixture, None, gas_limit()).unwrap().unwrap();
+
+	assert_eq!(output.result, 10);
+}
+
+#[test]
+fn passes_params_and_returns_output_data() {
+	// Echoes its parameters back as its structured result
+	let code = wasm(
+		r#"(module
+			(import "host" "read_params" (func $read_params (param i32)))
+			(import "host" "return_data" (func $return_data (param i32 i32)))
+			(import "env" "memory" (memory 1))
+			(func (export "calc_output") (result i64)
+				(call $read_params (i32.const 64))
+				(call $return_data (i32.const 64) (i32.const 4))
+				(i64.load32_u (i32.const 64))))"#,
+	);
+	let fixture = json!({ "inputs": [], "params": { "schema": "weights", "values": [7] } });
+
+	let output = run(&code, &schemas(), &fixture, Some("weights"), gas_limit()).unwrap().unwrap();
+
+	assert_eq!((output.result, output.data), (7, Some(7u32.to_le_bytes().to_vec())));
+}
+
+#[test]
+fn reports_modules_failing_as_run_failures() {
+	let fixture = json!({ "inputs": [] });
+
+	let trapping = wasm(r#"(module (func (export "calc") (result i64) unreachable))"#);
+	let failure = run(&trapping, &schemas(), &fixture, None, gas_limit()).unwrap().unwrap_err();
+	assert!(matches!(failure.error, Error::AcmFailedToCalculate));
+
+	// Writing the (empty) inputs costs one memory op, leaving a little gas to loop with
+	let looping = wasm(r#"(module (func (export "calc") (result i64) (loop (br 0)) (i64.const 0)))"#);
+	let small_limit = <Runtime as pallet_algorithms::Config>::GasCost::get().memory_op + 100_000;
+	let failure = run(&looping, &schemas(), &fixture, None, small_limit).unwrap().unwrap_err();
+	assert!(matches!(failure.error, Error::OutOfGas));
+	assert_eq!(failure.gas_used, small_limit);
+
+	// Validated as `upload_code` would
+	let floats = wasm(r#"(module (func (export "calc") (result i64) (i64.trunc_f64_s (f64.const 1))))"#);
+	let failure = run(&floats, &schemas(), &fixture, None, gas_limit()).unwrap().unwrap_err();
+	assert!(matches!(failure.error, Error::FloatsNotAllowed));
+}
+
+#[test]
+fn rejects_invalid_fixtures() {
+	let code = wasm(r#"(module (func (export "calc") (result i64) (i64.const 0)))"#);
+	let run_with = |fixture: Value, output_schema: Option<&str>| {
+		run(&code, &schemas(), &fixture, output_schema, gas_limit()).map(|_| ())
+	};
+
+	assert_eq!(run_with(json!({}), None), Err("Fixture has no `inputs` array".into()));
+	assert_eq!(
+		run_with(json!({ "inputs": [{ "schema": "stars" }] }), None),
+		Err("Input 0: Unknown schema `stars`".into())
+	);
+	assert_eq!(
+		run_with(json!({ "inputs": [{ "schema": "repos", "values": [1] }] }), None),
+		Err("Input 0: Expected 2 values, got 1".into())
+	);
+	assert_eq!(
+		run_with(json!({ "inputs": [{ "schema": "weights", "values": [-1] }] }), None),
+		Err("Input 0: -1 is out of range for U32".into())
+	);
+	assert_eq!(
+		run_with(json!({ "inputs": [], "params": { "schema": "weights" } }), None),
+		Err("Params have no `values`".into())
+	);
+	assert_eq!(run_with(json!({ "inputs": [] }), Some("stars")), Err("Unknown output schema `stars`".into()));
+}
+
+#[test]
+fn parse_schemas_rejects_unknown_types() {
+	assert_eq!(
+		parse_schemas(&json!({ "repos": [["no_of_repos", "U128"]] })).map(|_| ()),
+		Err("Unknown field type `U128`".into())
+	);
+	assert_eq!(
+		parse_schemas(&json!({ "repos": ["no_of_repos"] })).map(|_| ()),
+		Err("Fields of `repos` must be `[name, type]` pairs".into())
+	);
+}
@@ -51,4 +51,7 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Run an algorithm module against fixtures, as the runtime would.
+	RunAlgo(algorithm_harness::RunAlgoCmd),
 }
@@ -39,6 +39,7 @@ impl SubstrateCli for Cli {
 		Ok(match id {
 			"dev" => Box::new(chain_spec::development_config()?),
 			"" | "local" => Box::new(chain_spec::local_testnet_config()?),
+			"reputation_demo" => Box::new(chain_spec::reputation_demo_config()?),
 			path =>
 				Box::new(chain_spec::ChainSpec::from_json_file(std::path::PathBuf::from(path))?),
 		})
@@ -174,6 +175,11 @@ pub fn run() -> sc_cli::Result<()> {
 			let runner = cli.create_runner(cmd)?;
 			runner.sync_run(|config| cmd.run::<Block>(&config))
 		},
+		Some(Subcommand::RunAlgo(cmd)) => {
+			// Show the module's `host.print*` output and traps
+			sc_cli::LoggerBuilder::new("algo=debug").init()?;
+			cmd.run().map_err(sc_cli::Error::Input)
+		},
     Some(Subcommand::TryRuntime) => todo!(),
 		None => {
 			let runner = cli.create_runner(&cli.run)?;
@@ -0,0 +1,19 @@
+//! Runtime API definition for `pallet_algorithms`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+pub use pallet_algorithms::{InputRef, Score};
+
+sp_api::decl_runtime_apis! {
+    pub trait AlgorithmsApi<Hash, BlockNumber> where
+        Hash: Codec,
+        BlockNumber: Codec,
+    {
+        /// Latest score of `account_id` for `algorithm_id` run with `issuer_hash`.
+        /// `account_id` is accepted in any format `run_algo_for` accepts.
+        fn score(algorithm_id: u64, issuer_hash: Hash, account_id: Vec<u8>) -> Option<Score<Hash, BlockNumber>>;
+    }
+}
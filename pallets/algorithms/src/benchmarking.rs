@@ -0,0 +1,608 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{v2::*, account, whitelisted_caller, BenchmarkError};
+use frame_support::{BoundedVec, ensure, traits::{Currency, Get}};
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
+use pallet_credentials::{self as credentials, Attestations, CredType, Schemas};
+use pallet_issuers::VerificationTier;
+use sp_std::vec;
+use sp_std::vec::Vec;
+use sp_runtime::traits::{Bounded, Hash, One};
+
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    fn leb128(mut value: u32) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                bytes.push(byte);
+                return bytes;
+            }
+            bytes.push(byte | 0x80);
+        }
+    }
+
+    fn section(id: u8, content: Vec<u8>) -> Vec<u8> {
+        [vec![id], leb128(content.len() as u32), content].concat()
+    }
+
+    /// Module importing `env.memory` and exporting `calc` as a function of type `() -> i64`
+    /// with `body`. Written out by hand, as `wat` is only a dev-dependency.
+    fn module(body: Vec<u8>) -> Vec<u8> {
+        [
+            b"\0asm\x01\0\0\0".to_vec(),
+            // Type `() -> i64`
+            section(1, vec![0x01, 0x60, 0x00, 0x01, 0x7e]),
+            section(2, [&[0x01, 0x03][..], b"env", &[0x06], b"memory", &[0x02, 0x00, 0x01]].concat()),
+            section(3, vec![0x01, 0x00]),
+            section(7, [&[0x01, 0x04][..], b"calc", &[0x00, 0x00]].concat()),
+            section(10, [vec![0x01], leb128(body.len() as u32), body].concat()),
+        ].concat()
+    }
+
+    /// Module of at most `size` bytes returning the first eight bytes of its inputs, padded
+    /// with `nop`s to about `size`.
+    fn calc_module(size: u32) -> Vec<u8> {
+        // No locals, `nop`s, then `i64.load (i32.const 0)`
+        let mut body = vec![0x00];
+        body.extend(vec![0x01; size.saturating_sub(64) as usize]);
+        body.extend([0x41, 0x00, 0x29, 0x03, 0x00, 0x0b]);
+        module(body)
+    }
+
+    /// Module looping until it runs out of gas.
+    fn looping_module() -> Vec<u8> {
+        // No locals, `loop (br 0)`, then `i64.const 0`
+        module(vec![0x00, 0x03, 0x40, 0x0c, 0x00, 0x0b, 0x42, 0x00, 0x0b])
+    }
+
+    fn fund<T: Config>(who: &T::AccountId) {
+        <T as pallet_issuers::Config>::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value() / 1_000u32.into());
+    }
+
+    /// Account holding enough for any deposit or fee.
+    fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+        let who = account(name, index, 0);
+        fund::<T>(&who);
+        who
+    }
+
+    /// Distinct Ethereum addresses, the first being the subject of the benchmarked runs.
+    fn subject(index: u32) -> Vec<u8> {
+        [index.to_le_bytes().to_vec(), vec![5; 16]].concat()
+    }
+
+    /// Schema of a single `U64` named after `index`, stored.
+    fn u64_schema<T: Config>(index: u32) -> T::Hash {
+        let schema = credentials::Pallet::<T>::bound_schema(vec![(index.to_le_bytes().to_vec(), CredType::U64)]).unwrap();
+        let schema_hash = credentials::Pallet::<T>::schema_hash(&schema);
+        Schemas::<T>::insert(schema_hash, schema);
+        schema_hash
+    }
+
+    /// Issuer controlled by `controller`, attesting `n` schemas for the subject.
+    fn attesting_issuer<T: Config>(controller: &T::AccountId, n: u32) -> (T::Hash, Vec<T::Hash>) {
+        let issuer_hash = <T as Config>::Hashing::hash(b"issuer");
+        pallet_issuers::Issuers::<T>::insert(issuer_hash, pallet_issuers::Issuer::<T> {
+            name: BoundedVec::truncate_from(b"issuer".to_vec()),
+            controllers: BoundedVec::truncate_from(vec![controller.clone()]),
+        });
+
+        let acquirer_address = credentials::Pallet::<T>::parse_acquirer_address(subject(0)).unwrap();
+        let schema_hashes = (0..n).map(|i| {
+            let schema_hash = u64_schema::<T>(i);
+            Attestations::<T>::insert((acquirer_address.clone(), issuer_hash, schema_hash), vec![
+                BoundedVec::truncate_from(vec![BoundedVec::truncate_from(7u64.to_le_bytes().to_vec())]),
+            ]);
+            schema_hash
+        }).collect();
+
+        (issuer_hash, schema_hashes)
+    }
+
+    /// Algorithm of `owner` reading `schema_hashes` from the issuer it is run with, run by a
+    /// module of about `c` bytes, with `status`.
+    fn algorithm<T: Config>(owner: &T::AccountId, schema_hashes: Vec<T::Hash>, c: u32, status: AlgoStatus) -> u64 {
+        let algorithm_id = NextAlgoId::<T>::get();
+        Pallet::<T>::save_algo(RawOrigin::Signed(owner.clone()).into(), schema_hashes, calc_module(c), None).unwrap();
+        Algorithms::<T>::mutate(algorithm_id, |algorithm| {
+            if let Some(algorithm) = algorithm {
+                algorithm.status = status;
+            }
+        });
+        algorithm_id
+    }
+
+    /// Active algorithm of `owner` reading every input its issuer attests, with the largest
+    /// module.
+    fn active_algorithm<T: Config>(owner: &T::AccountId) -> (u64, T::Hash) {
+        let (issuer_hash, schema_hashes) = attesting_issuer::<T>(owner, T::MaxSchemas::get());
+        let algorithm_id = algorithm::<T>(owner, schema_hashes, T::MaxCodeSize::get(), AlgoStatus::Active);
+        (algorithm_id, issuer_hash)
+    }
+
+    /// Active algorithm with the most that disabling it undoes: a refresh policy watching
+    /// all of its inputs and a pending owner.
+    fn watched_algorithm<T: Config>(owner: &T::AccountId) -> u64 {
+        let (algorithm_id, _) = active_algorithm::<T>(owner);
+        let origin: T::RuntimeOrigin = RawOrigin::Signed(owner.clone()).into();
+        Pallet::<T>::set_refresh_policy(origin.clone(), algorithm_id, RefreshPolicy::MarkDirty).unwrap();
+        Pallet::<T>::transfer_algo_ownership(origin, algorithm_id, account("new_owner", 0, 0)).unwrap();
+        algorithm_id
+    }
+
+    fn schedule<T: Config>(owner: &T::AccountId) -> u64 {
+        let (algorithm_id, issuer_hash) = active_algorithm::<T>(owner);
+        let schedule_id = NextScheduleId::<T>::get();
+        Pallet::<T>::create_schedule(
+            RawOrigin::Signed(owner.clone()).into(),
+            issuer_hash,
+            algorithm_id,
+            BoundedVec::default(),
+            schedule_interval::<T>(),
+        ).unwrap();
+        schedule_id
+    }
+
+    fn schedule_interval<T: Config>() -> BlockNumberFor<T> {
+        T::MinScheduleInterval::get().max(One::one())
+    }
+
+    fn subjects<T: Config>(s: u32) -> BoundedVec<Vec<u8>, T::MaxBatchSize> {
+        BoundedVec::truncate_from((0..s).map(subject).collect())
+    }
+
+    #[benchmark]
+    fn save_algo(n: Linear<0, { T::MaxSchemas::get() }>, c: Linear<64, { T::MaxCodeSize::get() }>) -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let (_, schema_hashes) = attesting_issuer::<T>(&caller, n);
+        let algorithm_id = NextAlgoId::<T>::get();
+
+        #[extrinsic_call]
+        save_algo(RawOrigin::Signed(caller), schema_hashes, calc_module(c), None);
+
+        ensure!(Algorithms::<T>::contains_key(algorithm_id), "Algorithm was not saved");
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn run_algo_for(n: Linear<0, { T::MaxSchemas::get() }>) -> Result<(), BenchmarkError> {
+        let owner = funded_account::<T>("owner", 0);
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let (issuer_hash, schema_hashes) = attesting_issuer::<T>(&owner, n);
+        let algorithm_id = algorithm::<T>(&owner, schema_hashes.clone(), T::MaxCodeSize::get(), AlgoStatus::Active);
+
+        // Fees paid to the owner and a payee per input
+        let fee = <T as pallet_issuers::Config>::Currency::minimum_balance();
+        RunFees::<T>::insert(algorithm_id, fee);
+        for (i, schema_hash) in schema_hashes.into_iter().enumerate() {
+            let payee = funded_account::<T>("payee", i as u32);
+            DataFees::<T>::insert(issuer_hash, schema_hash, DataFee { payee, amount: fee });
+        }
+
+        #[extrinsic_call]
+        run_algo_for(RawOrigin::Signed(caller), issuer_hash, subject(0), algorithm_id, BoundedVec::default());
+
+        let acquirer_address = credentials::Pallet::<T>::parse_acquirer_address(subject(0))?;
+        ensure!(Scores::<T>::contains_key((algorithm_id, issuer_hash, acquirer_address)), "Score was not stored");
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn save_algo_with_inputs(
+        n: Linear<0, { T::MaxSchemas::get() }>,
+        c: Linear<64, { T::MaxCodeSize::get() }>,
+    ) -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let (issuer_hash, schema_hashes) = attesting_issuer::<T>(&caller, n);
+        let trusted_issuers = (1..T::MaxTrustedIssuers::get())
+            .map(|i| <T as Config>::Hashing::hash(&i.to_le_bytes()))
+            .chain([issuer_hash])
+            .collect::<Vec<_>>();
+        let inputs = schema_hashes.into_iter()
+            .map(|schema_hash| AlgoInput {
+                issuer: IssuerSelector::AnyOf(BoundedVec::truncate_from(trusted_issuers.clone())),
+                schema_hash,
+            })
+            .collect::<Vec<_>>();
+        let param_schema = u64_schema::<T>(n);
+        let algorithm_id = NextAlgoId::<T>::get();
+
+        #[extrinsic_call]
+        save_algo_with_inputs(
+            RawOrigin::Signed(caller),
+            vec![b'a'; T::MaxAlgoNameLength::get() as usize],
+            vec![b'a'; T::MaxAlgoDescriptionLength::get() as usize],
+            inputs,
+            MissingInputPolicy::BestEffort,
+            calc_module(c),
+            None,
+            None,
+            Some(param_schema),
+        );
+
+        ensure!(Algorithms::<T>::contains_key(algorithm_id), "Algorithm was not saved");
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn update_algo() -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let (algorithm_id, _) = active_algorithm::<T>(&caller);
+        let code = calc_module(T::MaxCodeSize::get() - 1);
+        let code_hash = <T as Config>::Hashing::hash(&code);
+        Pallet::<T>::upload_code(RawOrigin::Signed(caller.clone()).into(), code)?;
+
+        #[extrinsic_call]
+        update_algo(
+            RawOrigin::Signed(caller),
+            algorithm_id,
+            Some(vec![b'a'; T::MaxAlgoNameLength::get() as usize]),
+            Some(vec![b'a'; T::MaxAlgoDescriptionLength::get() as usize]),
+            Some(code_hash),
+            Some(T::MaxGasLimit::get()),
+        );
+
+        let algorithm = Algorithms::<T>::get(algorithm_id).ok_or("Algorithm was removed")?;
+        ensure!(algorithm.code_hash == code_hash, "Module was not updated");
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn disable_algo() -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let algorithm_id = watched_algorithm::<T>(&caller);
+
+        #[extrinsic_call]
+        disable_algo(RawOrigin::Signed(caller), algorithm_id);
+
+        let algorithm = Algorithms::<T>::get(algorithm_id).ok_or("Algorithm was removed")?;
+        ensure!(algorithm.status == AlgoStatus::Disabled, "Algorithm was not disabled");
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn transfer_algo_ownership() -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let (algorithm_id, _) = active_algorithm::<T>(&caller);
+        let new_owner: T::AccountId = account("new_owner", 0, 0);
+
+        #[extrinsic_call]
+        transfer_algo_ownership(RawOrigin::Signed(caller), algorithm_id, new_owner.clone());
+
+        ensure!(PendingOwners::<T>::get(algorithm_id) == Some(new_owner), "Ownership was not offered");
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn upload_code(c: Linear<64, { T::MaxCodeSize::get() }>) -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let code = calc_module(c);
+        let code_hash = <T as Config>::Hashing::hash(&code);
+
+        #[extrinsic_call]
+        upload_code(RawOrigin::Signed(caller), code);
+
+        ensure!(CodeByHash::<T>::contains_key(code_hash), "Module was not stored");
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn remove_code() -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let code = calc_module(T::MaxCodeSize::get());
+        let code_hash = <T as Config>::Hashing::hash(&code);
+        Pallet::<T>::upload_code(RawOrigin::Signed(caller.clone()).into(), code)?;
+
+        #[extrinsic_call]
+        remove_code(RawOrigin::Signed(caller), code_hash);
+
+        ensure!(!CodeByHash::<T>::contains_key(code_hash), "Module was not removed");
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn create_algo(n: Linear<0, { T::MaxSchemas::get() }>) -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let (_, schema_hashes) = attesting_issuer::<T>(&caller, n);
+        let inputs = schema_hashes.into_iter()
+            .map(|schema_hash| AlgoInput { issuer: IssuerSelector::Caller, schema_hash })
+            .collect::<Vec<_>>();
+        let param_schema = u64_schema::<T>(n);
+        let code = calc_module(T::MaxCodeSize::get());
+        let code_hash = <T as Config>::Hashing::hash(&code);
+        Pallet::<T>::upload_code(RawOrigin::Signed(caller.clone()).into(), code)?;
+        let algorithm_id = NextAlgoId::<T>::get();
+
+        #[extrinsic_call]
+        create_algo(
+            RawOrigin::Signed(caller),
+            vec![b'a'; T::MaxAlgoNameLength::get() as usize],
+            vec![b'a'; T::MaxAlgoDescriptionLength::get() as usize],
+            inputs,
+            MissingInputPolicy::RequireAll,
+            code_hash,
+            None,
+            None,
+            Some(param_schema),
+        );
+
+        ensure!(Algorithms::<T>::contains_key(algorithm_id), "Algorithm was not created");
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn request_algo_run() -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let (algorithm_id, issuer_hash) = active_algorithm::<T>(&caller);
+        let request_id = NextRunRequestId::<T>::get();
+
+        #[extrinsic_call]
+        request_algo_run(RawOrigin::Signed(caller), issuer_hash, subject(0), algorithm_id, BoundedVec::default());
+
+        ensure!(RunRequests::<T>::contains_key(request_id), "Run was not requested");
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn set_offchain_authorities(a: Linear<1, { T::MaxOffchainAuthorities::get() }>) -> Result<(), BenchmarkError> {
+        let origin = T::AuthorityOrigin::try_successful_origin()
+            .map_err(|_| BenchmarkError::Weightless)?;
+        let authorities: Vec<T::AccountId> = (0..a).map(|i| account("authority", i, 0)).collect();
+
+        #[extrinsic_call]
+        set_offchain_authorities(origin as T::RuntimeOrigin, BoundedVec::truncate_from(authorities));
+
+        ensure!(OffchainAuthorities::<T>::get().len() == a as usize, "Authorities were not set");
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn create_schedule() -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let (algorithm_id, issuer_hash) = active_algorithm::<T>(&caller);
+        let schedule_id = NextScheduleId::<T>::get();
+
+        #[extrinsic_call]
+        create_schedule(RawOrigin::Signed(caller), issuer_hash, algorithm_id, BoundedVec::default(), schedule_interval::<T>());
+
+        ensure!(Schedules::<T>::contains_key(schedule_id), "Schedule was not created");
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn add_scheduled_subjects(s: Linear<1, { T::MaxBatchSize::get().min(T::MaxScheduledSubjects::get()) }>) -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let schedule_id = schedule::<T>(&caller);
+
+        #[extrinsic_call]
+        add_scheduled_subjects(RawOrigin::Signed(caller), schedule_id, subjects::<T>(s));
+
+        let schedule = Schedules::<T>::get(schedule_id).ok_or("Schedule was removed")?;
+        ensure!(schedule.subjects == s, "Subjects were not added");
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn remove_scheduled_subjects(s: Linear<1, { T::MaxBatchSize::get().min(T::MaxScheduledSubjects::get()) }>) -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let schedule_id = schedule::<T>(&caller);
+        Pallet::<T>::add_scheduled_subjects(RawOrigin::Signed(caller.clone()).into(), schedule_id, subjects::<T>(s))?;
+
+        #[extrinsic_call]
+        remove_scheduled_subjects(RawOrigin::Signed(caller), schedule_id, subjects::<T>(s));
+
+        let schedule = Schedules::<T>::get(schedule_id).ok_or("Schedule was removed")?;
+        ensure!(schedule.subjects == 0, "Subjects were not removed");
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn cancel_schedule(s: Linear<0, { T::MaxScheduledSubjects::get() }>) -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let schedule_id = schedule::<T>(&caller);
+        for i in 0..s {
+            let acquirer_address = credentials::Pallet::<T>::parse_acquirer_address(subject(i))?;
+            ScheduledSubjects::<T>::insert(schedule_id, acquirer_address, ());
+        }
+        Schedules::<T>::mutate(schedule_id, |schedule| {
+            if let Some(schedule) = schedule {
+                schedule.subjects = s;
+            }
+        });
+        // An unfinished round to drop
+        ScheduledRunQueue::<T>::mutate(|queue| queue.try_push(ScheduledRun { schedule_id, last_subject: None }))
+            .map_err(|_| "Run queue is full")?;
+
+        #[extrinsic_call]
+        cancel_schedule(RawOrigin::Signed(caller), schedule_id);
+
+        ensure!(!Schedules::<T>::contains_key(schedule_id), "Schedule was not cancelled");
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn set_refresh_policy(n: Linear<0, { T::MaxSchemas::get() }>) -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let (_, schema_hashes) = attesting_issuer::<T>(&caller, n);
+        let algorithm_id = algorithm::<T>(&caller, schema_hashes, T::MaxCodeSize::get(), AlgoStatus::Active);
+
+        #[extrinsic_call]
+        set_refresh_policy(RawOrigin::Signed(caller), algorithm_id, RefreshPolicy::MarkDirty);
+
+        ensure!(RefreshPolicies::<T>::get(algorithm_id) == RefreshPolicy::MarkDirty, "Policy was not set");
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn set_run_fee() -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let (algorithm_id, _) = active_algorithm::<T>(&caller);
+        let fee = <T as pallet_issuers::Config>::Currency::minimum_balance();
+
+        #[extrinsic_call]
+        set_run_fee(RawOrigin::Signed(caller), algorithm_id, Some(fee));
+
+        ensure!(RunFees::<T>::get(algorithm_id) == Some(fee), "Fee was not set");
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn set_data_fee() -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        let (issuer_hash, schema_hashes) = attesting_issuer::<T>(&caller, 1);
+        let fee = DataFee { payee: caller.clone(), amount: <T as pallet_issuers::Config>::Currency::minimum_balance() };
+
+        #[extrinsic_call]
+        set_data_fee(RawOrigin::Signed(caller), issuer_hash, schema_hashes[0], Some(fee));
+
+        ensure!(DataFees::<T>::contains_key(issuer_hash, schema_hashes[0]), "Fee was not set");
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn set_params() -> Result<(), BenchmarkError> {
+        let origin = T::ParamsOrigin::try_successful_origin()
+            .map_err(|_| BenchmarkError::Weightless)?;
+        // Deposits can't be above the total issuance
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let params = Parameters::<T>::get();
+
+        #[extrinsic_call]
+        set_params(origin as T::RuntimeOrigin, params);
+
+        ensure!(Parameters::<T>::exists(), "Parameters were not set");
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn accept_algo_ownership() -> Result<(), BenchmarkError> {
+        let owner = funded_account::<T>("owner", 0);
+        let algorithm_id = watched_algorithm::<T>(&owner);
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        Pallet::<T>::transfer_algo_ownership(RawOrigin::Signed(owner).into(), algorithm_id, caller.clone())?;
+
+        #[extrinsic_call]
+        accept_algo_ownership(RawOrigin::Signed(caller.clone()), algorithm_id);
+
+        let algorithm = Algorithms::<T>::get(algorithm_id).ok_or("Algorithm was removed")?;
+        ensure!(algorithm.owner == caller, "Ownership was not transferred");
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn approve_algo() -> Result<(), BenchmarkError> {
+        let origin = T::ApprovalOrigin::try_successful_origin()
+            .map_err(|_| BenchmarkError::Weightless)?;
+        let owner = funded_account::<T>("owner", 0);
+        let (_, schema_hashes) = attesting_issuer::<T>(&owner, T::MaxSchemas::get());
+        let algorithm_id = algorithm::<T>(&owner, schema_hashes, T::MaxCodeSize::get(), AlgoStatus::PendingApproval);
+
+        #[extrinsic_call]
+        approve_algo(origin as T::RuntimeOrigin, algorithm_id);
+
+        let algorithm = Algorithms::<T>::get(algorithm_id).ok_or("Algorithm was removed")?;
+        ensure!(algorithm.status == AlgoStatus::Active, "Algorithm was not approved");
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn reject_algo() -> Result<(), BenchmarkError> {
+        let origin = T::ApprovalOrigin::try_successful_origin()
+            .map_err(|_| BenchmarkError::Weightless)?;
+        let owner = funded_account::<T>("owner", 0);
+        let algorithm_id = watched_algorithm::<T>(&owner);
+        Algorithms::<T>::mutate(algorithm_id, |algorithm| {
+            if let Some(algorithm) = algorithm {
+                algorithm.status = AlgoStatus::PendingApproval;
+            }
+        });
+
+        #[extrinsic_call]
+        reject_algo(origin as T::RuntimeOrigin, algorithm_id);
+
+        let algorithm = Algorithms::<T>::get(algorithm_id).ok_or("Algorithm was removed")?;
+        ensure!(algorithm.status == AlgoStatus::Disabled, "Algorithm was not rejected");
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn set_input_tier() -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let (algorithm_id, _) = active_algorithm::<T>(&caller);
+
+        #[extrinsic_call]
+        set_input_tier(RawOrigin::Signed(caller), algorithm_id, 0, VerificationTier::Verified);
+
+        ensure!(InputTiers::<T>::get(algorithm_id, 0) == VerificationTier::Verified, "Tier was not set");
+
+        Ok(())
+    }
+
+    /// Running a module for `k` thousand units of gas, which `run_algo_for` and the other
+    /// runs add to their own weight for the gas they used.
+    #[benchmark]
+    fn execute(k: Linear<0, { (T::MaxGasLimit::get() / 1_000) as u32 }>) -> Result<(), BenchmarkError> {
+        let code = looping_module();
+        let failure;
+
+        #[block]
+        {
+            failure = Pallet::<T>::run_code(code, Vec::new(), Vec::new(), Vec::new(), k as u64 * 1_000, None).err();
+        }
+
+        ensure!(matches!(failure, Some(RunFailure { error: Error::<T>::OutOfGas, .. })), "Module did not run out of gas");
+
+        Ok(())
+    }
+
+    impl_benchmark_test_suite!(Pallet, crate::tests::new_test_ext(), crate::tests::Test);
+}
@@ -0,0 +1,65 @@
+//! Fixed-point math exposed to algorithms as `host.fixed_*` functions.
+//!
+//! Values cross the module boundary as the inner `i64` of a [`FixedI64`], i.e. scaled by
+//! `10^9`. Everything here is integer arithmetic, so results are the same on every node.
+//! `None` means the operation overflowed or is undefined for its input.
+
+use sp_runtime::{
+    traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, One, Zero},
+    FixedI64, FixedPointNumber,
+};
+
+/// `ln(2)` scaled by `10^9`.
+const LN_2: FixedI64 = FixedI64::from_inner(693_147_181);
+
+pub fn mul(a: i64, b: i64) -> Option<i64> {
+    FixedI64::from_inner(a).checked_mul(&FixedI64::from_inner(b)).map(FixedI64::into_inner)
+}
+
+pub fn div(a: i64, b: i64) -> Option<i64> {
+    FixedI64::from_inner(a).checked_div(&FixedI64::from_inner(b)).map(FixedI64::into_inner)
+}
+
+pub fn sqrt(a: i64) -> Option<i64> {
+    FixedI64::from_inner(a).try_sqrt().map(FixedI64::into_inner)
+}
+
+pub fn ln(a: i64) -> Option<i64> {
+    let x = FixedI64::from_inner(a);
+    if x <= FixedI64::zero() {
+        return None;
+    }
+
+    let one = FixedI64::one();
+    let two = FixedI64::saturating_from_integer(2);
+
+    // Write x as m * 2^k with 1 <= m < 2, so ln(x) = ln(m) + k * ln(2).
+    let mut m = x;
+    let mut k = 0i64;
+    while m >= two {
+        m = m.checked_div(&two)?;
+        k += 1;
+    }
+    while m < one {
+        m = m.checked_mul(&two)?;
+        k -= 1;
+    }
+
+    // ln(m) = 2 * atanh(z) = 2 * (z + z^3/3 + z^5/5 + ...) with z = (m - 1) / (m + 1),
+    // and 0 <= z < 1/3, so the terms vanish after a dozen iterations.
+    let z = m.checked_sub(&one)?.checked_div(&m.checked_add(&one)?)?;
+    let z_squared = z.checked_mul(&z)?;
+
+    let mut sum = FixedI64::zero();
+    let mut power = z;
+    let mut n = 1i64;
+    while !power.is_zero() {
+        sum = sum.checked_add(&power.checked_div(&FixedI64::saturating_from_integer(n))?)?;
+        power = power.checked_mul(&z_squared)?;
+        n += 2;
+    }
+
+    sum.checked_mul(&two)?
+        .checked_add(&LN_2.checked_mul(&FixedI64::saturating_from_integer(k))?)
+        .map(FixedI64::into_inner)
+}
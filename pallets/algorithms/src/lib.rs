@@ -2,80 +2,477 @@
 
 pub use pallet::*;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod fixed;
+pub mod migrations;
+mod validation;
+pub mod weights;
+
+#[cfg(test)]
+mod tests;
+
+/// Key type of the keys offchain workers sign algorithm run outcomes with.
+pub const KEY_TYPE: sp_runtime::KeyTypeId = sp_runtime::KeyTypeId(*b"algo");
+
+/// Keys offchain workers sign algorithm run outcomes with. An authority adds its key to the
+/// node keystore with `key insert --key-type algo`.
+pub mod crypto {
+    use super::KEY_TYPE;
+    use sp_runtime::{
+        app_crypto::{app_crypto, sr25519},
+        MultiSignature, MultiSigner,
+    };
+
+    app_crypto!(sr25519, KEY_TYPE);
+
+    pub struct AuthorityId;
+
+    impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for AuthorityId {
+        type RuntimeAppPublic = Public;
+        type GenericSignature = sp_core::sr25519::Signature;
+        type GenericPublic = sp_core::sr25519::Public;
+    }
+}
+
 #[frame_support::pallet]
 pub mod pallet {
     use log;
     use frame_support::{dispatch, dispatch::*, pallet_prelude::*};
     use frame_system::pallet_prelude::*;
+    use frame_system::offchain::{AppCrypto, CreateSignedTransaction, SendSignedTransaction, Signer};
+    use sp_runtime::offchain::storage::{StorageRetrievalError, StorageValueRef};
+    use sp_runtime::traits::IdentifyAccount;
+    use sp_runtime::RuntimeAppPublic;
     use scale_info::prelude;
     use sp_runtime::{FixedI64, FixedPointNumber, Rounding};
-    use wasmi::{self, core::F64, Value};
+    use wasmi::{self, core::TrapCode};
     use sp_runtime::Vec;
-    use sp_runtime::traits::Hash;
+    use sp_runtime::traits::{Hash, Saturating, Zero};
+    use frame_support::traits::{Currency, ExistenceRequirement, ReservableCurrency};
+    use frame_support::storage::with_storage_layer;
     use wasmi::{Func, Caller};
     use pallet_credentials::Schemas;
-    use wasmi::core::Trap;
 
     use pallet_credentials::{self as credentials, Attestations, CredAttestation, CredSchema, AcquirerAddress};
+    use pallet_issuers::VerificationTier;
+    use sp_std::vec;
 
     use super::*;
+    pub use weights::WeightInfo;
 
-    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
-    pub struct GasMeter {
-        pub consumed: u64,
-        pub limit: u64,
+    pub type BalanceOf<T> = <<T as pallet_issuers::Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+    /// The in-code storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+    /// Longest string read from module memory for diagnostics, in bytes.
+    const MAX_DEBUG_STRING_LENGTH: u32 = 256;
+
+    /// Blocks an offchain worker waits for its submitted outcome to land before sending it
+    /// again.
+    const RESUBMIT_AFTER: u32 = 10;
+
+    /// Data of the store an algorithm runs in. Gas is metered as the store's fuel.
+    pub struct HostState {
+        /// Bytes last passed to `host.return_data`.
+        pub return_data: Option<Vec<u8>>,
+        /// Set when the module called `env.abort`.
+        pub abort: Option<AbortInfo>,
+        /// Gas costs of the run, read once from `Parameters`.
+        pub gas_costs: GasCosts,
     }
 
-    impl GasMeter {
-        pub fn new(limit: u64) -> Self {
-            Self { 
-                consumed: 0, 
-                limit 
-            }
-        }
+    /// What a successful algorithm run produced.
+    #[derive(Clone, PartialEq, RuntimeDebug)]
+    pub struct RunOutput {
+        pub result: i64,
+        /// Structured result, for algorithms that declare an output schema.
+        pub data: Option<Vec<u8>>,
+        pub gas_used: u64,
+    }
 
-        pub fn charge(&mut self, amount: u64) -> Result<(), DispatchError> {
-            self.consumed = self.consumed.checked_add(amount)
-                .ok_or(DispatchError::Other("Gas Overflow"))?;
+    /// Why an algorithm run failed.
+    #[derive(RuntimeDebugNoBound)]
+    pub struct RunFailure<T: Config> {
+        pub error: Error<T>,
+        pub gas_used: u64,
+        pub abort: Option<AbortInfo>,
+    }
 
-            if self.consumed > self.limit {
-                return Err(DispatchError::Other("Out of Gas"));
-            }
-            Ok(())
+    impl<T: Config> From<Error<T>> for RunFailure<T> {
+        fn from(error: Error<T>) -> Self {
+            Self { error, gas_used: 0, abort: None }
         }
     }
 
+    /// A run queued by `request_algo_run` for offchain workers.
+    #[derive(CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound, Encode, Decode, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct RunRequest<T: Config> {
+        pub requester: T::AccountId,
+        pub algorithm_id: u64,
+        /// Module of the algorithm when the run was requested. An outcome is only accepted
+        /// while the algorithm still runs it.
+        pub code_hash: T::Hash,
+        pub issuer_hash: T::Hash,
+        pub account_id: Vec<u8>,
+        pub params: BoundedVec<u8, T::MaxParamsSize>,
+        pub requested_at: BlockNumberFor<T>,
+        /// Amount reserved from `requester`.
+        pub deposit: BalanceOf<T>,
+    }
+
+    /// A recurring run of an algorithm over the subjects in `ScheduledSubjects`.
+    #[derive(CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound, Encode, Decode, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct Schedule<T: Config> {
+        pub owner: T::AccountId,
+        pub algorithm_id: u64,
+        pub issuer_hash: T::Hash,
+        pub params: BoundedVec<u8, T::MaxParamsSize>,
+        /// Blocks between runs.
+        pub interval: BlockNumberFor<T>,
+        /// Block the schedule is next due at.
+        pub next_run: BlockNumberFor<T>,
+        /// Amount reserved from `owner`.
+        pub deposit: BalanceOf<T>,
+        /// Number of subjects in `ScheduledSubjects`.
+        pub subjects: u32,
+    }
+
+    /// A round of a schedule waiting for idle block weight.
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub struct ScheduledRun {
+        pub schedule_id: u64,
+        /// Raw storage key of the last subject run, if the round spans several blocks.
+        pub last_subject: Option<Vec<u8>>,
+    }
+
+    /// What an offchain worker got running a requested algorithm.
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub enum RunOutcome<Hash> {
+        Success {
+            result: i64,
+            data: Option<Vec<u8>>,
+            inputs: Vec<Option<InputRef<Hash>>>,
+        },
+        Failed {
+            error: DispatchError,
+            gas_used: u64,
+            abort: Option<AbortInfo>,
+        },
+    }
+
+    /// What an algorithm run reads, gathered before its module is loaded.
+    pub struct PreparedRun<T: Config> {
+        pub attestations: Vec<CredAttestation<T>>,
+        /// Where each input was found, `None` where a best-effort run found nothing.
+        pub inputs: Vec<Option<InputRef<T::Hash>>>,
+        pub code: Vec<u8>,
+        pub output_size: Option<u32>,
+    }
+
+    /// Arguments of an AssemblyScript `abort`, with the strings decoded to UTF-8.
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub struct AbortInfo {
+        pub message: Vec<u8>,
+        pub file: Vec<u8>,
+        pub line: u32,
+        pub column: u32,
+    }
+
+    /// Which issuers an algorithm input may be read from.
+    #[derive(CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound, Encode, Decode, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub enum IssuerSelector<T: Config> {
+        /// The issuer passed to `run_algo_for`.
+        Caller,
+        /// A fixed issuer, regardless of the one passed to `run_algo_for`.
+        Fixed(T::Hash),
+        /// The first issuer in the list that has attested the subject.
+        AnyOf(BoundedVec<T::Hash, T::MaxTrustedIssuers>),
+    }
+
+    /// An attestation read by an algorithm. Inputs are laid out in module memory in
+    /// declaration order.
+    #[derive(CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound, Encode, Decode, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct AlgoInput<T: Config> {
+        pub issuer: IssuerSelector<T>,
+        pub schema_hash: T::Hash,
+    }
+
+    /// What `run_algo_for` does when the subject has no attestation for an input.
+    #[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, Default)]
+    pub enum MissingInputPolicy {
+        /// Fail with `AttestationNotFound`.
+        #[default]
+        RequireAll,
+        /// Run anyway. Missing inputs are zero-filled so the offsets of later inputs don't
+        /// move, and the module can check `host.input_present` to tell them apart.
+        BestEffort,
+    }
+
+    /// What happens to an algorithm's stored scores when an attestation they may have read
+    /// is created or updated.
+    #[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, Default)]
+    pub enum RefreshPolicy {
+        /// Nothing. Updated inputs are still caught by `InvalidateStaleScores`.
+        #[default]
+        Keep,
+        /// Mark the scores dirty. Dirty scores aren't returned until the algorithm is run
+        /// again.
+        MarkDirty,
+        /// Mark the scores dirty and run the algorithm again for them in idle block weight.
+        /// Only for algorithms without parameters.
+        Recompute,
+    }
+
+    /// The attestation an algorithm run read for one of its inputs.
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub struct InputRef<Hash> {
+        pub issuer_hash: Hash,
+        pub schema_hash: Hash,
+        pub attestation_index: u32,
+        /// Hash of the attestation when it was read, used to detect later updates.
+        pub attestation_hash: Hash,
+    }
+
+    /// The latest result of an algorithm for a subject.
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub struct Score<Hash, BlockNumber> {
+        pub result: i64,
+        pub block_number: BlockNumber,
+        /// One entry per algorithm input, `None` where a best-effort run found no attestation.
+        pub inputs: Vec<Option<InputRef<Hash>>>,
+        /// Structured result laid out as the algorithm's output schema.
+        pub data: Option<Vec<u8>>,
+    }
+
+    #[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub enum AlgoStatus {
+        Active,
+        /// Can no longer be run or updated.
+        Disabled,
+        /// Saved, or given a new module, and waiting for `ApprovalOrigin` to approve it with
+        /// `approve_algo` before it can be run. Only while `RequireApproval` is set.
+        PendingApproval,
+    }
+
+    /// Bookkeeping for a module stored in `CodeByHash`.
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct CodeInfo<T: Config> {
+        /// Account that uploaded the code and paid its deposit.
+        pub owner: T::AccountId,
+        pub deposit: BalanceOf<T>,
+        /// Number of active algorithms using the code.
+        pub refcount: u32,
+    }
+
+    /// Fee an issuer charges runs for reading its attestations of a schema.
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct DataFee<T: Config> {
+        /// Account the fee is paid to.
+        pub payee: T::AccountId,
+        pub amount: BalanceOf<T>,
+    }
+
+    /// Prices and defaults `set_params` can change without a runtime upgrade. Deposits
+    /// already reserved keep the amount they were reserved with.
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct PalletParams<T: Config> {
+        pub gas_costs: GasCosts,
+        /// Gas limit of algorithms saved without one.
+        pub default_gas_limit: u64,
+        pub algorithm_deposit: BalanceOf<T>,
+        pub code_deposit_per_byte: BalanceOf<T>,
+        pub schedule_deposit: BalanceOf<T>,
+        pub run_request_deposit: BalanceOf<T>,
+    }
+
     #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
     #[scale_info(skip_type_params(T))]
     pub struct Algorithm<T: Config> {
-        pub schema_hashes: BoundedVec<T::Hash, T::MaxSchemas>,
-        pub code: BoundedVec<u8, T::MaxCodeSize>,
+        pub owner: T::AccountId,
+        pub name: BoundedVec<u8, T::MaxAlgoNameLength>,
+        /// Free-form description or a URI pointing to one.
+        pub description: BoundedVec<u8, T::MaxAlgoDescriptionLength>,
+        pub status: AlgoStatus,
+        /// Amount reserved from `owner`.
+        pub deposit: BalanceOf<T>,
+        pub inputs: BoundedVec<AlgoInput<T>, T::MaxSchemas>,
+        pub missing_inputs: MissingInputPolicy,
+        /// Key of the module in `CodeByHash`.
+        pub code_hash: T::Hash,
         pub gas_limit: u64,
+        /// Schema in `pallet_credentials::Schemas` describing the structured result. Such
+        /// algorithms are run through `calc_output` and must call `host.return_data`.
+        pub output_schema: Option<T::Hash>,
+        /// Schema in `pallet_credentials::Schemas` the `params` of `run_algo_for` must be
+        /// laid out as. Without one, `params` must be empty.
+        pub param_schema: Option<T::Hash>,
     }
 
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
     #[pallet::config]
-    pub trait Config: frame_system::Config + pallet_issuers::Config + pallet_credentials::Config {
+    pub trait Config:
+        CreateSignedTransaction<Call<Self>> + frame_system::Config + pallet_issuers::Config + pallet_credentials::Config
+    {
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
         type Hashing: Hash<Output = Self::Hash>;
 
+        /// Weights of the calls, and of running a module per thousand units of gas.
+        type WeightInfo: WeightInfo;
+
+        /// Keys offchain workers sign run outcomes with.
+        type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+
+        /// Origin allowed to set the offchain authorities.
+        type AuthorityOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Origin allowed to change the gas costs, deposits and default gas limit.
+        type ParamsOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Origin allowed to approve or reject algorithms before they can be run.
+        type ApprovalOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Whether saved algorithms, and algorithms given a new module, wait for
+        /// `ApprovalOrigin` to approve them before they can be run. Without it they can be
+        /// run right away.
+        #[pallet::constant]
+        type RequireApproval: Get<bool>;
+
+        #[pallet::constant]
+        type MaxOffchainAuthorities: Get<u32>;
+
+        /// Number of offchain authorities that must submit the same outcome for a requested
+        /// run before it is accepted.
+        #[pallet::constant]
+        type RequiredAgreements: Get<u32>;
+
+        /// Maximum number of requested runs waiting for offchain workers.
+        #[pallet::constant]
+        type MaxRunRequests: Get<u32>;
+
+        /// Deposit reserved from the requester of a run until offchain authorities agree on
+        /// its outcome or it expires, until `set_params` changes it.
+        #[pallet::constant]
+        type RunRequestDeposit: Get<BalanceOf<Self>>;
+
+        /// Blocks a requested run waits for offchain authorities to agree on its outcome
+        /// before it is dropped. Must be at least one.
+        #[pallet::constant]
+        type RunRequestTimeout: Get<BlockNumberFor<Self>>;
+
+        /// Gas limit offchain workers run requested algorithms with, in place of the
+        /// algorithm's own.
+        #[pallet::constant]
+        type OffchainGasLimit: Get<u64>;
+
+        /// Maximum number of subjects `run_algo_batch` and the schedule calls take at once.
+        #[pallet::constant]
+        type MaxBatchSize: Get<u32>;
+
+        #[pallet::constant]
+        type MaxSchedules: Get<u32>;
+
+        #[pallet::constant]
+        type MaxScheduledSubjects: Get<u32>;
+
+        #[pallet::constant]
+        type MinScheduleInterval: Get<BlockNumberFor<Self>>;
+
+        /// Deposit reserved from a schedule owner, until `set_params` changes it.
+        #[pallet::constant]
+        type ScheduleDeposit: Get<BalanceOf<Self>>;
+
+        /// Maximum number of algorithms refreshing their scores on changes to one schema.
+        #[pallet::constant]
+        type MaxSchemaWatchers: Get<u32>;
+
+        /// Maximum number of issuers whose scores of a subject are refreshed per algorithm.
+        #[pallet::constant]
+        type MaxScoreIssuers: Get<u32>;
+
+        /// Deposit reserved from the owner of an algorithm whose refresh policy is other than
+        /// `RefreshPolicy::Keep`, as it takes one of the `MaxSchemaWatchers` slots of each
+        /// schema it reads.
+        #[pallet::constant]
+        type RefreshDeposit: Get<BalanceOf<Self>>;
+
         #[pallet::constant]
         type MaxSchemas: Get<u32>;
 
+        /// Maximum number of issuers an `IssuerSelector::AnyOf` input may list.
+        #[pallet::constant]
+        type MaxTrustedIssuers: Get<u32>;
+
         #[pallet::constant]
         type MaxCodeSize: Get<u32>;
 
         #[pallet::constant]
         type MaxMemoryPages: Get<u32>;
 
+        /// Maximum size of the structured result an algorithm may return.
+        #[pallet::constant]
+        type MaxReturnDataSize: Get<u32>;
+
+        /// Maximum size of the parameters `run_algo_for` passes to an algorithm.
+        #[pallet::constant]
+        type MaxParamsSize: Get<u32>;
+
+        #[pallet::constant]
+        type MaxAlgoNameLength: Get<u32>;
+
+        #[pallet::constant]
+        type MaxAlgoDescriptionLength: Get<u32>;
+
+        /// Deposit reserved from an algorithm owner, until `set_params` changes it.
+        #[pallet::constant]
+        type AlgorithmDeposit: Get<BalanceOf<Self>>;
+
+        /// Deposit reserved from the uploader of a module per byte of code, until
+        /// `set_params` changes it.
+        #[pallet::constant]
+        type CodeDepositPerByte: Get<BalanceOf<Self>>;
+
+        /// Gas limit of algorithms saved without one, until `set_params` changes it.
         #[pallet::constant]
         type DefaultGasLimit: Get<u64>;
 
+        /// Highest gas limit an algorithm may be saved or updated with, and run with in a
+        /// block. A run using this much gas must fit in a block, which `integrity_test`
+        /// checks with `WeightInfo::execute`.
+        #[pallet::constant]
+        type MaxGasLimit: Get<u64>;
+
+        /// Gas charged by host functions, until `set_params` changes it.
         #[pallet::constant]
         type GasCost: Get<GasCosts>;
+
+        /// Bounds on the functions, globals and tables of uploaded modules.
+        #[pallet::constant]
+        type ModuleLimits: Get<ModuleLimits>;
+
+        /// Whether modules may use floating-point instructions. Float results can differ
+        /// between nodes (e.g. NaN bit patterns), so runtimes should leave this off and have
+        /// algorithms use the `host.fixed_*` functions instead.
+        #[pallet::constant]
+        type AllowFloats: Get<bool>;
+
+        /// Whether a stored score stops being returned once one of the attestations it was
+        /// computed from is updated or removed.
+        #[pallet::constant]
+        type InvalidateStaleScores: Get<bool>;
     }
 
     #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
@@ -85,28 +482,392 @@ pub mod pallet {
         pub call_op: u64,
     }
 
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+    pub struct ModuleLimits {
+        /// Functions defined by the module, not counting imports.
+        pub functions: u32,
+        pub globals: u32,
+        pub tables: u32,
+        /// Initial and maximum size of each table.
+        pub table_elements: u32,
+    }
+
     #[pallet::storage]
     pub type Algorithms<T: Config> =
     StorageMap<_, Blake2_128Concat, u64 /*algoId*/, Algorithm<T>, OptionQuery>;
 
+    /// Latest result of each algorithm, per issuer it was run with and subject.
+    #[pallet::storage]
+    pub type Scores<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Blake2_128Concat, u64 /*algoId*/>,
+            NMapKey<Twox64Concat, T::Hash>,
+            NMapKey<Blake2_128Concat, AcquirerAddress>,
+        ),
+        Score<T::Hash, BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// Accounts algorithms were offered to with `transfer_algo_ownership`, until they accept
+    /// with `accept_algo_ownership`.
+    #[pallet::storage]
+    pub type PendingOwners<T: Config> = StorageMap<_, Blake2_128Concat, u64, T::AccountId, OptionQuery>;
+
+    /// Scores marked dirty by an attestation change, with the block they were marked at.
+    #[pallet::storage]
+    pub type DirtyScores<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Blake2_128Concat, u64>,
+            NMapKey<Twox64Concat, T::Hash>,
+            NMapKey<Blake2_128Concat, AcquirerAddress>,
+        ),
+        BlockNumberFor<T>,
+        OptionQuery,
+    >;
+
+    /// Issuers each subject has a score from per algorithm, latest last, so scores from
+    /// `Fixed` and `AnyOf` inputs can be found when refreshing.
+    #[pallet::storage]
+    pub type ScoreIssuers<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        u64,
+        Blake2_128Concat,
+        AcquirerAddress,
+        BoundedVec<T::Hash, T::MaxScoreIssuers>,
+        ValueQuery,
+    >;
+
+    #[pallet::storage]
+    pub type RefreshPolicies<T: Config> = StorageMap<_, Blake2_128Concat, u64, RefreshPolicy, ValueQuery>;
+
+    /// Amount reserved from the owner of an algorithm for its refresh policy.
+    #[pallet::storage]
+    pub type RefreshDeposits<T: Config> = StorageMap<_, Blake2_128Concat, u64, BalanceOf<T>, OptionQuery>;
+
+    /// Dirty scores of algorithms with `RefreshPolicy::Recompute`, waiting for idle block
+    /// weight to be run again.
+    #[pallet::storage]
+    pub type RecomputeQueue<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Blake2_128Concat, u64>,
+            NMapKey<Twox64Concat, T::Hash>,
+            NMapKey<Blake2_128Concat, AcquirerAddress>,
+        ),
+        (),
+        OptionQuery,
+    >;
+
+    /// Algorithms with a refresh policy other than `Keep`, by the schemas of their inputs.
+    #[pallet::storage]
+    pub type SchemaWatchers<T: Config> =
+    StorageMap<_, Identity, T::Hash, BoundedVec<u64, T::MaxSchemaWatchers>, ValueQuery>;
+
+    /// Fee paid to an algorithm's owner for each successful run.
+    #[pallet::storage]
+    pub type RunFees<T: Config> = StorageMap<_, Blake2_128Concat, u64, BalanceOf<T>, OptionQuery>;
+
+    /// Fee paid for each successful run reading an attestation, by issuer and schema.
+    #[pallet::storage]
+    pub type DataFees<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, T::Hash, DataFee<T>, OptionQuery>;
+
+    /// Tier an issuer needs for its attestations to be read as an input, by algorithm and
+    /// input index, for inputs requiring more than `Unverified`.
+    #[pallet::storage]
+    pub type InputTiers<T: Config> =
+    StorageDoubleMap<_, Blake2_128Concat, u64, Twox64Concat, u32, VerificationTier, ValueQuery>;
+
+    /// Algorithm modules, stored once per distinct code.
+    #[pallet::storage]
+    pub type CodeByHash<T: Config> =
+    StorageMap<_, Identity, T::Hash, BoundedVec<u8, T::MaxCodeSize>, OptionQuery>;
+
+    #[pallet::storage]
+    pub type CodeInfoOf<T: Config> =
+    StorageMap<_, Identity, T::Hash, CodeInfo<T>, OptionQuery>;
+
+    /// Accounts whose offchain workers may submit outcomes of requested runs.
+    #[pallet::storage]
+    pub type OffchainAuthorities<T: Config> =
+    StorageValue<_, BoundedVec<T::AccountId, T::MaxOffchainAuthorities>, ValueQuery>;
+
+    /// Runs waiting for offchain authorities to agree on their outcome.
+    #[pallet::storage]
+    pub type RunRequests<T: Config> =
+    CountedStorageMap<_, Blake2_128Concat, u64, RunRequest<T>, OptionQuery>;
+
+    #[pallet::storage]
+    pub type NextRunRequestId<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// Requested runs expiring at a block, unless their outcome is agreed on first.
+    #[pallet::storage]
+    pub type RunRequestExpiries<T: Config> =
+    StorageMap<_, Twox64Concat, BlockNumberFor<T>, BoundedVec<u64, T::MaxRunRequests>, ValueQuery>;
+
+    /// Authorities that submitted an outcome for a requested run, with its hash.
+    #[pallet::storage]
+    pub type RunSubmissions<T: Config> =
+    StorageMap<_, Blake2_128Concat, u64, BoundedVec<(T::AccountId, T::Hash), T::MaxOffchainAuthorities>, ValueQuery>;
+
+    #[pallet::storage]
+    pub type Schedules<T: Config> =
+    CountedStorageMap<_, Blake2_128Concat, u64, Schedule<T>, OptionQuery>;
+
+    #[pallet::storage]
+    pub type NextScheduleId<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// Subjects a schedule runs its algorithm for.
+    #[pallet::storage]
+    pub type ScheduledSubjects<T: Config> =
+    StorageDoubleMap<_, Blake2_128Concat, u64, Blake2_128Concat, AcquirerAddress, (), OptionQuery>;
+
+    /// Schedules due at a block.
+    #[pallet::storage]
+    pub type ScheduleAgenda<T: Config> =
+    StorageMap<_, Twox64Concat, BlockNumberFor<T>, BoundedVec<u64, T::MaxSchedules>, ValueQuery>;
+
+    /// Rounds of due schedules, run in order as idle block weight allows.
+    #[pallet::storage]
+    pub type ScheduledRunQueue<T: Config> =
+    StorageValue<_, BoundedVec<ScheduledRun, T::MaxSchedules>, ValueQuery>;
+
     #[pallet::type_value]
     pub fn DefaultNextAlgoId<T: Config>() -> u64 { 100u64 }
 
+    #[pallet::type_value]
+    pub fn DefaultParams<T: Config>() -> PalletParams<T> {
+        PalletParams {
+            gas_costs: T::GasCost::get(),
+            default_gas_limit: T::DefaultGasLimit::get(),
+            algorithm_deposit: T::AlgorithmDeposit::get(),
+            code_deposit_per_byte: T::CodeDepositPerByte::get(),
+            schedule_deposit: T::ScheduleDeposit::get(),
+            run_request_deposit: T::RunRequestDeposit::get(),
+        }
+    }
+
+    /// Current gas costs, deposits and default gas limit, the `Config` values until
+    /// `set_params` is called.
+    #[pallet::storage]
+    pub type Parameters<T: Config> = StorageValue<_, PalletParams<T>, ValueQuery, DefaultParams<T>>;
+
     #[pallet::storage]
     pub type NextAlgoId<T: Config> = StorageValue<_, u64, ValueQuery, DefaultNextAlgoId<T>>;
 
+    #[pallet::genesis_config]
+    #[derive(frame_support::DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// Algorithms to create, as `(owner, name, description, schema_hashes, code)`. Like
+        /// those saved with `save_algo`, they read their inputs from the issuer they are run
+        /// with. They get consecutive ids from `NextAlgoId`, and no deposit is reserved for
+        /// them or their code.
+        pub algorithms: Vec<(T::AccountId, Vec<u8>, Vec<u8>, Vec<T::Hash>, Vec<u8>)>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        fn build(&self) {
+            for (owner, name, description, schema_hashes, code) in &self.algorithms {
+                Pallet::<T>::validate_code(code).expect("Invalid genesis algorithm code");
+
+                let code_hash = <T as Config>::Hashing::hash(code);
+                if !CodeByHash::<T>::contains_key(code_hash) {
+                    CodeByHash::<T>::insert(code_hash, BoundedVec::<u8, T::MaxCodeSize>::truncate_from(code.clone()));
+                    CodeInfoOf::<T>::insert(code_hash, CodeInfo { owner: owner.clone(), deposit: Zero::zero(), refcount: 0 });
+                }
+                Pallet::<T>::increment_refcount(code_hash).expect("Code stored above");
+
+                let inputs = schema_hashes.iter()
+                    .map(|schema_hash| AlgoInput { issuer: IssuerSelector::Caller, schema_hash: *schema_hash })
+                    .collect::<Vec<_>>();
+
+                let id = NextAlgoId::<T>::get();
+                NextAlgoId::<T>::set(id + 1);
+
+                Algorithms::<T>::insert(id, Algorithm {
+                    owner: owner.clone(),
+                    name: BoundedVec::try_from(name.clone()).expect("Genesis algorithm name too long"),
+                    description: BoundedVec::try_from(description.clone()).expect("Genesis algorithm description too long"),
+                    status: AlgoStatus::Active,
+                    deposit: Zero::zero(),
+                    inputs: BoundedVec::try_from(inputs).expect("Too many genesis algorithm inputs"),
+                    missing_inputs: MissingInputPolicy::RequireAll,
+                    code_hash,
+                    gas_limit: Parameters::<T>::get().default_gas_limit,
+                    output_schema: None,
+                    param_schema: None,
+                });
+            }
+        }
+    }
+
     #[pallet::event]
     #[pallet::generate_deposit(pub (super) fn deposit_event)]
     pub enum Event<T: Config> {
         AlgorithmAdded {
             algorithm_id: u64,
-            schema_hashes: Vec<T::Hash>,
+            owner: T::AccountId,
+            inputs: Vec<AlgoInput<T>>,
+        },
+        AlgorithmUpdated {
+            algorithm_id: u64,
+            code_hash: T::Hash,
+        },
+        CodeUploaded {
+            code_hash: T::Hash,
+            owner: T::AccountId,
+        },
+        CodeRemoved {
+            code_hash: T::Hash,
+        },
+        AlgorithmDisabled {
+            algorithm_id: u64,
+        },
+        AlgorithmApproved {
+            algorithm_id: u64,
+        },
+        /// The algorithm was disabled instead of approved, and its deposit returned.
+        AlgorithmRejected {
+            algorithm_id: u64,
+        },
+        /// `to` may take over the algorithm with `accept_algo_ownership`.
+        AlgorithmOwnershipOffered {
+            algorithm_id: u64,
+            from: T::AccountId,
+            to: T::AccountId,
+        },
+        AlgorithmOwnershipTransferred {
+            algorithm_id: u64,
+            from: T::AccountId,
+            to: T::AccountId,
         },
         AlgoResult {
+            algorithm_id: u64,
             result: i64,
-            issuer_hash: T::Hash, 
-            account_id: Vec<u8>
+            issuer_hash: T::Hash,
+            account_id: Vec<u8>,
+            data: Option<Vec<u8>>,
+        },
+        /// A run failed once its module was loaded, or a batch or scheduled run could not
+        /// start. `abort` is set if the module aborted.
+        AlgoFailed {
+            algorithm_id: u64,
+            issuer_hash: T::Hash,
+            account_id: Vec<u8>,
+            error: DispatchError,
+            gas_used: u64,
+            abort: Option<AbortInfo>,
+        },
+        AlgoRunRequested {
+            request_id: u64,
+            algorithm_id: u64,
+            issuer_hash: T::Hash,
+            account_id: Vec<u8>,
+        },
+        AlgoRunSubmitted {
+            request_id: u64,
+            authority: T::AccountId,
+        },
+        /// No outcome was agreed on within `RunRequestTimeout`, so the request was dropped
+        /// and its deposit returned.
+        AlgoRunExpired {
+            request_id: u64,
+        },
+        OffchainAuthoritiesSet {
+            authorities: Vec<T::AccountId>,
+        },
+        /// A batch ran. Each subject's result is in its `AlgoResult` or `AlgoFailed` event.
+        AlgoBatchRun {
+            algorithm_id: u64,
+            issuer_hash: T::Hash,
+            succeeded: u32,
+            failed: u32,
+        },
+        ScheduleCreated {
+            schedule_id: u64,
+            owner: T::AccountId,
+            algorithm_id: u64,
+            issuer_hash: T::Hash,
+            interval: BlockNumberFor<T>,
+        },
+        ScheduledSubjectsAdded {
+            schedule_id: u64,
+            accounts: Vec<Vec<u8>>,
+        },
+        ScheduledSubjectsRemoved {
+            schedule_id: u64,
+            accounts: Vec<Vec<u8>>,
+        },
+        ScheduleCancelled {
+            schedule_id: u64,
+        },
+        /// A schedule ran for all of its subjects.
+        ScheduledRunCompleted {
+            schedule_id: u64,
+        },
+        RefreshPolicySet {
+            algorithm_id: u64,
+            policy: RefreshPolicy,
+        },
+        RunFeeSet {
+            algorithm_id: u64,
+            fee: Option<BalanceOf<T>>,
+        },
+        DataFeeSet {
+            issuer_hash: T::Hash,
+            schema_hash: T::Hash,
+            fee: Option<DataFee<T>>,
+        },
+        ParamsSet {
+            params: PalletParams<T>,
         },
+        InputTierSet {
+            algorithm_id: u64,
+            input_index: u32,
+            tier: VerificationTier,
+        },
+        /// `payer` paid the fees of a run, `total` split as `shares` between the algorithm
+        /// owner and the payees of data fees.
+        RevenueSplit {
+            algorithm_id: u64,
+            payer: T::AccountId,
+            total: BalanceOf<T>,
+            shares: Vec<(T::AccountId, BalanceOf<T>)>,
+        },
+        /// An attestation change left scores of `account_id` from these issuers dirty.
+        ScoresMarkedDirty {
+            algorithm_id: u64,
+            account_id: Vec<u8>,
+            issuer_hashes: Vec<T::Hash>,
+        },
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(block_number: BlockNumberFor<T>) -> Weight {
+            Self::queue_due_schedules(block_number)
+                .saturating_add(Self::expire_run_requests(block_number))
+        }
+
+        fn on_idle(_block_number: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            let used = Self::run_scheduled(remaining_weight);
+            used.saturating_add(Self::recompute_dirty(remaining_weight.saturating_sub(used)))
+        }
+
+        fn offchain_worker(block_number: BlockNumberFor<T>) {
+            Self::run_requested_algorithms(block_number);
+        }
+
+        fn integrity_test() {
+            assert!(
+                Self::subject_run_weight().all_lte(<T as frame_system::Config>::BlockWeights::get().max_block),
+                "A run with `MaxGasLimit` gas must fit in a block",
+            );
+        }
     }
 
     #[pallet::error]
@@ -120,203 +881,2110 @@ pub mod pallet {
         AcmFailedToFindCalcFunction,
         AcmFailedToCalculate,
         InvalidWasmProvided,
+        FloatsNotAllowed,
+        ForbiddenImport,
+        InvalidImportSignature,
+        MissingEntryPoint,
+        InvalidEntryPoint,
+        StartFunctionNotAllowed,
+        UnsupportedFeature,
+        MemoryTooLarge,
+        /// An imported memory caps its size below the `MaxMemoryPages` it is linked with.
+        MemoryTooSmall,
+        TooManyFunctions,
+        TooManyGlobals,
+        TooManyTables,
+        TableTooLarge,
         TooManySchemas,
         CodeTooHeavy,
         SchemaNotFound,
+        NoTrustedIssuers,
+        NotAlgoOwner,
+        AlgoDisabled,
+        AlgoNameTooLong,
+        AlgoDescriptionTooLong,
+        InsufficientBalance,
+        GasLimitTooHigh,
+        NotPendingOwner,
+        CodeNotFound,
+        CodeAlreadyExists,
+        CodeInUse,
+        NotCodeOwner,
+        SchemaHasText,
+        OutputSchemaTooLarge,
+        ParamSchemaTooLarge,
+        ParamsMismatch,
+        MissingReturnData,
+        ReturnDataMismatch,
 
         AlgoExecutionFailed,
         TooComplexModule,
         OutOfGas,
         GasOverflow,
         GasMeteringNotSupported,
+        AlgoAborted,
+        NotOffchainAuthority,
+        RunRequestNotFound,
+        AlreadySubmitted,
+        TooManyRunRequests,
+        ScheduleNotFound,
+        NotScheduleOwner,
+        TooManySchedules,
+        TooManyScheduledSubjects,
+        ScheduleIntervalTooShort,
+        TooManySchemaWatchers,
+        /// The subject already has scores of the algorithm from `MaxScoreIssuers` issuers.
+        TooManyScoreIssuers,
+        /// `set_params` was given a zero or out of range gas cost, gas limit or deposit.
+        InvalidParams,
+        /// The algorithm must be approved with `approve_algo` before it can be run.
+        AlgoNotApproved,
+        AlgoNotPendingApproval,
+        RecomputeWithParams,
+        InvalidInputIndex,
+        IssuerTierTooLow,
+        OutcomeTooLarge,
+        /// The algorithm was given a new module after the run was requested.
+        AlgoCodeChanged,
     }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         #[pallet::call_index(1)]
-        #[pallet::weight(100_000)]
+        #[pallet::weight(T::WeightInfo::save_algo(schema_hashes.len() as u32, code.len() as u32))]
         pub fn save_algo(origin: OriginFor<T>, schema_hashes: Vec<T::Hash>, code: Vec<u8>, gas_limit: Option<u64>) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
-            ensure!(schema_hashes.len() <= T::MaxSchemas::get() as usize, Error::<T>::TooManySchemas);
+            let inputs = schema_hashes.into_iter()
+                .map(|schema_hash| AlgoInput { issuer: IssuerSelector::Caller, schema_hash })
+                .collect();
 
-            ensure!(code.len() <= T::MaxCodeSize::get() as usize, Error::<T>::CodeTooHeavy);
-
-            let engine = wasmi::Engine::default();
-    
-            // Just validate without storing the module
-            wasmi::Module::new(&engine, code.as_slice())
-                .map_err(|_| Error::<T>::InvalidWasmProvided)?;
-
-            let id = NextAlgoId::<T>::get();
-            NextAlgoId::<T>::set(id + 1);
+            let code_hash = Self::upload_if_missing(&who, code)?;
 
+            Self::do_save_algo(who, Vec::new(), Vec::new(), inputs, MissingInputPolicy::RequireAll, code_hash, gas_limit, None, None)
+        }
 
+        /// Run an algorithm for `account_id` and store the result as its score. A module that
+        /// fails once loaded doesn't fail the call; the failure is reported by an `AlgoFailed`
+        /// event instead. A successful run charges the caller the fees set with
+        /// `set_run_fee` and `set_data_fee`. The call is weighed for `MaxGasLimit` gas, and
+        /// the gas the run didn't use is refunded.
+        #[pallet::call_index(2)]
+        #[pallet::weight(Pallet::<T>::subject_run_weight())]
+        pub fn run_algo_for(
+            origin: OriginFor<T>,
+            issuer_hash: T::Hash,
+            account_id: Vec<u8>,
+            algorithm_id: u64,
+            params: BoundedVec<u8, T::MaxParamsSize>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
 
-            Algorithms::<T>::insert(id, Algorithm {
-                schema_hashes: BoundedVec::try_from(schema_hashes.clone()).map_err(|_| Error::<T>::TooManySchemas)?,
-                code: BoundedVec::try_from(code).map_err(|_| Error::<T>::CodeTooHeavy)?,
-                gas_limit: gas_limit.unwrap_or_else(|| T::DefaultGasLimit::get()),
-            });
+            let algorithm = Self::runnable_algorithm(algorithm_id, params.len())?;
 
-            Self::deposit_event(Event::AlgorithmAdded {
-                algorithm_id: id,
-                schema_hashes: schema_hashes,
-            });
+            // A failed run still succeeds so its event isn't rolled back with the call
+            let (_, gas_used) =
+                Self::run_and_record(Some(&who), algorithm_id, &algorithm, issuer_hash, account_id, params.into_inner())?;
 
-            Ok(())
+            Ok(Some(Self::run_weight(algorithm.inputs.len(), gas_used)).into())
         }
 
-        #[pallet::call_index(2)]
-        #[pallet::weight(100_000)]
-        pub fn run_algo_for(origin: OriginFor<T>, issuer_hash: T::Hash, account_id: Vec<u8>, algorithm_id: u64) -> DispatchResult {
+        /// Save an algorithm whose inputs may come from issuers other than the one
+        /// `run_algo_for` is called with. With an `output_schema`, the algorithm also returns
+        /// a structured result laid out as that schema. With a `param_schema`, it is run with
+        /// parameters laid out as that schema.
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::save_algo_with_inputs(inputs.len() as u32, code.len() as u32))]
+        #[allow(clippy::too_many_arguments)]
+        pub fn save_algo_with_inputs(
+            origin: OriginFor<T>,
+            name: Vec<u8>,
+            description: Vec<u8>,
+            inputs: Vec<AlgoInput<T>>,
+            missing_inputs: MissingInputPolicy,
+            code: Vec<u8>,
+            gas_limit: Option<u64>,
+            output_schema: Option<T::Hash>,
+            param_schema: Option<T::Hash>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let code_hash = Self::upload_if_missing(&who, code)?;
+
+            Self::do_save_algo(who, name, description, inputs, missing_inputs, code_hash, gas_limit, output_schema, param_schema)
+        }
+
+        /// Change the metadata, module or gas limit of an algorithm. `code_hash` must have
+        /// been uploaded already, and export the entry point the algorithm is run through.
+        /// The deposit is adjusted to the size of the new module, and with `RequireApproval`
+        /// the algorithm waits for approval again.
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::update_algo())]
+        pub fn update_algo(
+            origin: OriginFor<T>,
+            algorithm_id: u64,
+            name: Option<Vec<u8>>,
+            description: Option<Vec<u8>>,
+            code_hash: Option<T::Hash>,
+            gas_limit: Option<u64>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut algorithm = Self::owned_active_algorithm(&who, algorithm_id)?;
+
+            if let Some(name) = name {
+                algorithm.name = BoundedVec::try_from(name).map_err(|_| Error::<T>::AlgoNameTooLong)?;
+            }
+
+            if let Some(description) = description {
+                algorithm.description = BoundedVec::try_from(description)
+                    .map_err(|_| Error::<T>::AlgoDescriptionTooLong)?;
+            }
+
+            if let Some(code_hash) = code_hash {
+                if code_hash != algorithm.code_hash {
+                    Self::check_entry_point(code_hash, algorithm.output_schema.is_some())?;
+
+                    let deposit = Self::algorithm_deposit(code_hash)?;
+                    Self::adjust_deposit(&who, algorithm.deposit, deposit)?;
+                    algorithm.deposit = deposit;
+
+                    Self::increment_refcount(code_hash)?;
+                    Self::decrement_refcount(algorithm.code_hash);
+                    algorithm.code_hash = code_hash;
+                    algorithm.status = Self::unapproved_status();
+                }
+            }
+
+            if let Some(gas_limit) = gas_limit {
+                ensure!(gas_limit <= T::MaxGasLimit::get(), Error::<T>::GasLimitTooHigh);
+                algorithm.gas_limit = gas_limit;
+            }
+
+            let code_hash = algorithm.code_hash;
+            Algorithms::<T>::insert(algorithm_id, algorithm);
+
+            Self::deposit_event(Event::AlgorithmUpdated { algorithm_id, code_hash });
+
+            Ok(())
+        }
+
+        /// Permanently stop an algorithm from being run or updated and return its deposit.
+        /// Its stored scores are kept, and its module no longer counts as in use.
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::disable_algo())]
+        pub fn disable_algo(origin: OriginFor<T>, algorithm_id: u64) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let algorithm = Self::owned_active_algorithm(&who, algorithm_id)?;
+            Self::do_disable(algorithm_id, algorithm);
+
+            Self::deposit_event(Event::AlgorithmDisabled { algorithm_id });
+
+            Ok(())
+        }
+
+        /// Offer an algorithm to `new_owner`, who takes it over, and its deposits, by calling
+        /// `accept_algo_ownership`. A later offer replaces this one, and offering it to the
+        /// owner withdraws it.
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::transfer_algo_ownership())]
+        pub fn transfer_algo_ownership(origin: OriginFor<T>, algorithm_id: u64, new_owner: T::AccountId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::owned_active_algorithm(&who, algorithm_id)?;
+
+            if new_owner == who {
+                PendingOwners::<T>::remove(algorithm_id);
+            } else {
+                PendingOwners::<T>::insert(algorithm_id, &new_owner);
+            }
+
+            Self::deposit_event(Event::AlgorithmOwnershipOffered { algorithm_id, from: who, to: new_owner });
+
+            Ok(())
+        }
+
+        /// Take over an algorithm offered to the caller with `transfer_algo_ownership`. Its
+        /// deposits are reserved from the caller and returned to the previous owner.
+        #[pallet::call_index(23)]
+        #[pallet::weight(T::WeightInfo::accept_algo_ownership())]
+        pub fn accept_algo_ownership(origin: OriginFor<T>, algorithm_id: u64) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(PendingOwners::<T>::get(algorithm_id).as_ref() == Some(&who), Error::<T>::NotPendingOwner);
+            let mut algorithm = Algorithms::<T>::get(algorithm_id).ok_or(Error::<T>::AlgoNotFound)?;
+            ensure!(algorithm.status != AlgoStatus::Disabled, Error::<T>::AlgoDisabled);
+
+            let deposit = algorithm.deposit.saturating_add(RefreshDeposits::<T>::get(algorithm_id).unwrap_or_else(Zero::zero));
+            <T as pallet_issuers::Config>::Currency::reserve(&who, deposit)
+                .map_err(|_| Error::<T>::InsufficientBalance)?;
+            <T as pallet_issuers::Config>::Currency::unreserve(&algorithm.owner, deposit);
+
+            PendingOwners::<T>::remove(algorithm_id);
+            let from = core::mem::replace(&mut algorithm.owner, who.clone());
+            Algorithms::<T>::insert(algorithm_id, algorithm);
+
+            Self::deposit_event(Event::AlgorithmOwnershipTransferred { algorithm_id, from, to: who });
+
+            Ok(())
+        }
+
+        /// Store a module so algorithms can be created from it with `create_algo`. The
+        /// uploader pays a deposit proportional to its size.
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::upload_code(code.len() as u32))]
+        pub fn upload_code(origin: OriginFor<T>, code: Vec<u8>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let code_hash = <T as Config>::Hashing::hash(&code);
+            ensure!(!CodeByHash::<T>::contains_key(code_hash), Error::<T>::CodeAlreadyExists);
+
+            Self::do_upload_code(&who, code_hash, code)
+        }
+
+        /// Remove a module no active algorithm uses and return its deposit.
+        #[pallet::call_index(8)]
+        #[pallet::weight(T::WeightInfo::remove_code())]
+        pub fn remove_code(origin: OriginFor<T>, code_hash: T::Hash) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let code_info = CodeInfoOf::<T>::get(code_hash).ok_or(Error::<T>::CodeNotFound)?;
+            ensure!(code_info.owner == who, Error::<T>::NotCodeOwner);
+            ensure!(code_info.refcount == 0, Error::<T>::CodeInUse);
+
+            <T as pallet_issuers::Config>::Currency::unreserve(&who, code_info.deposit);
+            CodeByHash::<T>::remove(code_hash);
+            CodeInfoOf::<T>::remove(code_hash);
+
+            Self::deposit_event(Event::CodeRemoved { code_hash });
+
+            Ok(())
+        }
+
+        /// Create an algorithm from a module uploaded with `upload_code`.
+        #[pallet::call_index(9)]
+        #[pallet::weight(T::WeightInfo::create_algo(inputs.len() as u32))]
+        #[allow(clippy::too_many_arguments)]
+        pub fn create_algo(
+            origin: OriginFor<T>,
+            name: Vec<u8>,
+            description: Vec<u8>,
+            inputs: Vec<AlgoInput<T>>,
+            missing_inputs: MissingInputPolicy,
+            code_hash: T::Hash,
+            gas_limit: Option<u64>,
+            output_schema: Option<T::Hash>,
+            param_schema: Option<T::Hash>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::do_save_algo(who, name, description, inputs, missing_inputs, code_hash, gas_limit, output_schema, param_schema)
+        }
+
+        /// Queue a run for offchain workers, for algorithms too heavy to run in a block. They
+        /// run it with `OffchainGasLimit`, and the outcome is accepted once
+        /// `RequiredAgreements` offchain authorities submitted the same one. The requester's
+        /// deposit is returned then, or when the request expires after `RunRequestTimeout`.
+        #[pallet::call_index(10)]
+        #[pallet::weight(T::WeightInfo::request_algo_run())]
+        pub fn request_algo_run(
+            origin: OriginFor<T>,
+            issuer_hash: T::Hash,
+            account_id: Vec<u8>,
+            algorithm_id: u64,
+            params: BoundedVec<u8, T::MaxParamsSize>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            credentials::Pallet::<T>::parse_acquirer_address(account_id.clone())?;
+            let algorithm = Self::runnable_algorithm(algorithm_id, params.len())?;
+            ensure!(RunRequests::<T>::count() < T::MaxRunRequests::get(), Error::<T>::TooManyRunRequests);
+
+            let deposit = Parameters::<T>::get().run_request_deposit;
+            <T as pallet_issuers::Config>::Currency::reserve(&who, deposit)
+                .map_err(|_| Error::<T>::InsufficientBalance)?;
+
+            let request_id = NextRunRequestId::<T>::get();
+            NextRunRequestId::<T>::set(request_id + 1);
+
+            let requested_at = frame_system::Pallet::<T>::block_number();
+            RunRequestExpiries::<T>::mutate(requested_at.saturating_add(T::RunRequestTimeout::get()), |expiring| {
+                // Can't overflow, there are at most `MaxRunRequests` requests
+                let _ = expiring.try_push(request_id);
+            });
+
+            RunRequests::<T>::insert(request_id, RunRequest {
+                requester: who,
+                algorithm_id,
+                code_hash: algorithm.code_hash,
+                issuer_hash,
+                account_id: account_id.clone(),
+                params,
+                requested_at,
+                deposit,
+            });
+
+            Self::deposit_event(Event::AlgoRunRequested { request_id, algorithm_id, issuer_hash, account_id });
+
+            Ok(())
+        }
+
+        /// Submit the outcome of a requested run. Each offchain authority submits once per
+        /// request, free of charge.
+        #[pallet::call_index(11)]
+        #[pallet::weight(Pallet::<T>::submit_run_weight())]
+        pub fn submit_algo_run(
+            origin: OriginFor<T>,
+            request_id: u64,
+            outcome: RunOutcome<T::Hash>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            ensure!(OffchainAuthorities::<T>::get().contains(&who), Error::<T>::NotOffchainAuthority);
+            Self::check_outcome_size(&outcome)?;
+            let request = RunRequests::<T>::get(request_id).ok_or(Error::<T>::RunRequestNotFound)?;
+
+            let mut submissions = RunSubmissions::<T>::get(request_id);
+            ensure!(!submissions.iter().any(|(authority, _)| *authority == who), Error::<T>::AlreadySubmitted);
+
+            let outcome_hash = <T as Config>::Hashing::hash_of(&outcome);
+            // Cannot overflow while every submitter is one of the current authorities
+            submissions.try_push((who.clone(), outcome_hash)).map_err(|_| Error::<T>::NotOffchainAuthority)?;
+
+            Self::deposit_event(Event::AlgoRunSubmitted { request_id, authority: who });
+
+            let agreements = submissions.iter().filter(|(_, hash)| *hash == outcome_hash).count() as u32;
+            if agreements >= T::RequiredAgreements::get() {
+                Self::remove_run_request(request_id, &request);
+                Self::accept_outcome(request, outcome);
+            } else {
+                RunSubmissions::<T>::insert(request_id, submissions);
+            }
+
+            Ok(Pays::No.into())
+        }
+
+        #[pallet::call_index(12)]
+        #[pallet::weight(T::WeightInfo::set_offchain_authorities(authorities.len() as u32))]
+        pub fn set_offchain_authorities(
+            origin: OriginFor<T>,
+            authorities: BoundedVec<T::AccountId, T::MaxOffchainAuthorities>,
+        ) -> DispatchResult {
+            T::AuthorityOrigin::ensure_origin(origin)?;
+
+            OffchainAuthorities::<T>::put(&authorities);
+
+            Self::deposit_event(Event::OffchainAuthoritiesSet { authorities: authorities.into_inner() });
+
+            Ok(())
+        }
+
+        /// Run an algorithm for each of `accounts` as `run_algo_for` does. A subject whose
+        /// run fails, or cannot start, gets an `AlgoFailed` event without failing the call.
+        /// Gas the runs didn't use is refunded.
+        #[pallet::call_index(13)]
+        #[pallet::weight(Pallet::<T>::subject_run_weight().saturating_mul(accounts.len() as u64))]
+        pub fn run_algo_batch(
+            origin: OriginFor<T>,
+            issuer_hash: T::Hash,
+            algorithm_id: u64,
+            accounts: BoundedVec<Vec<u8>, T::MaxBatchSize>,
+            params: BoundedVec<u8, T::MaxParamsSize>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let algorithm = Self::runnable_algorithm(algorithm_id, params.len())?;
+
+            let mut succeeded = 0u32;
+            let mut weight = Weight::zero();
+            for account_id in accounts.iter() {
+                let (success, gas_used) =
+                    Self::run_subject(Some(&who), algorithm_id, &algorithm, issuer_hash, account_id.clone(), params.to_vec());
+                if success {
+                    succeeded += 1;
+                }
+                weight.saturating_accrue(Self::run_weight(algorithm.inputs.len(), gas_used));
+            }
+
+            Self::deposit_event(Event::AlgoBatchRun {
+                algorithm_id,
+                issuer_hash,
+                succeeded,
+                failed: accounts.len() as u32 - succeeded,
+            });
+
+            Ok(Some(weight).into())
+        }
+
+        /// Run an algorithm every `interval` blocks for the subjects added with
+        /// `add_scheduled_subjects`. Rounds run in idle block weight, so a large one may
+        /// span several blocks.
+        #[pallet::call_index(14)]
+        #[pallet::weight(T::WeightInfo::create_schedule())]
+        pub fn create_schedule(
+            origin: OriginFor<T>,
+            issuer_hash: T::Hash,
+            algorithm_id: u64,
+            params: BoundedVec<u8, T::MaxParamsSize>,
+            interval: BlockNumberFor<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::runnable_algorithm(algorithm_id, params.len())?;
+            ensure!(
+                !interval.is_zero() && interval >= T::MinScheduleInterval::get(),
+                Error::<T>::ScheduleIntervalTooShort
+            );
+            ensure!(Schedules::<T>::count() < T::MaxSchedules::get(), Error::<T>::TooManySchedules);
+
+            let deposit = Parameters::<T>::get().schedule_deposit;
+            <T as pallet_issuers::Config>::Currency::reserve(&who, deposit)
+                .map_err(|_| Error::<T>::InsufficientBalance)?;
+
+            let schedule_id = NextScheduleId::<T>::get();
+            NextScheduleId::<T>::set(schedule_id + 1);
+
+            let next_run = frame_system::Pallet::<T>::block_number().saturating_add(interval);
+            Self::add_to_agenda(schedule_id, next_run);
+
+            Schedules::<T>::insert(schedule_id, Schedule {
+                owner: who.clone(),
+                algorithm_id,
+                issuer_hash,
+                params,
+                interval,
+                next_run,
+                deposit,
+                subjects: 0,
+            });
+
+            Self::deposit_event(Event::ScheduleCreated { schedule_id, owner: who, algorithm_id, issuer_hash, interval });
+
+            Ok(())
+        }
+
+        #[pallet::call_index(15)]
+        #[pallet::weight(T::WeightInfo::add_scheduled_subjects(accounts.len() as u32))]
+        pub fn add_scheduled_subjects(
+            origin: OriginFor<T>,
+            schedule_id: u64,
+            accounts: BoundedVec<Vec<u8>, T::MaxBatchSize>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut schedule = Self::owned_schedule(&who, schedule_id)?;
+
+            for account_id in accounts.iter() {
+                let acquirer_address = credentials::Pallet::<T>::parse_acquirer_address(account_id.clone())?;
+
+                if !ScheduledSubjects::<T>::contains_key(schedule_id, &acquirer_address) {
+                    ScheduledSubjects::<T>::insert(schedule_id, acquirer_address, ());
+                    schedule.subjects += 1;
+                }
+            }
+            ensure!(schedule.subjects <= T::MaxScheduledSubjects::get(), Error::<T>::TooManyScheduledSubjects);
+
+            Schedules::<T>::insert(schedule_id, schedule);
+
+            Self::deposit_event(Event::ScheduledSubjectsAdded { schedule_id, accounts: accounts.into_inner() });
+
+            Ok(())
+        }
+
+        #[pallet::call_index(16)]
+        #[pallet::weight(T::WeightInfo::remove_scheduled_subjects(accounts.len() as u32))]
+        pub fn remove_scheduled_subjects(
+            origin: OriginFor<T>,
+            schedule_id: u64,
+            accounts: BoundedVec<Vec<u8>, T::MaxBatchSize>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut schedule = Self::owned_schedule(&who, schedule_id)?;
+
+            for account_id in accounts.iter() {
+                let acquirer_address = credentials::Pallet::<T>::parse_acquirer_address(account_id.clone())?;
+
+                if ScheduledSubjects::<T>::take(schedule_id, acquirer_address).is_some() {
+                    schedule.subjects = schedule.subjects.saturating_sub(1);
+                }
+            }
+
+            Schedules::<T>::insert(schedule_id, schedule);
+
+            Self::deposit_event(Event::ScheduledSubjectsRemoved { schedule_id, accounts: accounts.into_inner() });
+
+            Ok(())
+        }
+
+        /// Stop a schedule, dropping its subjects and any unfinished round, and return its
+        /// deposit. The call is weighed for `MaxScheduledSubjects` subjects, and refunded
+        /// down to the schedule's own.
+        #[pallet::call_index(17)]
+        #[pallet::weight(T::WeightInfo::cancel_schedule(T::MaxScheduledSubjects::get()))]
+        pub fn cancel_schedule(origin: OriginFor<T>, schedule_id: u64) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let schedule = Self::owned_schedule(&who, schedule_id)?;
+
+            let _ = ScheduledSubjects::<T>::clear_prefix(schedule_id, schedule.subjects, None);
+            ScheduleAgenda::<T>::mutate(schedule.next_run, |due| due.retain(|id| *id != schedule_id));
+            ScheduledRunQueue::<T>::mutate(|queue| queue.retain(|run| run.schedule_id != schedule_id));
+            Schedules::<T>::remove(schedule_id);
+
+            <T as pallet_issuers::Config>::Currency::unreserve(&who, schedule.deposit);
+
+            Self::deposit_event(Event::ScheduleCancelled { schedule_id });
+
+            Ok(Some(T::WeightInfo::cancel_schedule(schedule.subjects)).into())
+        }
+
+        /// Choose what happens to the algorithm's stored scores when an attestation they
+        /// may have read is created or updated. Policies other than `Keep` take a
+        /// `RefreshDeposit`, returned when going back to `Keep`.
+        #[pallet::call_index(18)]
+        #[pallet::weight(T::WeightInfo::set_refresh_policy(T::MaxSchemas::get()))]
+        pub fn set_refresh_policy(origin: OriginFor<T>, algorithm_id: u64, policy: RefreshPolicy) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
-            let acquirer_address = credentials::Pallet::<T>::parse_acquirer_address(account_id.clone())?;
+            let algorithm = Self::owned_active_algorithm(&who, algorithm_id)?;
+            ensure!(
+                policy != RefreshPolicy::Recompute || algorithm.param_schema.is_none(),
+                Error::<T>::RecomputeWithParams
+            );
+
+            if policy == RefreshPolicy::Keep {
+                Self::unwatch_inputs(algorithm_id, &algorithm);
+                RefreshPolicies::<T>::remove(algorithm_id);
+                if let Some(refresh_deposit) = RefreshDeposits::<T>::take(algorithm_id) {
+                    <T as pallet_issuers::Config>::Currency::unreserve(&who, refresh_deposit);
+                }
+            } else {
+                if !RefreshPolicies::<T>::contains_key(algorithm_id) {
+                    let refresh_deposit = T::RefreshDeposit::get();
+                    <T as pallet_issuers::Config>::Currency::reserve(&who, refresh_deposit)
+                        .map_err(|_| Error::<T>::InsufficientBalance)?;
+                    RefreshDeposits::<T>::insert(algorithm_id, refresh_deposit);
+                }
+                Self::watch_inputs(algorithm_id, &algorithm)?;
+                RefreshPolicies::<T>::insert(algorithm_id, policy);
+            }
+
+            Self::deposit_event(Event::RefreshPolicySet { algorithm_id, policy });
+
+            Ok(())
+        }
+
+        /// Charge a fee, paid to the algorithm owner, for each successful run of the
+        /// algorithm. `None` removes the fee.
+        #[pallet::call_index(19)]
+        #[pallet::weight(T::WeightInfo::set_run_fee())]
+        pub fn set_run_fee(origin: OriginFor<T>, algorithm_id: u64, fee: Option<BalanceOf<T>>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::owned_active_algorithm(&who, algorithm_id)?;
+            RunFees::<T>::set(algorithm_id, fee);
+
+            Self::deposit_event(Event::RunFeeSet { algorithm_id, fee });
+
+            Ok(())
+        }
+
+        /// Charge a fee for each successful run reading an attestation of `schema_hash` by
+        /// `issuer_hash`. Only the issuer's controllers may set it. `None` removes the fee.
+        #[pallet::call_index(20)]
+        #[pallet::weight(T::WeightInfo::set_data_fee())]
+        pub fn set_data_fee(
+            origin: OriginFor<T>,
+            issuer_hash: T::Hash,
+            schema_hash: T::Hash,
+            fee: Option<DataFee<T>>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let issuer = pallet_issuers::Issuers::<T>::get(issuer_hash)
+                .ok_or(pallet_issuers::Error::<T>::IssuerNotFound)?;
+            ensure!(issuer.controllers.contains(&who), pallet_issuers::Error::<T>::NotAuthorized);
+            ensure!(Schemas::<T>::contains_key(schema_hash), Error::<T>::SchemaNotFound);
+
+            DataFees::<T>::set(issuer_hash, schema_hash, fee.clone());
+
+            Self::deposit_event(Event::DataFeeSet { issuer_hash, schema_hash, fee });
+
+            Ok(())
+        }
+
+        /// Change the gas costs, deposits and default gas limit. Takes effect for runs,
+        /// deposits and saved algorithms from the next call on. Gas costs and the default gas
+        /// limit must be non-zero and at most `MaxGasLimit`, and deposits non-zero and at
+        /// most the total issuance.
+        #[pallet::call_index(21)]
+        #[pallet::weight(T::WeightInfo::set_params())]
+        pub fn set_params(origin: OriginFor<T>, params: PalletParams<T>) -> DispatchResult {
+            T::ParamsOrigin::ensure_origin(origin)?;
+
+            Self::check_params(&params)?;
+            Parameters::<T>::put(&params);
+
+            Self::deposit_event(Event::ParamsSet { params });
+
+            Ok(())
+        }
+
+        /// Let an algorithm waiting for approval be run.
+        #[pallet::call_index(24)]
+        #[pallet::weight(T::WeightInfo::approve_algo())]
+        pub fn approve_algo(origin: OriginFor<T>, algorithm_id: u64) -> DispatchResult {
+            T::ApprovalOrigin::ensure_origin(origin)?;
+
+            Algorithms::<T>::try_mutate(algorithm_id, |algorithm| {
+                let algorithm = algorithm.as_mut().ok_or(Error::<T>::AlgoNotFound)?;
+                ensure!(algorithm.status == AlgoStatus::PendingApproval, Error::<T>::AlgoNotPendingApproval);
+                algorithm.status = AlgoStatus::Active;
+                Ok::<_, Error<T>>(())
+            })?;
+
+            Self::deposit_event(Event::AlgorithmApproved { algorithm_id });
+
+            Ok(())
+        }
+
+        /// Disable an algorithm waiting for approval, returning its deposit to its owner.
+        #[pallet::call_index(25)]
+        #[pallet::weight(T::WeightInfo::reject_algo())]
+        pub fn reject_algo(origin: OriginFor<T>, algorithm_id: u64) -> DispatchResult {
+            T::ApprovalOrigin::ensure_origin(origin)?;
+
+            let algorithm = Algorithms::<T>::get(algorithm_id).ok_or(Error::<T>::AlgoNotFound)?;
+            ensure!(algorithm.status == AlgoStatus::PendingApproval, Error::<T>::AlgoNotPendingApproval);
+            Self::do_disable(algorithm_id, algorithm);
+
+            Self::deposit_event(Event::AlgorithmRejected { algorithm_id });
+
+            Ok(())
+        }
+
+        /// Only read input `input_index` from issuers verified at `tier` or above. Other
+        /// issuers are skipped as if they had no attestation. `Unverified` lifts the
+        /// requirement.
+        #[pallet::call_index(22)]
+        #[pallet::weight(T::WeightInfo::set_input_tier())]
+        pub fn set_input_tier(
+            origin: OriginFor<T>,
+            algorithm_id: u64,
+            input_index: u32,
+            tier: VerificationTier,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let algorithm = Self::owned_active_algorithm(&who, algorithm_id)?;
+            ensure!((input_index as usize) < algorithm.inputs.len(), Error::<T>::InvalidInputIndex);
+
+            if tier == VerificationTier::Unverified {
+                InputTiers::<T>::remove(algorithm_id, input_index);
+            } else {
+                InputTiers::<T>::insert(algorithm_id, input_index, tier);
+            }
+
+            Self::deposit_event(Event::InputTierSet { algorithm_id, input_index, tier });
+
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        #[allow(clippy::too_many_arguments)]
+        fn do_save_algo(
+            who: T::AccountId,
+            name: Vec<u8>,
+            description: Vec<u8>,
+            inputs: Vec<AlgoInput<T>>,
+            missing_inputs: MissingInputPolicy,
+            code_hash: T::Hash,
+            gas_limit: Option<u64>,
+            output_schema: Option<T::Hash>,
+            param_schema: Option<T::Hash>,
+        ) -> DispatchResult {
+            ensure!(inputs.len() <= T::MaxSchemas::get() as usize, Error::<T>::TooManySchemas);
+
+            for input in &inputs {
+                if let IssuerSelector::AnyOf(issuers) = &input.issuer {
+                    ensure!(!issuers.is_empty(), Error::<T>::NoTrustedIssuers);
+                }
+            }
+
+            let name = BoundedVec::try_from(name).map_err(|_| Error::<T>::AlgoNameTooLong)?;
+            let description = BoundedVec::try_from(description).map_err(|_| Error::<T>::AlgoDescriptionTooLong)?;
+
+            let gas_limit = gas_limit.unwrap_or_else(|| Parameters::<T>::get().default_gas_limit);
+            ensure!(gas_limit <= T::MaxGasLimit::get(), Error::<T>::GasLimitTooHigh);
+
+            if let Some(output_schema) = output_schema {
+                Self::output_size(output_schema)?;
+            }
+
+            if let Some(param_schema) = param_schema {
+                Self::params_size(param_schema)?;
+            }
+
+            Self::check_entry_point(code_hash, output_schema.is_some())?;
+
+            let deposit = Self::algorithm_deposit(code_hash)?;
+            <T as pallet_issuers::Config>::Currency::reserve(&who, deposit)
+                .map_err(|_| Error::<T>::InsufficientBalance)?;
+
+            Self::increment_refcount(code_hash)?;
+
+            let id = NextAlgoId::<T>::get();
+            NextAlgoId::<T>::set(id + 1);
+
+            Algorithms::<T>::insert(id, Algorithm {
+                owner: who.clone(),
+                name,
+                description,
+                status: Self::unapproved_status(),
+                deposit,
+                inputs: BoundedVec::try_from(inputs.clone()).map_err(|_| Error::<T>::TooManySchemas)?,
+                missing_inputs,
+                code_hash,
+                gas_limit,
+                output_schema,
+                param_schema,
+            });
+
+            Self::deposit_event(Event::AlgorithmAdded {
+                algorithm_id: id,
+                owner: who,
+                inputs,
+            });
+
+            Ok(())
+        }
+
+        /// Status of an algorithm saved, or given a new module, which waits for approval only
+        /// with `RequireApproval`.
+        fn unapproved_status() -> AlgoStatus {
+            if T::RequireApproval::get() {
+                AlgoStatus::PendingApproval
+            } else {
+                AlgoStatus::Active
+            }
+        }
+
+        /// Check `code` fits `MaxCodeSize`, passes `check_module` and is a module the engine
+        /// accepts.
+        pub fn validate_code(code: &[u8]) -> Result<(), Error<T>> {
+            ensure!(code.len() <= T::MaxCodeSize::get() as usize, Error::<T>::CodeTooHeavy);
+
+            Self::check_module(code)?;
+
+            // Just validate without storing the module
+            if wasmi::Module::new(&Self::engine(), code).is_err() {
+                // Tell modules rejected only for using floats apart from invalid ones
+                let floats_allowed = wasmi::Engine::default();
+                return match wasmi::Module::new(&floats_allowed, code) {
+                    Ok(_) => Err(Error::<T>::FloatsNotAllowed),
+                    Err(_) => Err(Error::<T>::InvalidWasmProvided),
+                };
+            }
+
+            Ok(())
+        }
+
+        /// Engine algorithms are validated and run with. Functions are only translated
+        /// when first called, as a run usually calls a small part of its module.
+        pub(crate) fn engine() -> wasmi::Engine {
+            let mut config = wasmi::Config::default();
+            config
+                .floats(T::AllowFloats::get())
+                .consume_fuel(true)
+                .compilation_mode(wasmi::CompilationMode::LazyTranslation);
+            wasmi::Engine::new(&config)
+        }
+
+        /// Take `amount` from the fuel left to the module calling a host function.
+        fn charge(caller: &mut Caller<'_, HostState>, cost: impl FnOnce(&GasCosts) -> u64) -> Result<(), wasmi::Error> {
+            let amount = cost(&caller.data().gas_costs);
+            let fuel = caller.get_fuel()?;
+            caller.set_fuel(fuel.checked_sub(amount).ok_or(TrapCode::OutOfFuel)?)?;
+            Ok(())
+        }
+
+        /// Algorithm `algorithm_id`, if it can be run with `params_len` bytes of parameters.
+        fn runnable_algorithm(algorithm_id: u64, params_len: usize) -> Result<Algorithm<T>, Error<T>> {
+            let algorithm = Algorithms::<T>::get(algorithm_id).ok_or(Error::<T>::AlgoNotFound)?;
+            Self::ensure_active(&algorithm)?;
+
+            let params_size = algorithm.param_schema
+                .map(Self::params_size)
+                .transpose()?
+                .unwrap_or(0);
+            ensure!(params_len == params_size as usize, Error::<T>::ParamsMismatch);
+
+            Ok(algorithm)
+        }
+
+        /// Read the inputs and module of algorithm `algorithm_id` for a run over
+        /// `acquirer_address` with `issuer_hash`.
+        pub fn prepare_run(
+            algorithm_id: u64,
+            algorithm: &Algorithm<T>,
+            acquirer_address: &AcquirerAddress,
+            issuer_hash: T::Hash,
+        ) -> Result<PreparedRun<T>, Error<T>> {
+            let mut attestations: Vec<pallet_credentials::CredAttestation<T>> = Vec::<>::with_capacity(algorithm.inputs.len());
+            let mut inputs = Vec::with_capacity(algorithm.inputs.len());
+
+            for (index, input) in algorithm.inputs.iter().enumerate() {
+                let (attestation, input_ref) = Self::input_attestation(
+                    acquirer_address,
+                    issuer_hash,
+                    input,
+                    InputTiers::<T>::get(algorithm_id, index as u32),
+                    algorithm.missing_inputs,
+                )?;
+                attestations.push(attestation);
+                inputs.push(input_ref);
+            }
+
+            let code = CodeByHash::<T>::get(algorithm.code_hash).ok_or(Error::<T>::CodeNotFound)?;
+
+            let output_size = algorithm.output_schema
+                .map(Self::output_size)
+                .transpose()?;
+
+            Ok(PreparedRun { attestations, inputs, code: code.into_inner(), output_size })
+        }
+
+        /// Run `algorithm` for `account_id` and store the result as its score, charging
+        /// `payer` the fees of a successful run and reporting a module failing once loaded by
+        /// an `AlgoFailed` event. Errors if the run can't start or its fees can't be paid.
+        /// Returns whether the run succeeded, and the gas it used.
+        fn run_and_record(
+            payer: Option<&T::AccountId>,
+            algorithm_id: u64,
+            algorithm: &Algorithm<T>,
+            issuer_hash: T::Hash,
+            account_id: Vec<u8>,
+            params: Vec<u8>,
+        ) -> Result<(bool, u64), DispatchError> {
+            let acquirer_address = credentials::Pallet::<T>::parse_acquirer_address(account_id.clone())?;
+            Self::ensure_score_issuer_room(algorithm_id, &acquirer_address, issuer_hash)?;
+
+            let prepared = Self::prepare_run(algorithm_id, algorithm, &acquirer_address, issuer_hash)?;
+
+            let present = prepared.inputs.iter().map(Option::is_some).collect();
+
+            // Algorithms saved before `MaxGasLimit` may have a higher limit
+            let gas_limit = algorithm.gas_limit.min(T::MaxGasLimit::get());
+
+            match Self::run_code(prepared.code, prepared.attestations, present, params, gas_limit, prepared.output_size) {
+              Ok(output) => {
+                  if let Some(payer) = payer {
+                      Self::charge_fees(payer, algorithm_id, &algorithm.owner, &prepared.inputs)?;
+                  }
+                  Self::record_score(algorithm_id, issuer_hash, account_id, acquirer_address, output.result, output.data, prepared.inputs);
+                  Ok((true, output.gas_used))
+              },
+              Err(failure) => {
+                  log::error!(target: "algo", "Algo execution failed {:?}", failure);
+
+                  Self::deposit_event(Event::AlgoFailed {
+                      algorithm_id,
+                      issuer_hash,
+                      account_id,
+                      error: failure.error.into(),
+                      gas_used: failure.gas_used,
+                      abort: failure.abort,
+                  });
+                  Ok((false, failure.gas_used))
+              }
+          }
+        }
+
+        /// `run_and_record` for one of many subjects, also reporting a run that can't start
+        /// by an `AlgoFailed` event. Returns whether the run succeeded, and the gas it used.
+        fn run_subject(
+            payer: Option<&T::AccountId>,
+            algorithm_id: u64,
+            algorithm: &Algorithm<T>,
+            issuer_hash: T::Hash,
+            account_id: Vec<u8>,
+            params: Vec<u8>,
+        ) -> (bool, u64) {
+            match Self::run_and_record(payer, algorithm_id, algorithm, issuer_hash, account_id.clone(), params) {
+                Ok(run) => run,
+                Err(error) => {
+                    Self::deposit_event(Event::AlgoFailed {
+                        algorithm_id,
+                        issuer_hash,
+                        account_id,
+                        error,
+                        gas_used: 0,
+                        abort: None,
+                    });
+                    (false, 0)
+                },
+            }
+        }
+
+        /// Charge `payer` the run fee of `algorithm_id`, paid to its `owner`, and the data
+        /// fees of the attestations a run read. Nothing is paid unless all fees are.
+        fn charge_fees(
+            payer: &T::AccountId,
+            algorithm_id: u64,
+            owner: &T::AccountId,
+            inputs: &[Option<InputRef<T::Hash>>],
+        ) -> DispatchResult {
+            fn add_share<AccountId: PartialEq, Balance: Saturating + Copy>(
+                shares: &mut Vec<(AccountId, Balance)>,
+                payee: AccountId,
+                amount: Balance,
+            ) {
+                match shares.iter_mut().find(|(account, _)| *account == payee) {
+                    Some((_, total)) => *total = total.saturating_add(amount),
+                    None => shares.push((payee, amount)),
+                }
+            }
+
+            let mut shares = Vec::new();
+
+            if let Some(fee) = RunFees::<T>::get(algorithm_id) {
+                add_share(&mut shares, owner.clone(), fee);
+            }
+            for input_ref in inputs.iter().flatten() {
+                if let Some(fee) = DataFees::<T>::get(input_ref.issuer_hash, input_ref.schema_hash) {
+                    add_share(&mut shares, fee.payee, fee.amount);
+                }
+            }
+
+            shares.retain(|(payee, amount)| payee != payer && !amount.is_zero());
+            if shares.is_empty() {
+                return Ok(());
+            }
+
+            with_storage_layer(|| {
+                for (payee, amount) in &shares {
+                    <T as pallet_issuers::Config>::Currency::transfer(payer, payee, *amount, ExistenceRequirement::KeepAlive)
+                        .map_err(|_| Error::<T>::InsufficientBalance)?;
+                }
+                Ok::<_, DispatchError>(())
+            })?;
+
+            let total = shares.iter().fold(BalanceOf::<T>::zero(), |total, (_, amount)| total.saturating_add(*amount));
+
+            Self::deposit_event(Event::RevenueSplit { algorithm_id, payer: payer.clone(), total, shares });
+
+            Ok(())
+        }
+
+        /// Schedule `schedule_id` owned by `who`.
+        fn owned_schedule(who: &T::AccountId, schedule_id: u64) -> Result<Schedule<T>, Error<T>> {
+            let schedule = Schedules::<T>::get(schedule_id).ok_or(Error::<T>::ScheduleNotFound)?;
+            ensure!(schedule.owner == *who, Error::<T>::NotScheduleOwner);
+            Ok(schedule)
+        }
+
+        fn add_to_agenda(schedule_id: u64, block_number: BlockNumberFor<T>) {
+            ScheduleAgenda::<T>::mutate(block_number, |due| {
+                // Can't overflow, each schedule is in a single agenda
+                let _ = due.try_push(schedule_id);
+            });
+        }
+
+        /// Queue a round of each schedule due at `block_number`, unless one is still
+        /// running, and set when it is next due.
+        fn queue_due_schedules(block_number: BlockNumberFor<T>) -> Weight {
+            let db_weight = T::DbWeight::get();
+
+            let due = ScheduleAgenda::<T>::take(block_number);
+            if due.is_empty() {
+                return db_weight.reads_writes(1, 1);
+            }
+
+            let mut queue = ScheduledRunQueue::<T>::get();
+            let mut weight = db_weight.reads_writes(2, 2);
+
+            for schedule_id in due {
+                weight.saturating_accrue(db_weight.reads_writes(2, 2));
+
+                let Some(mut schedule) = Schedules::<T>::get(schedule_id) else {
+                    continue;
+                };
+
+                if !queue.iter().any(|run| run.schedule_id == schedule_id) {
+                    // Can't overflow, the queue holds a round per schedule at most
+                    let _ = queue.try_push(ScheduledRun { schedule_id, last_subject: None });
+                }
+
+                schedule.next_run = block_number.saturating_add(schedule.interval);
+                Self::add_to_agenda(schedule_id, schedule.next_run);
+                Schedules::<T>::insert(schedule_id, schedule);
+            }
+
+            ScheduledRunQueue::<T>::put(queue);
+
+            weight
+        }
+
+        /// Run queued rounds for as many subjects as `remaining_weight` allows, carrying on
+        /// from the last subject run next time.
+        fn run_scheduled(remaining_weight: Weight) -> Weight {
+            let db_weight = T::DbWeight::get();
+            let subject_weight = Self::subject_run_weight();
+
+            let mut used = db_weight.reads_writes(1, 1);
+            if remaining_weight.any_lt(used.saturating_add(subject_weight)) {
+                return Weight::zero();
+            }
+
+            let mut queue = ScheduledRunQueue::<T>::get();
+            if queue.is_empty() {
+                return db_weight.reads(1);
+            }
+
+            while let Some(run) = queue.first().cloned() {
+                used.saturating_accrue(db_weight.reads(2));
+
+                // Rounds of disabled algorithms are dropped
+                let runnable = Schedules::<T>::get(run.schedule_id).and_then(|schedule| {
+                    let algorithm = Self::runnable_algorithm(schedule.algorithm_id, schedule.params.len()).ok()?;
+                    Some((schedule, algorithm))
+                });
+                let Some((schedule, algorithm)) = runnable else {
+                    queue.remove(0);
+                    continue;
+                };
+
+                let mut subjects = match run.last_subject {
+                    Some(last_subject) => ScheduledSubjects::<T>::iter_key_prefix_from(run.schedule_id, last_subject),
+                    None => ScheduledSubjects::<T>::iter_key_prefix(run.schedule_id),
+                };
+
+                loop {
+                    if remaining_weight.any_lt(used.saturating_add(subject_weight)) {
+                        queue[0].last_subject = Some(subjects.last_raw_key().to_vec());
+                        ScheduledRunQueue::<T>::put(queue);
+                        return used;
+                    }
+
+                    let Some(acquirer_address) = subjects.next() else {
+                        break;
+                    };
+
+                    let (_, gas_used) = Self::run_subject(
+                        Some(&schedule.owner),
+                        schedule.algorithm_id,
+                        &algorithm,
+                        schedule.issuer_hash,
+                        acquirer_address.to_bytes(),
+                        schedule.params.to_vec(),
+                    );
+                    used.saturating_accrue(Self::run_weight(algorithm.inputs.len(), gas_used));
+                }
+
+                queue.remove(0);
+                Self::deposit_event(Event::ScheduledRunCompleted { schedule_id: run.schedule_id });
+            }
+
+            ScheduledRunQueue::<T>::put(queue);
+
+            used
+        }
+
+        /// Check a score of `acquirer_address` from `issuer_hash` can be stored, which it
+        /// can't if the subject has scores from `MaxScoreIssuers` other issuers.
+        fn ensure_score_issuer_room(
+            algorithm_id: u64,
+            acquirer_address: &AcquirerAddress,
+            issuer_hash: T::Hash,
+        ) -> Result<(), Error<T>> {
+            let issuers = ScoreIssuers::<T>::get(algorithm_id, acquirer_address);
+            ensure!(issuers.contains(&issuer_hash) || !issuers.is_full(), Error::<T>::TooManyScoreIssuers);
+            Ok(())
+        }
+
+        /// Run algorithms again for the scores in `RecomputeQueue`, as many as
+        /// `remaining_weight` allows. Scores whose algorithm was disabled, or no longer
+        /// recomputes them, are dropped from the queue and stay dirty.
+        fn recompute_dirty(remaining_weight: Weight) -> Weight {
+            let db_weight = T::DbWeight::get();
+            let lookup = db_weight.reads_writes(2, 1);
+            let per_score = Self::subject_run_weight().saturating_add(lookup);
+
+            let mut used = Weight::zero();
+            let mut queue = RecomputeQueue::<T>::iter_keys();
+
+            while remaining_weight.all_gte(used.saturating_add(per_score)) {
+                let Some((algorithm_id, issuer_hash, acquirer_address)) = queue.next() else {
+                    break;
+                };
+                used.saturating_accrue(lookup);
+
+                RecomputeQueue::<T>::remove((algorithm_id, issuer_hash, acquirer_address.clone()));
+
+                let Some(algorithm) = Algorithms::<T>::get(algorithm_id) else {
+                    continue;
+                };
+                if algorithm.status != AlgoStatus::Active || RefreshPolicies::<T>::get(algorithm_id) != RefreshPolicy::Recompute {
+                    continue;
+                }
+
+                let (_, gas_used) =
+                    Self::run_subject(None, algorithm_id, &algorithm, issuer_hash, acquirer_address.to_bytes(), Vec::new());
+                used.saturating_accrue(Self::run_weight(algorithm.inputs.len(), gas_used));
+            }
+
+            used
+        }
+
+        /// Store the result of a successful run as the score of `acquirer_address`, which
+        /// `ensure_score_issuer_room` checked there is room for.
+        fn record_score(
+            algorithm_id: u64,
+            issuer_hash: T::Hash,
+            account_id: Vec<u8>,
+            acquirer_address: AcquirerAddress,
+            result: i64,
+            data: Option<Vec<u8>>,
+            inputs: Vec<Option<InputRef<T::Hash>>>,
+        ) {
+            ScoreIssuers::<T>::mutate(algorithm_id, &acquirer_address, |issuers| {
+                issuers.retain(|issuer| *issuer != issuer_hash);
+                // Can't overflow, checked by `ensure_score_issuer_room`
+                let _ = issuers.try_push(issuer_hash);
+            });
+            DirtyScores::<T>::remove((algorithm_id, issuer_hash, acquirer_address.clone()));
+            RecomputeQueue::<T>::remove((algorithm_id, issuer_hash, acquirer_address.clone()));
+
+            Scores::<T>::insert(
+                (algorithm_id, issuer_hash, acquirer_address),
+                Score {
+                    result,
+                    block_number: frame_system::Pallet::<T>::block_number(),
+                    inputs,
+                    data: data.clone(),
+                },
+            );
+
+            Self::deposit_event(Event::AlgoResult {
+                algorithm_id,
+                result,
+                issuer_hash,
+                account_id,
+                data,
+            });
+        }
+
+        /// Add `algorithm_id` to the watchers of the schemas of its inputs.
+        fn watch_inputs(algorithm_id: u64, algorithm: &Algorithm<T>) -> Result<(), Error<T>> {
+            for input in &algorithm.inputs {
+                SchemaWatchers::<T>::try_mutate(input.schema_hash, |watchers| {
+                    if !watchers.contains(&algorithm_id) {
+                        watchers.try_push(algorithm_id).map_err(|_| Error::<T>::TooManySchemaWatchers)?;
+                    }
+                    Ok::<_, Error<T>>(())
+                })?;
+            }
+            Ok(())
+        }
+
+        fn unwatch_inputs(algorithm_id: u64, algorithm: &Algorithm<T>) {
+            for input in &algorithm.inputs {
+                SchemaWatchers::<T>::mutate_exists(input.schema_hash, |watchers| {
+                    if let Some(list) = watchers {
+                        list.retain(|id| *id != algorithm_id);
+                        if list.is_empty() {
+                            *watchers = None;
+                        }
+                    }
+                });
+            }
+        }
+
+        /// Issuers `algorithm` may have been run with for `acquirer_address` whose score
+        /// read the attestations of `issuer_hash` for `schema_hash`.
+        fn dependent_issuers(
+            algorithm_id: u64,
+            algorithm: &Algorithm<T>,
+            acquirer_address: &AcquirerAddress,
+            issuer_hash: T::Hash,
+            schema_hash: T::Hash,
+        ) -> Vec<T::Hash> {
+            let mut issuers = Vec::new();
+
+            for input in algorithm.inputs.iter().filter(|input| input.schema_hash == schema_hash) {
+                let reads_issuer = match &input.issuer {
+                    IssuerSelector::Caller => {
+                        issuers.push(issuer_hash);
+                        false
+                    },
+                    IssuerSelector::Fixed(fixed) => *fixed == issuer_hash,
+                    IssuerSelector::AnyOf(trusted) => trusted.contains(&issuer_hash),
+                };
+
+                // The input is read from this issuer whichever issuer the algorithm ran with
+                if reads_issuer {
+                    issuers.extend(ScoreIssuers::<T>::get(algorithm_id, acquirer_address));
+                }
+            }
+
+            issuers.sort();
+            issuers.dedup();
+            issuers
+        }
+
+        /// Store or report an outcome the offchain authorities agreed on. A successful outcome
+        /// is reported as failed if the algorithm can no longer be run, or runs another
+        /// module than it did when the run was requested.
+        fn accept_outcome(request: RunRequest<T>, outcome: RunOutcome<T::Hash>) {
+            match outcome {
+                RunOutcome::Success { result, data, inputs } => {
+                    // Parsed successfully when the run was requested
+                    let Ok(acquirer_address) = credentials::Pallet::<T>::parse_acquirer_address(request.account_id.clone()) else {
+                        return;
+                    };
+
+                    // The algorithm may have been disabled, or given a new module, since
+                    let charged = Self::runnable_algorithm(request.algorithm_id, request.params.len())
+                        .map_err(DispatchError::from)
+                        .and_then(|algorithm| {
+                            ensure!(algorithm.code_hash == request.code_hash, Error::<T>::AlgoCodeChanged);
+                            Self::ensure_score_issuer_room(request.algorithm_id, &acquirer_address, request.issuer_hash)?;
+                            Self::charge_fees(&request.requester, request.algorithm_id, &algorithm.owner, &inputs)
+                        });
+                    if let Err(error) = charged {
+                        Self::deposit_event(Event::AlgoFailed {
+                            algorithm_id: request.algorithm_id,
+                            issuer_hash: request.issuer_hash,
+                            account_id: request.account_id,
+                            error,
+                            gas_used: 0,
+                            abort: None,
+                        });
+                        return;
+                    }
+
+                    Self::record_score(
+                        request.algorithm_id,
+                        request.issuer_hash,
+                        request.account_id,
+                        acquirer_address,
+                        result,
+                        data,
+                        inputs,
+                    );
+                },
+                RunOutcome::Failed { error, gas_used, abort } => {
+                    Self::deposit_event(Event::AlgoFailed {
+                        algorithm_id: request.algorithm_id,
+                        issuer_hash: request.issuer_hash,
+                        account_id: request.account_id,
+                        error,
+                        gas_used,
+                        abort,
+                    });
+                },
+            }
+        }
+
+        /// Run the requested runs the offchain authorities with keys on this node haven't
+        /// submitted an outcome for, and submit one from each of them.
+        fn run_requested_algorithms(block_number: BlockNumberFor<T>) {
+            let authorities = Self::local_authorities();
+            if authorities.is_empty() {
+                return;
+            }
+
+            for (request_id, request) in RunRequests::<T>::iter() {
+                let submissions = RunSubmissions::<T>::get(request_id);
+
+                let submitters = authorities.iter()
+                    .filter(|(_, account)| !submissions.iter().any(|(authority, _)| authority == account))
+                    .filter(|(_, account)| Self::claim_submission(request_id, account, block_number))
+                    .map(|(public, _)| public.clone())
+                    .collect::<Vec<_>>();
+
+                if submitters.is_empty() {
+                    continue;
+                }
+
+                let outcome = Self::offchain_outcome(&request);
+
+                let results = Signer::<T, T::AuthorityId>::all_accounts()
+                    .with_filter(submitters)
+                    .send_signed_transaction(|_| Call::submit_algo_run { request_id, outcome: outcome.clone() });
+
+                for (account, result) in results {
+                    if result.is_err() {
+                        log::error!(target: "algo", "Failed to submit outcome of run {} from {:?}", request_id, account.id);
+                    }
+                }
+            }
+        }
+
+        /// Keys in the local keystore belonging to offchain authorities, with their accounts.
+        fn local_authorities() -> Vec<(T::Public, T::AccountId)> {
+            let authorities = OffchainAuthorities::<T>::get();
+
+            <T::AuthorityId as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic::all()
+                .into_iter()
+                .map(|key| {
+                    let public: T::Public = <T::AuthorityId as AppCrypto<T::Public, T::Signature>>::GenericPublic::from(key).into();
+                    let account = public.clone().into_account();
+                    (public, account)
+                })
+                .filter(|(_, account)| authorities.contains(account))
+                .collect()
+        }
+
+        /// Whether `account` should submit an outcome for `request_id` at `block_number`,
+        /// recording that it does. A submission is only repeated after `RESUBMIT_AFTER`
+        /// blocks, in case the transaction was dropped.
+        fn claim_submission(request_id: u64, account: &T::AccountId, block_number: BlockNumberFor<T>) -> bool {
+            let key = (b"algorithms::submitted", request_id, account).encode();
+            let last_submission = StorageValueRef::persistent(&key);
+
+            last_submission
+                .mutate(|last: Result<Option<BlockNumberFor<T>>, StorageRetrievalError>| match last {
+                    Ok(Some(last)) if block_number < last.saturating_add(RESUBMIT_AFTER.into()) => Err(()),
+                    _ => Ok(block_number),
+                })
+                .is_ok()
+        }
+
+        /// Outcome of running `request` with `OffchainGasLimit`.
+        fn offchain_outcome(request: &RunRequest<T>) -> RunOutcome<T::Hash> {
+            let prepared = credentials::Pallet::<T>::parse_acquirer_address(request.account_id.clone())
+                .and_then(|acquirer_address| {
+                    let algorithm = Self::runnable_algorithm(request.algorithm_id, request.params.len())?;
+                    Ok(Self::prepare_run(request.algorithm_id, &algorithm, &acquirer_address, request.issuer_hash)?)
+                });
+
+            let prepared = match prepared {
+                Ok(prepared) => prepared,
+                Err(error) => return RunOutcome::Failed { error, gas_used: 0, abort: None },
+            };
+
+            let present = prepared.inputs.iter().map(Option::is_some).collect();
+
+            match Self::run_code(
+                prepared.code,
+                prepared.attestations,
+                present,
+                request.params.to_vec(),
+                T::OffchainGasLimit::get(),
+                prepared.output_size,
+            ) {
+                Ok(output) => RunOutcome::Success { result: output.result, data: output.data, inputs: prepared.inputs },
+                Err(failure) => RunOutcome::Failed {
+                    error: failure.error.into(),
+                    gas_used: failure.gas_used,
+                    abort: failure.abort,
+                },
+            }
+        }
+
+        /// Size of a value laid out as the schema `schema_hash`. Text fields have no fixed
+        /// size, so such schemas may not contain them.
+        fn schema_size(schema_hash: T::Hash) -> Result<u32, Error<T>> {
+            let schema = Schemas::<T>::get(schema_hash).ok_or(Error::<T>::SchemaNotFound)?;
+            Self::layout_size(&schema)
+        }
 
+        /// Size of a value laid out as `schema`, which must not have text fields.
+        pub fn layout_size(schema: &CredSchema<T>) -> Result<u32, Error<T>> {
+            let mut size = 0u32;
+            for (_, cred_type) in schema.iter() {
+                ensure!(*cred_type != credentials::CredType::Text, Error::<T>::SchemaHasText);
+                let credentials::SizeInBytes::Limited(field_size) = cred_type.size_in_bytes();
+                size += field_size as u32;
+            }
+
+            Ok(size)
+        }
+
+        /// Size of the structured result described by the output schema `schema_hash`.
+        fn output_size(schema_hash: T::Hash) -> Result<u32, Error<T>> {
+            let size = Self::schema_size(schema_hash)?;
+            ensure!(size <= T::MaxReturnDataSize::get(), Error::<T>::OutputSchemaTooLarge);
+            Ok(size)
+        }
+
+        /// Size of the parameters described by the param schema `schema_hash`.
+        fn params_size(schema_hash: T::Hash) -> Result<u32, Error<T>> {
+            let size = Self::schema_size(schema_hash)?;
+            ensure!(size <= T::MaxParamsSize::get(), Error::<T>::ParamSchemaTooLarge);
+            Ok(size)
+        }
+
+        /// Whether `algorithm` may be run.
+        fn ensure_active(algorithm: &Algorithm<T>) -> Result<(), Error<T>> {
+            match algorithm.status {
+                AlgoStatus::Active => Ok(()),
+                AlgoStatus::Disabled => Err(Error::<T>::AlgoDisabled),
+                AlgoStatus::PendingApproval => Err(Error::<T>::AlgoNotApproved),
+            }
+        }
+
+        /// Algorithm `algorithm_id`, if `who` owns it and it is not disabled. It may still be
+        /// waiting for approval.
+        fn owned_active_algorithm(who: &T::AccountId, algorithm_id: u64) -> Result<Algorithm<T>, Error<T>> {
             let algorithm = Algorithms::<T>::get(algorithm_id).ok_or(Error::<T>::AlgoNotFound)?;
+            ensure!(algorithm.owner == *who, Error::<T>::NotAlgoOwner);
+            ensure!(algorithm.status != AlgoStatus::Disabled, Error::<T>::AlgoDisabled);
+            Ok(algorithm)
+        }
 
-            let mut attestations: Vec<pallet_credentials::CredAttestation<T>> = Vec::<>::with_capacity(algorithm.schema_hashes.len());
-            
-            // For each schema, get the latest attestation
-            for schema_hash in &algorithm.schema_hashes {
-              let attestations_for_schema = Attestations::<T>::get(
-                  (acquirer_address.clone(), issuer_hash, *schema_hash)
-              ).ok_or(Error::<T>::AttestationNotFound)?;
+        /// Disable `algorithm`, releasing its module and returning its deposits to its owner.
+        fn do_disable(algorithm_id: u64, mut algorithm: Algorithm<T>) {
+            Self::decrement_refcount(algorithm.code_hash);
+            Self::unwatch_inputs(algorithm_id, &algorithm);
+            RefreshPolicies::<T>::remove(algorithm_id);
+            PendingOwners::<T>::remove(algorithm_id);
 
-              // Check if there are any attestations
-              ensure!(!attestations_for_schema.is_empty(), Error::<T>::AttestationNotFound);
+            if let Some(refresh_deposit) = RefreshDeposits::<T>::take(algorithm_id) {
+                <T as pallet_issuers::Config>::Currency::unreserve(&algorithm.owner, refresh_deposit);
+            }
+            <T as pallet_issuers::Config>::Currency::unreserve(&algorithm.owner, algorithm.deposit);
+            algorithm.deposit = Zero::zero();
+            algorithm.status = AlgoStatus::Disabled;
+            Algorithms::<T>::insert(algorithm_id, algorithm);
+        }
 
-              // Get the latest attestation (last element in the vector)
+        /// Hash of `code`, uploading it on behalf of `who` unless it is already stored.
+        fn upload_if_missing(who: &T::AccountId, code: Vec<u8>) -> Result<T::Hash, DispatchError> {
+            let code_hash = <T as Config>::Hashing::hash(&code);
 
-              let schema = Schemas::<T>::get(schema_hash).ok_or(Error::<T>::SchemaNotFound)?;
-            
-              // Get text field indices
-              let text_indices: Vec<usize> = schema.iter()
-                  .enumerate()
-                  .filter_map(|(idx, (_, cred_type))| {
-                      if *cred_type == credentials::CredType::Text {
-                          Some(idx)
-                      } else {
-                          None
-                      }
-                  })
-                  .collect();
-
-              // Get the latest attestation and remove text fields
-              let mut latest_attestation = attestations_for_schema.last().unwrap().clone();
-              
-              // Remove text fields from highest index to lowest to maintain index validity
-              for &index in text_indices.iter().rev() {
-                  latest_attestation.remove(index);
-              } 
-
-              attestations.push(latest_attestation.clone());
-            }
-
-            match Self::run_code(algorithm.code.to_vec(), attestations, algorithm.gas_limit) {
-              Ok(value) => {
-                  Self::deposit_event(Event::AlgoResult {
-                      result: value,
-                      issuer_hash,
-                      account_id,
-                  });
-                  Ok(())
-              },
-              Err(e) => {
-                  log::error!(target: "algo", "Algo execution failed {:?}", e);
-                  Err(Error::<T>::AlgoExecutionFailed.into())
-              }
-          }
+            if !CodeByHash::<T>::contains_key(code_hash) {
+                Self::do_upload_code(who, code_hash, code)?;
+            }
+
+            Ok(code_hash)
         }
-    }
 
-    impl<T: Config> Pallet<T> {
-        pub fn run_code(code: Vec<u8>, attestations: Vec<CredAttestation<T>>, gas_limit: u64) -> Result<i64, Error<T>> {
-            let engine = wasmi::Engine::default();
+        fn do_upload_code(who: &T::AccountId, code_hash: T::Hash, code: Vec<u8>) -> DispatchResult {
+            Self::validate_code(&code)?;
+
+            let deposit = Parameters::<T>::get().code_deposit_per_byte.saturating_mul((code.len() as u32).into());
+            <T as pallet_issuers::Config>::Currency::reserve(who, deposit)
+                .map_err(|_| Error::<T>::InsufficientBalance)?;
+
+            CodeByHash::<T>::insert(code_hash, BoundedVec::<u8, T::MaxCodeSize>::try_from(code).map_err(|_| Error::<T>::CodeTooHeavy)?);
+            CodeInfoOf::<T>::insert(code_hash, CodeInfo { owner: who.clone(), deposit, refcount: 0 });
+
+            Self::deposit_event(Event::CodeUploaded { code_hash, owner: who.clone() });
+
+            Ok(())
+        }
+
+        fn check_params(params: &PalletParams<T>) -> Result<(), Error<T>> {
+            let max_gas = T::MaxGasLimit::get();
+            let gas_within_bounds = [
+                params.gas_costs.basic_op,
+                params.gas_costs.memory_op,
+                params.gas_costs.call_op,
+                params.default_gas_limit,
+            ]
+                .iter()
+                .all(|gas| *gas > 0 && *gas <= max_gas);
+
+            let total_issuance = <T as pallet_issuers::Config>::Currency::total_issuance();
+            let deposits_within_bounds = [
+                params.algorithm_deposit,
+                params.code_deposit_per_byte,
+                params.schedule_deposit,
+                params.run_request_deposit,
+            ]
+                .iter()
+                .all(|deposit| !deposit.is_zero() && *deposit <= total_issuance);
+
+            ensure!(gas_within_bounds && deposits_within_bounds, Error::<T>::InvalidParams);
+            Ok(())
+        }
+
+        /// Deposit of an algorithm run with the module `code_hash`: `algorithm_deposit`, plus
+        /// `code_deposit_per_byte` for each byte of the module it keeps from being removed.
+        fn algorithm_deposit(code_hash: T::Hash) -> Result<BalanceOf<T>, Error<T>> {
+            let code_len = CodeByHash::<T>::decode_len(code_hash).ok_or(Error::<T>::CodeNotFound)?;
+            let params = Parameters::<T>::get();
+            Ok(params.algorithm_deposit.saturating_add(params.code_deposit_per_byte.saturating_mul((code_len as u32).into())))
+        }
+
+        /// Reserve or return the difference between the deposit `who` paid and `deposit`.
+        fn adjust_deposit(who: &T::AccountId, reserved: BalanceOf<T>, deposit: BalanceOf<T>) -> Result<(), Error<T>> {
+            if deposit > reserved {
+                <T as pallet_issuers::Config>::Currency::reserve(who, deposit - reserved)
+                    .map_err(|_| Error::<T>::InsufficientBalance)?;
+            } else {
+                <T as pallet_issuers::Config>::Currency::unreserve(who, reserved - deposit);
+            }
+            Ok(())
+        }
+
+        /// Weight of running a module with `gas` gas.
+        fn gas_weight(gas: u64) -> Weight {
+            T::WeightInfo::execute(gas.div_ceil(1_000).try_into().unwrap_or(u32::MAX))
+        }
+
+        /// Weight of a run for one subject of an algorithm with `inputs` inputs, which used
+        /// `gas_used` gas.
+        fn run_weight(inputs: usize, gas_used: u64) -> Weight {
+            T::WeightInfo::run_algo_for(inputs as u32).saturating_add(Self::gas_weight(gas_used))
+        }
+
+        /// Upper bound on the weight of a run for one subject, using `MaxGasLimit` gas, which
+        /// runs are budgeted with before the gas they use is known.
+        pub(crate) fn subject_run_weight() -> Weight {
+            Self::run_weight(T::MaxSchemas::get() as usize, T::MaxGasLimit::get())
+        }
+
+        /// Upper bound on the weight of `submit_algo_run`, accepting the outcome included.
+        /// Fees are paid to the algorithm owner and the payee of a data fee per input.
+        pub(crate) fn submit_run_weight() -> Weight {
+            let inputs = T::MaxSchemas::get() as u64;
+            // The payer besides the owner and an issuer per input
+            let accounts = inputs.saturating_add(2);
+
+            T::DbWeight::get().reads_writes(
+                // Authorities, request and its counter, submissions, expiries, algorithm,
+                // run fee, score issuers and a data fee per input
+                8u64.saturating_add(inputs).saturating_add(accounts),
+                // Request and its counter, submissions, expiries, score issuers, dirty
+                // score and score
+                7u64.saturating_add(accounts),
+            )
+        }
+
+        /// Check a submitted outcome holds no more than a run can produce, as submitting is
+        /// free.
+        fn check_outcome_size(outcome: &RunOutcome<T::Hash>) -> Result<(), Error<T>> {
+            let within_bounds = match outcome {
+                RunOutcome::Success { data, inputs, .. } => {
+                    data.as_ref().map_or(true, |data| data.len() <= T::MaxReturnDataSize::get() as usize)
+                        && inputs.len() <= T::MaxSchemas::get() as usize
+                },
+                // Strings decoded from UTF-16 take up to three bytes per two read
+                RunOutcome::Failed { abort, .. } => abort.as_ref().map_or(true, |abort| {
+                    let max_length = (MAX_DEBUG_STRING_LENGTH as usize / 2) * 3;
+                    abort.message.len() <= max_length && abort.file.len() <= max_length
+                }),
+            };
+            ensure!(within_bounds, Error::<T>::OutcomeTooLarge);
+            Ok(())
+        }
+
+        /// Remove a requested run with its submissions and return its deposit.
+        fn remove_run_request(request_id: u64, request: &RunRequest<T>) {
+            RunRequests::<T>::remove(request_id);
+            RunSubmissions::<T>::remove(request_id);
+            RunRequestExpiries::<T>::mutate_exists(request.requested_at.saturating_add(T::RunRequestTimeout::get()), |expiring| {
+                if let Some(list) = expiring {
+                    list.retain(|id| *id != request_id);
+                    if list.is_empty() {
+                        *expiring = None;
+                    }
+                }
+            });
+            <T as pallet_issuers::Config>::Currency::unreserve(&request.requester, request.deposit);
+        }
+
+        /// Drop the requested runs expiring at `block_number`.
+        fn expire_run_requests(block_number: BlockNumberFor<T>) -> Weight {
+            let db_weight = T::DbWeight::get();
+
+            let expiring = RunRequestExpiries::<T>::take(block_number);
+            let mut weight = db_weight.reads_writes(1, 1);
+
+            for request_id in expiring {
+                weight.saturating_accrue(db_weight.reads_writes(2, 4));
+
+                let Some(request) = RunRequests::<T>::get(request_id) else {
+                    continue;
+                };
+                Self::remove_run_request(request_id, &request);
+
+                Self::deposit_event(Event::AlgoRunExpired { request_id });
+            }
+
+            weight
+        }
+
+        /// Check the module `code_hash` exports `calc_output` if `has_output`, `calc` otherwise.
+        fn check_entry_point(code_hash: T::Hash, has_output: bool) -> Result<(), Error<T>> {
+            let code = CodeByHash::<T>::get(code_hash).ok_or(Error::<T>::CodeNotFound)?;
+            let entry_point = if has_output { "calc_output" } else { "calc" };
+            ensure!(Self::exports_entry_point(&code, entry_point), Error::<T>::MissingEntryPoint);
+            Ok(())
+        }
+
+        fn increment_refcount(code_hash: T::Hash) -> Result<(), Error<T>> {
+            CodeInfoOf::<T>::try_mutate(code_hash, |code_info| {
+                let code_info = code_info.as_mut().ok_or(Error::<T>::CodeNotFound)?;
+                code_info.refcount = code_info.refcount.saturating_add(1);
+                Ok(())
+            })
+        }
+
+        fn decrement_refcount(code_hash: T::Hash) {
+            CodeInfoOf::<T>::mutate(code_hash, |code_info| {
+                if let Some(code_info) = code_info {
+                    code_info.refcount = code_info.refcount.saturating_sub(1);
+                }
+            });
+        }
+
+        /// Latest attestation for `input` held by `acquirer_address`, with its text fields
+        /// removed, and where it was found. `caller_issuer` is the issuer `run_algo_for` was
+        /// called with, and issuers below `min_tier` are skipped. Under
+        /// `MissingInputPolicy::BestEffort` a missing attestation is replaced by zeroes of
+        /// the same size.
+        fn input_attestation(
+            acquirer_address: &AcquirerAddress,
+            caller_issuer: T::Hash,
+            input: &AlgoInput<T>,
+            min_tier: VerificationTier,
+            missing_inputs: MissingInputPolicy,
+        ) -> Result<(CredAttestation<T>, Option<InputRef<T::Hash>>), Error<T>> {
+            let schema = Schemas::<T>::get(input.schema_hash).ok_or(Error::<T>::SchemaNotFound)?;
+
+            let issuers = match &input.issuer {
+                IssuerSelector::Caller => vec![caller_issuer],
+                IssuerSelector::Fixed(issuer_hash) => vec![*issuer_hash],
+                IssuerSelector::AnyOf(issuer_hashes) => issuer_hashes.to_vec(),
+            };
+            let issuers = issuers.into_iter()
+                .filter(|issuer_hash| pallet_issuers::Pallet::<T>::tier_of(*issuer_hash) >= min_tier)
+                .collect::<Vec<_>>();
+            let any_eligible = !issuers.is_empty();
+
+            // Take the latest attestation (last element) from the first issuer that has one
+            let latest_attestation = issuers.into_iter()
+                .find_map(|issuer_hash| {
+                    let attestations = Attestations::<T>::get((acquirer_address.clone(), issuer_hash, input.schema_hash))?;
+                    let attestation = attestations.last()?.clone();
+                    let input_ref = InputRef {
+                        issuer_hash,
+                        schema_hash: input.schema_hash,
+                        attestation_index: attestations.len() as u32 - 1,
+                        attestation_hash: <T as Config>::Hashing::hash_of(&attestation),
+                    };
+                    Some((attestation, input_ref))
+                });
+
+            let Some((latest_attestation, input_ref)) = latest_attestation else {
+                ensure!(
+                    missing_inputs == MissingInputPolicy::BestEffort,
+                    if any_eligible { Error::<T>::AttestationNotFound } else { Error::<T>::IssuerTierTooLow }
+                );
+                return Ok((Self::missing_input(&schema), None));
+            };
 
-            let gas_meter = GasMeter::new(gas_limit);
+            Ok((Self::input_layout(&schema, latest_attestation), Some(input_ref)))
+        }
+
+        /// `attestation` of `schema` as written to module memory, without its text fields.
+        pub fn input_layout(schema: &CredSchema<T>, mut attestation: CredAttestation<T>) -> CredAttestation<T> {
+            // Remove text fields from highest index to lowest to maintain index validity
+            for (index, _) in schema.iter().enumerate().rev()
+                .filter(|(_, (_, cred_type))| *cred_type == credentials::CredType::Text)
+            {
+                if index < attestation.len() {
+                    attestation.remove(index);
+                }
+            }
+
+            attestation
+        }
+
+        /// What is written to module memory in place of a missing input of `schema`: its
+        /// fields other than text, zero-filled.
+        pub fn missing_input(schema: &CredSchema<T>) -> CredAttestation<T> {
+            let placeholder = schema.iter()
+                .filter(|(_, cred_type)| *cred_type != credentials::CredType::Text)
+                .map(|(_, cred_type)| {
+                    let credentials::SizeInBytes::Limited(size) = cred_type.size_in_bytes();
+                    BoundedVec::truncate_from(vec![0u8; size as usize])
+                })
+                .collect::<Vec<_>>();
+
+            BoundedVec::truncate_from(placeholder)
+        }
+
+        /// Stored score of `account_id` for `algorithm_id` run with `issuer_hash`. When
+        /// `InvalidateStaleScores` is set, scores whose input attestations were updated or
+        /// removed since are not returned. Neither are scores marked dirty.
+        pub fn score_of(
+            algorithm_id: u64,
+            issuer_hash: T::Hash,
+            account_id: Vec<u8>,
+        ) -> Option<Score<T::Hash, BlockNumberFor<T>>> {
+            let acquirer_address = credentials::Pallet::<T>::parse_acquirer_address(account_id).ok()?;
+            let score = Scores::<T>::get((algorithm_id, issuer_hash, acquirer_address.clone()))?;
+
+            if T::InvalidateStaleScores::get() && Self::is_stale(&acquirer_address, &score) {
+                return None;
+            }
+
+            if DirtyScores::<T>::contains_key((algorithm_id, issuer_hash, acquirer_address)) {
+                return None;
+            }
+
+            Some(score)
+        }
+
+        /// Whether any attestation `score` was computed from changed or disappeared.
+        pub fn is_stale(acquirer_address: &AcquirerAddress, score: &Score<T::Hash, BlockNumberFor<T>>) -> bool {
+            score.inputs.iter().flatten().any(|input_ref| {
+                let current = Attestations::<T>::get((acquirer_address.clone(), input_ref.issuer_hash, input_ref.schema_hash))
+                    .and_then(|attestations| attestations.get(input_ref.attestation_index as usize).cloned());
+
+                match current {
+                    Some(attestation) => <T as Config>::Hashing::hash_of(&attestation) != input_ref.attestation_hash,
+                    None => true,
+                }
+            })
+        }
+
+        /// Run `calc` over `attestations` with `gas_limit` fuel, which executed instructions
+        /// and host calls use up. `present[i]` tells the module, through
+        /// `host.input_present(i)`, whether input `i` was found or zero-filled. `params` can
+        /// be copied into module memory with `host.read_params(ptr)`, and their length read
+        /// with `host.params_len()`.
+        ///
+        /// `host.fixed_mul`, `host.fixed_div`, `host.sqrt` and `host.ln` work on `FixedI64`
+        /// values passed as their inner `i64`, and trap on overflow or invalid input.
+        /// `host.print(value)` logs an `i32`, and `host.print_str(ptr, len)` a UTF-8 string
+        /// in debug builds only. Both charge `basic_op` in every build, so a run uses the same
+        /// gas on every node.
+        ///
+        /// With an `output_size`, `calc_output` is run instead, and must pass exactly that
+        /// many bytes to `host.return_data(ptr, len)` besides returning its `i64`.
+        pub fn run_code(
+            code: Vec<u8>,
+            attestations: Vec<CredAttestation<T>>,
+            present: Vec<bool>,
+            params: Vec<u8>,
+            gas_limit: u64,
+            output_size: Option<u32>,
+        ) -> Result<RunOutput, RunFailure<T>> {
+            let engine = Self::engine();
 
             let module =
                 wasmi::Module::new(&engine, code.as_slice()).map_err(|_| Error::<T>::InvalidWasmProvided)?;
 
-            let mut store = wasmi::Store::new(&engine, gas_meter);
+            let mut store = wasmi::Store::new(
+                &engine,
+                HostState { return_data: None, abort: None, gas_costs: Parameters::<T>::get().gas_costs },
+            );
+            store.set_fuel(gas_limit).map_err(|_| Error::<T>::GasMeteringNotSupported)?;
+
+            let result = Self::execute(&mut store, &module, attestations, present, params, output_size);
+
+            let gas_used = gas_limit.saturating_sub(store.get_fuel().unwrap_or(0));
+            let state = store.into_data();
+
+            let result = result.and_then(|result| {
+                let data = match output_size {
+                    Some(output_size) => {
+                        let data = state.return_data.ok_or(Error::<T>::MissingReturnData)?;
+                        ensure!(data.len() == output_size as usize, Error::<T>::ReturnDataMismatch);
+                        Some(data)
+                    },
+                    None => None,
+                };
+                Ok(RunOutput { result, data, gas_used })
+            });
+
+            result.map_err(|error| RunFailure { error, gas_used, abort: state.abort })
+        }
+
+        /// Link `module` against the host functions, write its inputs to memory and call its
+        /// entry point.
+        fn execute(
+            store: &mut wasmi::Store<HostState>,
+            module: &wasmi::Module,
+            attestations: Vec<CredAttestation<T>>,
+            present: Vec<bool>,
+            params: Vec<u8>,
+            output_size: Option<u32>,
+        ) -> Result<i64, Error<T>> {
+            let memory = wasmi::Memory::new(
+                &mut *store,
+                wasmi::MemoryType::new(T::MaxMemoryPages::get(), Some(T::MaxMemoryPages::get())).map_err(|_| Error::<T>::AcmSetupFailed)?,
+            )
+                .map_err(|_| Error::<T>::AcmSetupFailed)?;
             
             let host_print = wasmi::Func::wrap(
-                &mut store,
-                |mut caller: wasmi::Caller<'_, GasMeter>, param: i32| {
-                    caller.data_mut().charge(T::GasCost::get().basic_op).map_err(|_| Trap::new("Gas charge failed"))?;
+                &mut *store,
+                |mut caller: wasmi::Caller<'_, HostState>, param: i32| -> Result<(), wasmi::Error> {
+                    Self::charge(&mut caller, |costs| costs.basic_op)?;
                     log::debug!(target: "algo", "Message:{:?}", param);
                     Ok(())
                 },
             );
 
-            let abort_func = wasmi::Func::wrap(
-              &mut store,
-              |mut caller: Caller<'_, GasMeter>, msg_id: i32, filename: i32, line: i32, col: i32| -> Result<(), Trap> {
-                  caller.data_mut().charge(T::GasCost::get().call_op).map_err(|_| Trap::new("Gas charge failed"))?;
-                  log::error!(
-                      target: "algo",
-                      "Abort called: msg_id={}, file={}, line={}, col={}",
-                      msg_id, filename, line, col
-                  );
-                  Err(Trap::new("Gas charge failed"))
-              },
+            let print_str = wasmi::Func::wrap(
+                &mut *store,
+                move |mut caller: Caller<'_, HostState>, ptr: i32, len: i32| -> Result<(), wasmi::Error> {
+                    // Charged before, and regardless of, the build-dependent logging
+                    Self::charge(&mut caller, |costs| costs.basic_op)?;
+                    if cfg!(debug_assertions) {
+                        let mut bytes = vec![0u8; (len as u32).min(MAX_DEBUG_STRING_LENGTH) as usize];
+                        if memory.read(&caller, ptr as u32 as usize, &mut bytes).is_ok() {
+                            log::debug!(target: "algo", "Message: {}", prelude::string::String::from_utf8_lossy(&bytes));
+                        }
+                    }
+                    Ok(())
+                },
             );
 
-            let memory = wasmi::Memory::new(
-                &mut store,
-                wasmi::MemoryType::new(T::MaxMemoryPages::get(), Some(T::MaxMemoryPages::get())).map_err(|_| Error::<T>::AcmSetupFailed)?,
-            )
-                .map_err(|_| Error::<T>::AcmSetupFailed)?;
+            let input_present = wasmi::Func::wrap(
+                &mut *store,
+                move |mut caller: Caller<'_, HostState>, index: i32| -> Result<i32, wasmi::Error> {
+                    Self::charge(&mut caller, |costs| costs.basic_op)?;
+                    let is_present = usize::try_from(index).ok()
+                        .and_then(|index| present.get(index).copied())
+                        .unwrap_or(false);
+                    Ok(is_present as i32)
+                },
+            );
+
+            let fixed_mul = wasmi::Func::wrap(
+                &mut *store,
+                |mut caller: Caller<'_, HostState>, a: i64, b: i64| -> Result<i64, wasmi::Error> {
+                    Self::charge(&mut caller, |costs| costs.basic_op)?;
+                    fixed::mul(a, b).ok_or_else(|| wasmi::Error::new("Fixed-point overflow"))
+                },
+            );
+
+            let fixed_div = wasmi::Func::wrap(
+                &mut *store,
+                |mut caller: Caller<'_, HostState>, a: i64, b: i64| -> Result<i64, wasmi::Error> {
+                    Self::charge(&mut caller, |costs| costs.basic_op)?;
+                    fixed::div(a, b).ok_or_else(|| wasmi::Error::new("Fixed-point division by zero or overflow"))
+                },
+            );
+
+            let fixed_sqrt = wasmi::Func::wrap(
+                &mut *store,
+                |mut caller: Caller<'_, HostState>, a: i64| -> Result<i64, wasmi::Error> {
+                    Self::charge(&mut caller, |costs| costs.call_op)?;
+                    fixed::sqrt(a).ok_or_else(|| wasmi::Error::new("Square root of a negative number"))
+                },
+            );
+
+            let fixed_ln = wasmi::Func::wrap(
+                &mut *store,
+                |mut caller: Caller<'_, HostState>, a: i64| -> Result<i64, wasmi::Error> {
+                    Self::charge(&mut caller, |costs| costs.call_op)?;
+                    fixed::ln(a).ok_or_else(|| wasmi::Error::new("Logarithm of a non-positive number"))
+                },
+            );
+
+            let params_len = params.len() as i32;
+
+            let host_params_len = wasmi::Func::wrap(
+                &mut *store,
+                move |mut caller: Caller<'_, HostState>| -> Result<i32, wasmi::Error> {
+                    Self::charge(&mut caller, |costs| costs.basic_op)?;
+                    Ok(params_len)
+                },
+            );
+
+            let read_params = wasmi::Func::wrap(
+                &mut *store,
+                move |mut caller: Caller<'_, HostState>, ptr: i32| -> Result<(), wasmi::Error> {
+                    Self::charge(&mut caller, |costs| costs.memory_op.saturating_mul(params.len() as u64 / 32 + 1))?;
+                    memory.write(&mut caller, ptr as u32 as usize, &params)
+                        .map_err(|_| wasmi::Error::new("Params out of bounds"))
+                },
+            );
+
+            let return_data = wasmi::Func::wrap(
+                &mut *store,
+                move |mut caller: Caller<'_, HostState>, ptr: i32, len: i32| -> Result<(), wasmi::Error> {
+                    let len = u32::try_from(len)
+                        .ok()
+                        .filter(|len| *len <= T::MaxReturnDataSize::get())
+                        .ok_or_else(|| wasmi::Error::new("Invalid return data length"))?;
+                    Self::charge(&mut caller, |costs| costs.memory_op.saturating_mul(len as u64 / 32 + 1))?;
+
+                    let mut data = vec![0u8; len as usize];
+                    memory.read(&caller, ptr as u32 as usize, &mut data)
+                        .map_err(|_| wasmi::Error::new("Return data out of bounds"))?;
+                    caller.data_mut().return_data = Some(data);
+                    Ok(())
+                },
+            );
 
-                // TODO (IMP)
-             // get schema indexes for text (CredType::Text) property
-             // remove the attestation indexes at schema indexes.    
+            let abort_func = wasmi::Func::wrap(
+              &mut *store,
+              move |mut caller: Caller<'_, HostState>, message: i32, file: i32, line: i32, column: i32| -> Result<(), wasmi::Error> {
+                  Self::charge(&mut caller, |costs| costs.call_op)?;
+                  let abort = AbortInfo {
+                      message: Self::read_as_string(&caller, memory, message),
+                      file: Self::read_as_string(&caller, memory, file),
+                      line: line as u32,
+                      column: column as u32,
+                  };
+                  log::error!(target: "algo", "Abort called: {:?}", abort);
+                  caller.data_mut().abort = Some(abort);
+                  Err(wasmi::Error::new("Module aborted"))
+              },
+            );
 
+            // Text fields were already left out by `input_layout`
             let bytes = attestations.into_iter().flatten().flatten().collect::<Vec<u8>>();
 
-            memory.write(&mut store, 0, &bytes).map_err(|e| {
+            memory.write(&mut *store, 0, &bytes).map_err(|e| {
                 log::error!(target: "algo", "Memory write error {:?}", e);
                 Error::<T>::AcmMemoryWriteError
             })?;
 
-            store.data_mut().charge(
-                T::GasCost::get().memory_op * (bytes.len() as u64 / 32 + 1))
-                .map_err(|_| Error::<T>::OutOfGas)?;
-
-            // memory.write(&mut store, 0, 5);
+            let fuel = store.get_fuel().map_err(|_| Error::<T>::GasMeteringNotSupported)?
+                .checked_sub(store.data().gas_costs.memory_op.saturating_mul(bytes.len() as u64 / 32 + 1))
+                .ok_or(Error::<T>::OutOfGas)?;
+            store.set_fuel(fuel).map_err(|_| Error::<T>::GasMeteringNotSupported)?;
 
-            let mut linker = <wasmi::Linker<GasMeter>>::new(&engine);
+            let mut linker = <wasmi::Linker<HostState>>::new(store.engine());
             linker.define("host", "print", host_print).map_err(|_| Error::<T>::AcmSetupFailed)?;
+            linker.define("host", "print_str", print_str).map_err(|_| Error::<T>::AcmSetupFailed)?;
+            linker.define("host", "input_present", input_present).map_err(|_| Error::<T>::AcmSetupFailed)?;
+            linker.define("host", "fixed_mul", fixed_mul).map_err(|_| Error::<T>::AcmSetupFailed)?;
+            linker.define("host", "fixed_div", fixed_div).map_err(|_| Error::<T>::AcmSetupFailed)?;
+            linker.define("host", "sqrt", fixed_sqrt).map_err(|_| Error::<T>::AcmSetupFailed)?;
+            linker.define("host", "ln", fixed_ln).map_err(|_| Error::<T>::AcmSetupFailed)?;
+            linker.define("host", "params_len", host_params_len).map_err(|_| Error::<T>::AcmSetupFailed)?;
+            linker.define("host", "read_params", read_params).map_err(|_| Error::<T>::AcmSetupFailed)?;
+            linker.define("host", "return_data", return_data).map_err(|_| Error::<T>::AcmSetupFailed)?;
             linker.define("env", "memory", memory).map_err(|_| Error::<T>::AcmSetupFailed)?;
       
             // Define the abort function in the linker
             linker.define("env", "abort", abort_func).map_err(|_| Error::<T>::AcmSetupFailed)?;
 
-            log::error!(target: "algo", "Algo3 {:?}", bytes.clone());
-            log::error!(target: "algo", "Algo3 {:?}", bytes.len());
+            log::trace!(target: "algo", "Instantiating module with {} bytes of inputs", bytes.len());
 
             let instance = linker
-            .instantiate(&mut store, &module)
+            .instantiate_and_start(&mut *store, module)
             .map_err(|e| {
                 log::error!(target: "algo", "Instantiation error {:?}", e);
                 Error::<T>::AcmLinkerFailed
-            })?
-            .start(&mut store)
-            .map_err(|_| Error::<T>::AcmFailedToStart)?;
+            })?;
+
+            let entry_point = if output_size.is_some() { "calc_output" } else { "calc" };
 
             let calc = instance
-                .get_typed_func::<(), i64>(&store, "calc")
+                .get_typed_func::<(), i64>(&*store, entry_point)
                 .map_err(|_| Error::<T>::AcmFailedToFindCalcFunction)?;
 
             // And finally we can call the wasm!
-            let result = calc.call(&mut store, ()).map_err(|e| {
+            let result = calc.call(&mut *store, ()).map_err(|e| {
                 log::error!(target: "algo", "Execution error {:?}", e);
-                Error::<T>::AcmFailedToCalculate
+                if store.data().abort.is_some() {
+                    return Error::<T>::AlgoAborted;
+                }
+                match e.as_trap_code() {
+                    Some(TrapCode::OutOfFuel) => Error::<T>::OutOfGas,
+                    _ => Error::<T>::AcmFailedToCalculate,
+                }
             })?;
 
             Ok(result)
         }
+
+        /// Read the AssemblyScript string at `ptr`, stored as UTF-16 with its length in bytes
+        /// in the four bytes before it, as UTF-8. Unreadable strings come back empty.
+        fn read_as_string(caller: &Caller<'_, HostState>, memory: wasmi::Memory, ptr: i32) -> Vec<u8> {
+            let ptr = ptr as u32 as usize;
+
+            let mut len = [0u8; 4];
+            if ptr < 4 || memory.read(caller, ptr - 4, &mut len).is_err() {
+                return Vec::new();
+            }
+
+            let mut bytes = vec![0u8; u32::from_le_bytes(len).min(MAX_DEBUG_STRING_LENGTH) as usize];
+            if memory.read(caller, ptr, &mut bytes).is_err() {
+                return Vec::new();
+            }
+
+            let units = bytes.chunks_exact(2).map(|unit| u16::from_le_bytes([unit[0], unit[1]]));
+            char::decode_utf16(units)
+                .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+                .collect::<prelude::string::String>()
+                .into_bytes()
+        }
+    }
+
+    impl<T: Config> credentials::OnAttestationChanged<T::Hash> for Pallet<T> {
+        /// Mark dirty the scores of `acquirer_address` that may have read the attestation,
+        /// for each algorithm watching `schema_hash`, and queue them to be run again under
+        /// `RefreshPolicy::Recompute`. Nothing is run here, so the attester doesn't pay for
+        /// running others' algorithms.
+        fn on_attestation_changed(
+            acquirer_address: &AcquirerAddress,
+            issuer_hash: T::Hash,
+            schema_hash: T::Hash,
+            _attestation_index: u32,
+        ) -> Weight {
+            let db_weight = T::DbWeight::get();
+            let now = frame_system::Pallet::<T>::block_number();
+
+            let mut weight = db_weight.reads(1);
+
+            for algorithm_id in SchemaWatchers::<T>::get(schema_hash) {
+                weight.saturating_accrue(db_weight.reads(3));
+
+                let Some(algorithm) = Algorithms::<T>::get(algorithm_id) else {
+                    continue;
+                };
+                if algorithm.status != AlgoStatus::Active {
+                    continue;
+                }
+                let policy = RefreshPolicies::<T>::get(algorithm_id);
+
+                let mut dirty = Vec::new();
+                for run_issuer in Self::dependent_issuers(algorithm_id, &algorithm, acquirer_address, issuer_hash, schema_hash) {
+                    weight.saturating_accrue(db_weight.reads(1));
+
+                    let key = (algorithm_id, run_issuer, acquirer_address.clone());
+                    if !Scores::<T>::contains_key(&key) {
+                        continue;
+                    }
+
+                    if policy == RefreshPolicy::Recompute {
+                        weight.saturating_accrue(db_weight.writes(1));
+                        RecomputeQueue::<T>::insert(&key, ());
+                    }
+
+                    weight.saturating_accrue(db_weight.writes(1));
+                    DirtyScores::<T>::insert(&key, now);
+                    dirty.push(run_issuer);
+                }
+
+                if !dirty.is_empty() {
+                    Self::deposit_event(Event::ScoresMarkedDirty {
+                        algorithm_id,
+                        account_id: acquirer_address.to_bytes(),
+                        issuer_hashes: dirty,
+                    });
+                }
+            }
+
+            weight
+        }
+
+        fn max_weight() -> Weight {
+            let db_weight = T::DbWeight::get();
+
+            // A dependent issuer per `Caller` input besides those from `ScoreIssuers`
+            let issuers = T::MaxScoreIssuers::get().saturating_add(T::MaxSchemas::get());
+            let per_algorithm = db_weight.reads(3)
+                .saturating_add(db_weight.reads_writes(1, 2).saturating_mul(issuers.into()));
+
+            db_weight.reads(1)
+                .saturating_add(per_algorithm.saturating_mul(T::MaxSchemaWatchers::get().into()))
+        }
     }
 }
@@ -0,0 +1,175 @@
+//! Storage migrations for `pallet_algorithms`.
+//!
+//! Each `vN` module holds the layouts at storage version `N` that later versions changed,
+//! such as `Algorithm` in v0, so a migration keeps translating into the layout it was
+//! written for after the pallet moves on.
+
+use super::*;
+use frame_support::{
+    migrations::VersionedMigration,
+    pallet_prelude::*,
+    traits::{ReservableCurrency, UncheckedOnRuntimeUpgrade},
+};
+use sp_runtime::traits::{Hash, Zero};
+use sp_std::{marker::PhantomData, vec::Vec};
+#[cfg(feature = "try-runtime")]
+use sp_runtime::TryRuntimeError;
+
+/// State a `pre_upgrade` check encoded for its `post_upgrade` check.
+#[cfg(feature = "try-runtime")]
+fn decode_state<S: Decode>(state: &[u8]) -> Result<S, TryRuntimeError> {
+    S::decode(&mut &state[..]).map_err(|_| "Invalid pre-upgrade state".into())
+}
+
+pub mod v0 {
+    use super::*;
+
+    #[derive(Encode, Decode)]
+    pub struct Algorithm<T: Config> {
+        pub schema_hashes: BoundedVec<T::Hash, T::MaxSchemas>,
+        pub code: BoundedVec<u8, T::MaxCodeSize>,
+        pub gas_limit: u64,
+    }
+}
+
+/// Algorithms were a list of schema hashes, a module and a gas limit. Each one now:
+///
+/// - reads its schemas from the issuer it is run for, and fails on missing attestations
+/// - belongs to `LegacyOwner`, without a deposit, and so does its module
+/// - has its module in `CodeByHash`, stored once per distinct module
+/// - is disabled if its module fails the checks of `validate_code`, active otherwise
+/// - keeps its gas limit, capped at `MaxGasLimit`
+/// - returns only its `i64` and takes no parameters
+///
+/// Run requests and submitted outcomes can't have been made against v0 algorithms. Any
+/// found are dropped, returning request deposits, and counted in the log.
+pub mod v1 {
+    use super::*;
+
+    pub struct InnerMigrateV0ToV1<T, LegacyOwner>(PhantomData<(T, LegacyOwner)>);
+
+    impl<T: Config, LegacyOwner: Get<T::AccountId>> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T, LegacyOwner> {
+        fn on_runtime_upgrade() -> Weight {
+            let owner = LegacyOwner::get();
+            let mut translated = 0u64;
+            let mut disabled = 0u64;
+
+            Algorithms::<T>::translate::<v0::Algorithm<T>, _>(|algorithm_id, old| {
+                translated += 1;
+
+                let code_hash = <T as Config>::Hashing::hash(&old.code);
+                let status = match Pallet::<T>::validate_code(&old.code) {
+                    Ok(()) => AlgoStatus::Active,
+                    Err(error) => {
+                        log::warn!(target: "algo", "Disabling algorithm {}, its module fails with {:?}", algorithm_id, error);
+                        disabled += 1;
+                        AlgoStatus::Disabled
+                    }
+                };
+
+                if !CodeByHash::<T>::contains_key(code_hash) {
+                    CodeByHash::<T>::insert(code_hash, old.code);
+                    CodeInfoOf::<T>::insert(code_hash, CodeInfo {
+                        owner: owner.clone(),
+                        deposit: Zero::zero(),
+                        refcount: 0,
+                    });
+                }
+
+                if status == AlgoStatus::Active {
+                    CodeInfoOf::<T>::mutate(code_hash, |code_info| {
+                        if let Some(code_info) = code_info {
+                            code_info.refcount += 1;
+                        }
+                    });
+                }
+
+                let inputs = old.schema_hashes.into_iter()
+                    .map(|schema_hash| AlgoInput { issuer: IssuerSelector::Caller, schema_hash })
+                    .collect::<Vec<_>>();
+
+                Some(Algorithm {
+                    owner: owner.clone(),
+                    name: BoundedVec::default(),
+                    description: BoundedVec::default(),
+                    status,
+                    deposit: Zero::zero(),
+                    // Same bound as `schema_hashes`, so this cannot truncate.
+                    inputs: BoundedVec::truncate_from(inputs),
+                    missing_inputs: MissingInputPolicy::RequireAll,
+                    code_hash,
+                    gas_limit: old.gas_limit.min(T::MaxGasLimit::get()),
+                    output_schema: None,
+                    param_schema: None,
+                })
+            });
+
+            log::info!(target: "algo", "Migrated {} algorithms to v1, {} of them disabled", translated, disabled);
+
+            let mut requests = 0u64;
+            for (_, request) in RunRequests::<T>::drain() {
+                requests += 1;
+                <T as pallet_issuers::Config>::Currency::unreserve(&request.requester, request.deposit);
+            }
+            let expiries = RunRequestExpiries::<T>::clear(u32::MAX, None).unique as u64;
+            let submissions = RunSubmissions::<T>::clear(u32::MAX, None).unique as u64;
+
+            log::info!(
+                target: "algo",
+                "Dropped {} run requests and the outcomes submitted for {} requests",
+                requests,
+                submissions
+            );
+
+            let dropped = requests.saturating_mul(2).saturating_add(expiries).saturating_add(submissions);
+            // Each algorithm may also read and write its code and code info.
+            T::DbWeight::get().reads_writes(translated * 3 + dropped, translated * 3 + dropped)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+            Ok((Algorithms::<T>::iter_keys().count() as u64).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+            let count: u64 = decode_state(&state)?;
+            let owner = LegacyOwner::get();
+            let mut migrated = 0u64;
+            for algorithm in Algorithms::<T>::iter_values() {
+                ensure!(algorithm.owner == owner && algorithm.deposit.is_zero(), "Algorithm not handed to the legacy owner");
+                ensure!(algorithm.gas_limit <= T::MaxGasLimit::get(), "Algorithm gas limit above MaxGasLimit");
+                let code = CodeByHash::<T>::get(algorithm.code_hash).ok_or("Algorithm code not moved to CodeByHash")?;
+                ensure!(
+                    algorithm.status != AlgoStatus::Active || Pallet::<T>::validate_code(&code).is_ok(),
+                    "Active algorithm with a rejected module"
+                );
+                migrated += 1;
+            }
+            ensure!(migrated == count, "Algorithms lost migrating to v1");
+
+            for (code_hash, code_info) in CodeInfoOf::<T>::iter() {
+                let active = Algorithms::<T>::iter_values()
+                    .filter(|algorithm| algorithm.code_hash == code_hash && algorithm.status == AlgoStatus::Active)
+                    .count();
+                ensure!(code_info.refcount as usize == active, "Code refcount doesn't match its active algorithms");
+            }
+
+            ensure!(
+                RunRequests::<T>::count() == 0
+                    && RunRequestExpiries::<T>::iter_keys().next().is_none()
+                    && RunSubmissions::<T>::iter_keys().next().is_none(),
+                "Run requests or submissions left"
+            );
+            Ok(())
+        }
+    }
+
+    pub type MigrateV0ToV1<T, LegacyOwner> = VersionedMigration<
+        0,
+        1,
+        InnerMigrateV0ToV1<T, LegacyOwner>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
@@ -0,0 +1,1548 @@
+#![cfg(test)]
+// Tests for Algorithms Pallet
+
+use super::*;
+use crate::{self as pallet_algorithms, migrations::*};
+
+use codec::Encode;
+use frame_support::{
+    assert_err, assert_noop, assert_ok, derive_impl, parameter_types,
+    traits::{
+        ConstBool, ConstU128, ConstU32, ConstU64, Currency, GetStorageVersion, Hooks, OnRuntimeUpgrade,
+        ReservableCurrency, StorageVersion,
+    },
+    BoundedVec,
+};
+use frame_system::EnsureRoot;
+use pallet_credentials::{AcquirerAddress, CredType};
+use sp_core::{H160, H256};
+use sp_runtime::{
+    testing::TestXt,
+    traits::{BlakeTwo256, Extrinsic as ExtrinsicT, Hash, IdentifyAccount, IdentityLookup, Verify},
+    BuildStorage, DispatchError, MultiSignature,
+};
+
+pub type Signature = MultiSignature;
+pub type AccountPublic = <Signature as Verify>::Signer;
+pub type AccountId = <AccountPublic as IdentifyAccount>::AccountId;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+type Extrinsic = TestXt<RuntimeCall, ()>;
+
+frame_support::construct_runtime!(
+    pub enum Test
+    {
+        System: frame_system,
+        Balances: pallet_balances,
+        Issuers: pallet_issuers,
+        Credentials: pallet_credentials,
+        AlgorithmsModule: pallet_algorithms,
+    }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Block = Block;
+    type AccountData = pallet_balances::AccountData<u128>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+    type Balance = u128;
+    type ExistentialDeposit = ConstU128<1>;
+    type AccountStore = System;
+}
+
+impl pallet_issuers::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Hashing = BlakeTwo256;
+    type Currency = Balances;
+    type MaxNameLength = ConstU32<64>;
+    type MaxControllers = ConstU32<10>;
+    type WeightInfo = ();
+    type IssuerRegistryDeposit = ConstU128<1_000>;
+    type ParamsOrigin = EnsureRoot<AccountId>;
+    type VerifierOrigin = EnsureRoot<AccountId>;
+    type MaxProfileFieldLength = ConstU32<256>;
+    type ProfileDepositPerByte = ConstU128<1>;
+    type MaxRecoveryAccounts = ConstU32<10>;
+    type RecoveryChallengePeriod = ConstU64<10>;
+    type RecoveryDeposit = ConstU128<1_000>;
+}
+
+impl pallet_credentials::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Hashing = BlakeTwo256;
+    type MaxSchemaFields = ConstU32<20>;
+    type MaxSchemaFieldSize = ConstU32<120>;
+    type CredentialsWeightInfo = ();
+    type OnAttestationChanged = AlgorithmsModule;
+}
+
+parameter_types! {
+    pub const TestGasCosts: GasCosts = GasCosts { basic_op: 1, memory_op: 1, call_op: 1 };
+    pub static RequireApproval: bool = true;
+    pub const TestModuleLimits: ModuleLimits = ModuleLimits {
+        functions: 100,
+        globals: 10,
+        tables: 1,
+        table_elements: 100,
+    };
+}
+
+impl pallet_algorithms::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Hashing = BlakeTwo256;
+    type WeightInfo = ();
+    type AuthorityId = pallet_algorithms::crypto::AuthorityId;
+    type AuthorityOrigin = EnsureRoot<AccountId>;
+    type ParamsOrigin = EnsureRoot<AccountId>;
+    type ApprovalOrigin = EnsureRoot<AccountId>;
+    type RequireApproval = RequireApproval;
+    type MaxOffchainAuthorities = ConstU32<5>;
+    type RequiredAgreements = ConstU32<2>;
+    type MaxRunRequests = ConstU32<10>;
+    type RunRequestDeposit = ConstU128<100>;
+    type RunRequestTimeout = ConstU64<20>;
+    type OffchainGasLimit = ConstU64<1_000_000>;
+    type MaxBatchSize = ConstU32<10>;
+    type MaxSchedules = ConstU32<10>;
+    type MaxScheduledSubjects = ConstU32<10>;
+    type MinScheduleInterval = ConstU64<10>;
+    type ScheduleDeposit = ConstU128<100>;
+    type MaxSchemaWatchers = ConstU32<10>;
+    type MaxScoreIssuers = ConstU32<5>;
+    type RefreshDeposit = ConstU128<100>;
+    type MaxSchemas = ConstU32<5>;
+    type MaxTrustedIssuers = ConstU32<5>;
+    type MaxCodeSize = ConstU32<10_000>;
+    type MaxMemoryPages = ConstU32<1>;
+    type MaxReturnDataSize = ConstU32<64>;
+    type MaxParamsSize = ConstU32<64>;
+    type MaxAlgoNameLength = ConstU32<64>;
+    type MaxAlgoDescriptionLength = ConstU32<256>;
+    type AlgorithmDeposit = ConstU128<1_000>;
+    type CodeDepositPerByte = ConstU128<1>;
+    type DefaultGasLimit = ConstU64<100_000>;
+    type MaxGasLimit = ConstU64<1_000_000>;
+    type GasCost = TestGasCosts;
+    type ModuleLimits = TestModuleLimits;
+    type AllowFloats = ConstBool<false>;
+    type InvalidateStaleScores = ConstBool<true>;
+}
+
+impl frame_system::offchain::SigningTypes for Test {
+    type Public = AccountPublic;
+    type Signature = Signature;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Test
+where
+    RuntimeCall: From<C>,
+{
+    type Extrinsic = Extrinsic;
+    type OverarchingCall = RuntimeCall;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Test
+where
+    RuntimeCall: From<LocalCall>,
+{
+    fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+        call: RuntimeCall,
+        _public: AccountPublic,
+        _account: AccountId,
+        nonce: u64,
+    ) -> Option<(RuntimeCall, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+        Some((call, (nonce, ())))
+    }
+}
+
+fn account(n: u8) -> AccountId {
+    AccountId::new([n; 32])
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: (1..=4).map(|n| (account(n), 1_000_000)).collect(),
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(storage);
+    // Events are only recorded from block 1
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+/// Run migration `M` as a runtime upgrade would, with its try-runtime checks when the
+/// feature is on.
+fn run_migration<M: OnRuntimeUpgrade>() {
+    #[cfg(feature = "try-runtime")]
+    assert_ok!(M::try_on_runtime_upgrade(true));
+    #[cfg(not(feature = "try-runtime"))]
+    M::on_runtime_upgrade();
+}
+
+/// Write `value` under `key` as an older runtime would have.
+fn put_raw<V: Encode>(key: &[u8], value: &V) {
+    frame_support::storage::unhashed::put(key, value);
+}
+
+/// `text` compiled to a module.
+fn wasm(text: &str) -> Vec<u8> {
+    wat::parse_str(text).unwrap()
+}
+
+/// Module returning the first eight bytes of its inputs as an `i64`.
+fn calc_module() -> Vec<u8> {
+    wasm(
+        r#"(module
+            (import "env" "memory" (memory 1))
+            (func (export "calc") (result i64) (i64.load (i32.const 0))))"#,
+    )
+}
+
+/// Module returning the sum of its first two `U64` inputs.
+fn sum_module() -> Vec<u8> {
+    wasm(
+        r#"(module
+            (import "env" "memory" (memory 1))
+            (func (export "calc") (result i64) (i64.add (i64.load (i32.const 0)) (i64.load (i32.const 8)))))"#,
+    )
+}
+
+#[test]
+fn migration_to_v1_moves_algorithms_to_the_current_layout() {
+    parameter_types! {
+        pub LegacyOwner: AccountId = AccountId::new([9; 32]);
+    }
+
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(0).put::<AlgorithmsModule>();
+
+        let schema_hash = H256::repeat_byte(1);
+        let shared = calc_module();
+        // Valid wasm, importing a function `run_code` doesn't provide
+        let forbidden = wasm(r#"(module (import "env" "clock" (func)) (func (export "calc") (result i64) (i64.const 0)))"#);
+        let algorithm = |code: &Vec<u8>, gas_limit| v0::Algorithm::<Test> {
+            schema_hashes: BoundedVec::truncate_from(vec![schema_hash]),
+            code: BoundedVec::truncate_from(code.clone()),
+            gas_limit,
+        };
+        put_raw(&Algorithms::<Test>::hashed_key_for(1), &algorithm(&shared, 5_000));
+        put_raw(&Algorithms::<Test>::hashed_key_for(2), &algorithm(&shared, 2_000_000));
+        put_raw(&Algorithms::<Test>::hashed_key_for(3), &algorithm(&forbidden, 5_000));
+
+        run_migration::<v1::MigrateV0ToV1<Test, LegacyOwner>>();
+
+        let shared_hash = BlakeTwo256::hash(&shared);
+        let algorithm = Algorithms::<Test>::get(1).unwrap();
+        assert_eq!(algorithm.inputs.into_inner(), vec![AlgoInput { issuer: IssuerSelector::Caller, schema_hash }]);
+        assert_eq!(algorithm.missing_inputs, MissingInputPolicy::RequireAll);
+        assert_eq!((algorithm.owner, algorithm.deposit, algorithm.status), (LegacyOwner::get(), 0, AlgoStatus::Active));
+        assert!(algorithm.name.is_empty());
+        assert_eq!((algorithm.code_hash, algorithm.gas_limit), (shared_hash, 5_000));
+        assert_eq!((algorithm.output_schema, algorithm.param_schema), (None, None));
+        // Capped at `MaxGasLimit`
+        assert_eq!(Algorithms::<Test>::get(2).unwrap().gas_limit, 1_000_000);
+        assert_eq!(Algorithms::<Test>::get(3).unwrap().status, AlgoStatus::Disabled);
+
+        assert_eq!(CodeByHash::<Test>::get(shared_hash).unwrap().into_inner(), shared);
+        assert_eq!(CodeByHash::<Test>::iter_keys().count(), 2);
+        let code_info = CodeInfoOf::<Test>::get(shared_hash).unwrap();
+        assert_eq!((code_info.owner, code_info.deposit, code_info.refcount), (LegacyOwner::get(), 0, 2));
+        // The code stays for its owner to remove
+        assert_eq!(CodeInfoOf::<Test>::get(BlakeTwo256::hash(&forbidden)).unwrap().refcount, 0);
+        assert_eq!(AlgorithmsModule::on_chain_storage_version(), 1);
+    });
+}
+
+#[test]
+fn migration_to_v1_drops_run_requests_and_submissions() {
+    parameter_types! {
+        pub LegacyOwner: AccountId = AccountId::new([9; 32]);
+    }
+
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(0).put::<AlgorithmsModule>();
+
+        let requester = account(2);
+        assert_ok!(Balances::reserve(&requester, 100));
+        RunRequests::<Test>::insert(3, RunRequest::<Test> {
+            requester: requester.clone(),
+            algorithm_id: 100,
+            code_hash: H256::repeat_byte(7),
+            issuer_hash: H256::repeat_byte(2),
+            account_id: vec![5; 20],
+            params: BoundedVec::default(),
+            requested_at: 1,
+            deposit: 100,
+        });
+        RunRequestExpiries::<Test>::insert(21, BoundedVec::truncate_from(vec![3]));
+        RunSubmissions::<Test>::insert(3, BoundedVec::truncate_from(vec![(account(3), H256::repeat_byte(4))]));
+
+        run_migration::<v1::MigrateV0ToV1<Test, LegacyOwner>>();
+
+        assert_eq!(RunRequests::<Test>::count(), 0);
+        assert!(RunRequestExpiries::<Test>::get(21).is_empty());
+        assert!(RunSubmissions::<Test>::get(3).is_empty());
+        assert_eq!(Balances::reserved_balance(&requester), 0);
+        assert_eq!(AlgorithmsModule::on_chain_storage_version(), 1);
+    });
+}
+
+/// Scale of the fixed-point values `fixed` works on.
+const FIXED_ONE: i64 = 1_000_000_000;
+
+/// Inner value of `x` as a `FixedI64`, rounded as `f64` allows.
+fn to_fixed(x: f64) -> i64 {
+    (x * FIXED_ONE as f64).round() as i64
+}
+
+#[test]
+fn fixed_ln_is_accurate() {
+    assert_eq!(fixed::ln(FIXED_ONE), Some(0));
+    assert_eq!(fixed::ln(2 * FIXED_ONE), Some(693_147_181));
+    assert_eq!(fixed::ln(0), None);
+    assert_eq!(fixed::ln(-FIXED_ONE), None);
+
+    for a in [1, 1_000, 250_000_000, 999_999_999, 1_000_000_001, 2_718_281_828, 10 * FIXED_ONE, 123_456_789_000, i64::MAX] {
+        let expected = to_fixed((a as f64 / FIXED_ONE as f64).ln());
+        let actual = fixed::ln(a).unwrap();
+        // Within 1e-7 of the exact logarithm over the whole range
+        assert!((actual - expected).abs() <= 100, "ln({a}) = {actual}, expected {expected}");
+    }
+}
+
+#[test]
+fn fixed_sqrt_is_accurate() {
+    assert_eq!(fixed::sqrt(0), Some(0));
+    assert_eq!(fixed::sqrt(4 * FIXED_ONE), Some(2 * FIXED_ONE));
+    assert_eq!(fixed::sqrt(FIXED_ONE / 4), Some(FIXED_ONE / 2));
+    assert_eq!(fixed::sqrt(-1), None);
+
+    for a in [1, 2 * FIXED_ONE, 3 * FIXED_ONE, 123_456_789, 987_654_321_000, i64::MAX] {
+        let expected = to_fixed((a as f64 / FIXED_ONE as f64).sqrt());
+        let actual = fixed::sqrt(a).unwrap();
+        assert!((actual - expected).abs() <= 1, "sqrt({a}) = {actual}, expected {expected}");
+    }
+}
+
+#[test]
+fn fixed_mul_and_div_round_and_overflow() {
+    assert_eq!(fixed::mul(3 * FIXED_ONE / 2, 2 * FIXED_ONE), Some(3 * FIXED_ONE));
+    assert_eq!(fixed::div(FIXED_ONE, 4 * FIXED_ONE), Some(FIXED_ONE / 4));
+    assert_eq!(fixed::div(FIXED_ONE, 0), None);
+    assert_eq!(fixed::mul(i64::MAX, 2 * FIXED_ONE), None);
+}
+
+/// `validate_code`, whose errors `check_module` decides for modules the engine would accept.
+fn validate_code(code: &[u8]) -> Result<(), DispatchError> {
+    AlgorithmsModule::validate_code(code).map_err(Into::into)
+}
+
+fn validate(text: &str) -> Result<(), DispatchError> {
+    validate_code(&wasm(text))
+}
+
+#[test]
+fn check_module_accepts_modules_run_code_can_run() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(validate_code(&calc_module()));
+        assert_ok!(validate(
+            r#"(module
+                (import "host" "ln" (func $ln (param i64) (result i64)))
+                (import "env" "abort" (func $abort (param i32 i32 i32 i32)))
+                (memory 1 1)
+                (table 1 funcref)
+                (global i32 (i32.const 0))
+                (func (export "calc_output") (result i64) (call $ln (i64.const 1000000000))))"#
+        ));
+    });
+}
+
+#[test]
+fn check_module_rejects_what_run_code_does_not_provide() {
+    new_test_ext().execute_with(|| {
+        assert_err!(validate(r#"(module (import "env" "clock" (func)) (func (export "calc") (result i64) (i64.const 0)))"#), Error::<Test>::ForbiddenImport);
+        assert_err!(validate(r#"(module (import "host" "time" (func)) (func (export "calc") (result i64) (i64.const 0)))"#), Error::<Test>::ForbiddenImport);
+        assert_err!(
+            validate(r#"(module (import "host" "sqrt" (func (param i32) (result i32))) (func (export "calc") (result i64) (i64.const 0)))"#),
+            Error::<Test>::InvalidImportSignature
+        );
+        assert_err!(
+            validate(r#"(module (import "env" "abort" (func (param i32))) (func (export "calc") (result i64) (i64.const 0)))"#),
+            Error::<Test>::InvalidImportSignature
+        );
+
+        assert_err!(validate(r#"(module (func (export "run") (result i64) (i64.const 0)))"#), Error::<Test>::MissingEntryPoint);
+        assert_err!(validate(r#"(module (func (export "calc") (result i32) (i32.const 0)))"#), Error::<Test>::InvalidEntryPoint);
+        assert_err!(validate(r#"(module (global (export "calc") i64 (i64.const 0)))"#), Error::<Test>::InvalidEntryPoint);
+        assert_err!(
+            validate(r#"(module (func $init) (start $init) (func (export "calc") (result i64) (i64.const 0)))"#),
+            Error::<Test>::StartFunctionNotAllowed
+        );
+    });
+}
+
+#[test]
+fn check_module_rejects_memories_that_do_not_match_max_memory_pages() {
+    new_test_ext().execute_with(|| {
+        let calc = r#"(func (export "calc") (result i64) (i64.const 0))"#;
+
+        // `run_code` provides exactly one page
+        assert_err!(validate(&format!(r#"(module (import "env" "memory" (memory 0 0)) {calc})"#)), Error::<Test>::MemoryTooSmall);
+        assert_err!(validate(&format!(r#"(module (import "env" "memory" (memory 2)) {calc})"#)), Error::<Test>::MemoryTooLarge);
+        assert_err!(validate(&format!(r#"(module (import "env" "memory" (memory 1 2)) {calc})"#)), Error::<Test>::MemoryTooLarge);
+        assert_ok!(validate(&format!(r#"(module (import "env" "memory" (memory 0 1)) {calc})"#)));
+
+        // A module's own memory must cap its growth
+        assert_err!(validate(&format!(r#"(module (memory 1) {calc})"#)), Error::<Test>::MemoryTooLarge);
+        assert_err!(validate(&format!(r#"(module (memory 0 2) {calc})"#)), Error::<Test>::MemoryTooLarge);
+    });
+}
+
+#[test]
+fn check_module_enforces_module_limits() {
+    new_test_ext().execute_with(|| {
+        let calc = r#"(func (export "calc") (result i64) (i64.const 0))"#;
+
+        let functions = "(func)".repeat(100);
+        assert_err!(validate(&format!("(module {functions} {calc})")), Error::<Test>::TooManyFunctions);
+
+        let globals = "(global i32 (i32.const 0))".repeat(11);
+        assert_err!(validate(&format!("(module {globals} {calc})")), Error::<Test>::TooManyGlobals);
+
+        assert_err!(validate(&format!("(module (table 1 funcref) (table 1 funcref) {calc})")), Error::<Test>::TooManyTables);
+        assert_err!(validate(&format!("(module (table 101 funcref) {calc})")), Error::<Test>::TableTooLarge);
+        assert_err!(validate(&format!("(module (table 1 101 funcref) {calc})")), Error::<Test>::TableTooLarge);
+    });
+}
+
+#[test]
+fn validate_code_rejects_invalid_and_oversized_modules() {
+    new_test_ext().execute_with(|| {
+        assert_err!(validate_code(b"\0asm garbage"), Error::<Test>::InvalidWasmProvided);
+        assert_err!(validate_code(&[0u8; 10_001]), Error::<Test>::CodeTooHeavy);
+        assert_err!(
+            validate(r#"(module (func (export "calc") (result i64) (i64.trunc_f64_s (f64.const 1))))"#),
+            Error::<Test>::FloatsNotAllowed
+        );
+    });
+}
+
+/// Subject attested for by `attested_issuer`.
+fn subject() -> Vec<u8> {
+    vec![5; 20]
+}
+
+/// Address `subject()` parses to.
+fn subject_address() -> AcquirerAddress {
+    AcquirerAddress::Ethereum(H160::repeat_byte(5))
+}
+
+/// Issuer `name` controlled by `controller`.
+fn create_issuer(controller: &AccountId, name: &[u8]) -> H256 {
+    assert_ok!(Issuers::create_issuer(RuntimeOrigin::signed(controller.clone()), name.to_vec(), vec![controller.clone()]));
+    pallet_issuers::Pallet::<Test>::issuer_by_name(name).unwrap()
+}
+
+/// Schema of one `U64` named `field`, created by `issuer_hash`.
+fn u64_schema(controller: &AccountId, issuer_hash: H256, field: &[u8]) -> H256 {
+    let schema = vec![(field.to_vec(), CredType::U64)];
+    assert_ok!(Credentials::create_schema(RuntimeOrigin::signed(controller.clone()), issuer_hash, schema.clone()));
+    Credentials::schema_hash(&Credentials::bound_schema(schema).unwrap())
+}
+
+/// Attest `value` of the `U64` schema `schema_hash` for `subject()` as `issuer_hash`.
+fn attest(controller: &AccountId, issuer_hash: H256, schema_hash: H256, value: u64) {
+    assert_ok!(Credentials::attest(
+        RuntimeOrigin::signed(controller.clone()),
+        issuer_hash,
+        schema_hash,
+        subject(),
+        vec![value.to_le_bytes().to_vec()],
+    ));
+}
+
+/// Issuer "Acme" controlled by `controller`, with a schema of one `U64` it attested `value`
+/// for `subject()` with.
+fn attested_issuer(controller: &AccountId, value: u64) -> (H256, H256) {
+    let issuer_hash = create_issuer(controller, b"Acme");
+    let schema_hash = u64_schema(controller, issuer_hash, b"value");
+    attest(controller, issuer_hash, schema_hash, value);
+    (issuer_hash, schema_hash)
+}
+
+/// Save and approve an algorithm of `owner` returning its first input, and return its id.
+fn approved_algorithm(owner: &AccountId, schema_hash: H256) -> u64 {
+    let algorithm_id = NextAlgoId::<Test>::get();
+    assert_ok!(AlgorithmsModule::save_algo(RuntimeOrigin::signed(owner.clone()), vec![schema_hash], calc_module(), None));
+    assert_ok!(AlgorithmsModule::approve_algo(RuntimeOrigin::root(), algorithm_id));
+    algorithm_id
+}
+
+/// Save and approve an algorithm of `owner` running `code` over `inputs`, and return its id.
+fn algorithm_with_inputs(
+    owner: &AccountId,
+    inputs: Vec<AlgoInput<Test>>,
+    missing_inputs: MissingInputPolicy,
+    code: Vec<u8>,
+) -> u64 {
+    let algorithm_id = NextAlgoId::<Test>::get();
+    assert_ok!(AlgorithmsModule::save_algo_with_inputs(
+        RuntimeOrigin::signed(owner.clone()),
+        b"Algo".to_vec(),
+        Vec::new(),
+        inputs,
+        missing_inputs,
+        code,
+        None,
+        None,
+        None,
+    ));
+    assert_ok!(AlgorithmsModule::approve_algo(RuntimeOrigin::root(), algorithm_id));
+    algorithm_id
+}
+
+/// Run `algorithm_id` for `subject()` with `issuer_hash`, paid by `who`.
+fn run(who: &AccountId, issuer_hash: H256, algorithm_id: u64) {
+    assert_ok!(AlgorithmsModule::run_algo_for(
+        RuntimeOrigin::signed(who.clone()),
+        issuer_hash,
+        subject(),
+        algorithm_id,
+        BoundedVec::default(),
+    ));
+}
+
+/// Stored score of `subject()` for `algorithm_id` run with `issuer_hash`.
+fn score(algorithm_id: u64, issuer_hash: H256) -> Option<Score<H256, u64>> {
+    Scores::<Test>::get((algorithm_id, issuer_hash, subject_address()))
+}
+
+/// Issuers the inputs of `score` were read from.
+fn input_issuers(score: &Score<H256, u64>) -> Vec<Option<H256>> {
+    score.inputs.iter().map(|input| input.as_ref().map(|input| input.issuer_hash)).collect()
+}
+
+/// Error and abort of the last `AlgoFailed` event of `algorithm_id`.
+fn last_failure(algorithm_id: u64) -> Option<(DispatchError, Option<AbortInfo>)> {
+    System::events().into_iter().rev().find_map(|record| match record.event {
+        RuntimeEvent::AlgorithmsModule(Event::AlgoFailed { algorithm_id: id, error, abort, .. }) if id == algorithm_id => {
+            Some((error, abort))
+        }
+        _ => None,
+    })
+}
+
+#[test]
+fn inputs_are_read_from_the_issuers_they_select() {
+    new_test_ext().execute_with(|| {
+        let (owner, controller) = (account(1), account(2));
+        let acme = create_issuer(&controller, b"Acme");
+        let beta = create_issuer(&controller, b"Beta");
+        let gamma = create_issuer(&controller, b"Gamma");
+        let schema_hash = u64_schema(&controller, acme, b"value");
+        attest(&controller, beta, schema_hash, 10);
+        attest(&controller, acme, schema_hash, 5);
+
+        let inputs = vec![
+            AlgoInput { issuer: IssuerSelector::Fixed(beta), schema_hash },
+            // Gamma attested nothing, so Acme is read
+            AlgoInput { issuer: IssuerSelector::AnyOf(BoundedVec::truncate_from(vec![gamma, acme])), schema_hash },
+        ];
+        let algorithm_id = algorithm_with_inputs(&owner, inputs.clone(), MissingInputPolicy::RequireAll, sum_module());
+        System::assert_has_event(Event::<Test>::AlgorithmAdded { algorithm_id, owner: owner.clone(), inputs }.into());
+
+        // Neither input reads from the issuer the algorithm is run with
+        run(&owner, gamma, algorithm_id);
+
+        let score = score(algorithm_id, gamma).unwrap();
+        assert_eq!(score.result, 15);
+        assert_eq!(input_issuers(&score), vec![Some(beta), Some(acme)]);
+    });
+}
+
+#[test]
+fn inputs_need_an_attestation_from_a_selected_issuer() {
+    new_test_ext().execute_with(|| {
+        let (owner, controller) = (account(1), account(2));
+        let (acme, schema_hash) = attested_issuer(&controller, 5);
+        let beta = create_issuer(&controller, b"Beta");
+
+        let fixed = vec![AlgoInput { issuer: IssuerSelector::Fixed(beta), schema_hash }];
+        let algorithm_id = algorithm_with_inputs(&owner, fixed, MissingInputPolicy::RequireAll, calc_module());
+        assert_noop!(
+            AlgorithmsModule::run_algo_for(RuntimeOrigin::signed(owner.clone()), acme, subject(), algorithm_id, BoundedVec::default()),
+            Error::<Test>::AttestationNotFound
+        );
+
+        assert_noop!(
+            AlgorithmsModule::save_algo_with_inputs(
+                RuntimeOrigin::signed(owner),
+                Vec::new(),
+                Vec::new(),
+                vec![AlgoInput { issuer: IssuerSelector::AnyOf(BoundedVec::default()), schema_hash }],
+                MissingInputPolicy::RequireAll,
+                calc_module(),
+                None,
+                None,
+                None,
+            ),
+            Error::<Test>::NoTrustedIssuers
+        );
+    });
+}
+
+#[test]
+fn best_effort_runs_zero_fill_missing_inputs() {
+    new_test_ext().execute_with(|| {
+        let (owner, controller) = (account(1), account(2));
+        let (issuer_hash, attested) = attested_issuer(&controller, 5);
+        let missing = u64_schema(&controller, issuer_hash, b"other");
+        let inputs = vec![
+            AlgoInput { issuer: IssuerSelector::Caller, schema_hash: missing },
+            AlgoInput { issuer: IssuerSelector::Caller, schema_hash: attested },
+        ];
+        // 10 if the first input is present, plus 1 if the second is
+        let presence = wasm(
+            r#"(module
+                (import "host" "input_present" (func $present (param i32) (result i32)))
+                (import "env" "memory" (memory 1))
+                (func (export "calc") (result i64)
+                    (i64.extend_i32_u
+                        (i32.add
+                            (i32.mul (call $present (i32.const 0)) (i32.const 10))
+                            (call $present (i32.const 1))))))"#,
+        );
+
+        let require_all = algorithm_with_inputs(&owner, inputs.clone(), MissingInputPolicy::RequireAll, presence.clone());
+        assert_noop!(
+            AlgorithmsModule::run_algo_for(RuntimeOrigin::signed(owner.clone()), issuer_hash, subject(), require_all, BoundedVec::default()),
+            Error::<Test>::AttestationNotFound
+        );
+
+        let best_effort = algorithm_with_inputs(&owner, inputs.clone(), MissingInputPolicy::BestEffort, presence);
+        run(&owner, issuer_hash, best_effort);
+        let presence_score = score(best_effort, issuer_hash).unwrap();
+        assert_eq!(presence_score.result, 1);
+        assert_eq!(input_issuers(&presence_score), vec![None, Some(issuer_hash)]);
+
+        // The missing input takes its place as zeroes, so the second keeps its offset
+        let sum = algorithm_with_inputs(&owner, inputs, MissingInputPolicy::BestEffort, sum_module());
+        run(&owner, issuer_hash, sum);
+        assert_eq!(score(sum, issuer_hash).unwrap().result, 5);
+    });
+}
+
+#[test]
+fn runs_store_a_score_until_an_input_it_read_changes() {
+    new_test_ext().execute_with(|| {
+        let (owner, controller) = (account(1), account(2));
+        let (issuer_hash, schema_hash) = attested_issuer(&controller, 42);
+        let algorithm_id = approved_algorithm(&owner, schema_hash);
+
+        System::set_block_number(3);
+        run(&owner, issuer_hash, algorithm_id);
+
+        let stored = score(algorithm_id, issuer_hash).unwrap();
+        assert_eq!((stored.result, stored.block_number, stored.data.clone()), (42, 3, None));
+        let input = stored.inputs[0].clone().unwrap();
+        assert_eq!((input.issuer_hash, input.schema_hash, input.attestation_index), (issuer_hash, schema_hash, 0));
+        assert_eq!(AlgorithmsModule::score_of(algorithm_id, issuer_hash, subject()), Some(stored.clone()));
+        System::assert_has_event(
+            Event::<Test>::AlgoResult { algorithm_id, result: 42, issuer_hash, account_id: subject(), data: None }.into(),
+        );
+
+        // A later attestation leaves the one read in place
+        attest(&controller, issuer_hash, schema_hash, 7);
+        assert!(AlgorithmsModule::score_of(algorithm_id, issuer_hash, subject()).is_some());
+
+        assert_ok!(Credentials::update_attestation(
+            RuntimeOrigin::signed(controller.clone()),
+            issuer_hash,
+            schema_hash,
+            subject(),
+            0,
+            vec![43u64.to_le_bytes().to_vec()],
+        ));
+        assert!(AlgorithmsModule::is_stale(&subject_address(), &stored));
+        assert_eq!(AlgorithmsModule::score_of(algorithm_id, issuer_hash, subject()), None);
+        // Stale scores stay stored until the next run
+        assert_eq!(score(algorithm_id, issuer_hash), Some(stored));
+
+        System::set_block_number(4);
+        run(&owner, issuer_hash, algorithm_id);
+        let fresh = AlgorithmsModule::score_of(algorithm_id, issuer_hash, subject()).unwrap();
+        assert_eq!((fresh.result, fresh.block_number), (7, 4));
+        assert_eq!(fresh.inputs[0].as_ref().map(|input| input.attestation_index), Some(1));
+    });
+}
+
+#[test]
+fn only_owners_update_and_disable_their_algorithms() {
+    new_test_ext().execute_with(|| {
+        let (owner, controller) = (account(1), account(2));
+        let (issuer_hash, schema_hash) = attested_issuer(&controller, 42);
+        let algorithm_id = approved_algorithm(&owner, schema_hash);
+        let code_hash = Algorithms::<Test>::get(algorithm_id).unwrap().code_hash;
+
+        assert_noop!(
+            AlgorithmsModule::update_algo(RuntimeOrigin::signed(controller.clone()), algorithm_id, Some(b"Mine".to_vec()), None, None, None),
+            Error::<Test>::NotAlgoOwner
+        );
+        assert_noop!(
+            AlgorithmsModule::update_algo(RuntimeOrigin::signed(owner.clone()), algorithm_id, None, None, None, Some(1_000_001)),
+            Error::<Test>::GasLimitTooHigh
+        );
+        assert_ok!(AlgorithmsModule::update_algo(
+            RuntimeOrigin::signed(owner.clone()),
+            algorithm_id,
+            Some(b"Renamed".to_vec()),
+            None,
+            None,
+            Some(5_000),
+        ));
+        let algorithm = Algorithms::<Test>::get(algorithm_id).unwrap();
+        assert_eq!((algorithm.name.into_inner(), algorithm.gas_limit), (b"Renamed".to_vec(), 5_000));
+        System::assert_last_event(Event::<Test>::AlgorithmUpdated { algorithm_id, code_hash }.into());
+
+        let reserved = Balances::reserved_balance(&owner);
+        assert_noop!(
+            AlgorithmsModule::disable_algo(RuntimeOrigin::signed(controller.clone()), algorithm_id),
+            Error::<Test>::NotAlgoOwner
+        );
+        assert_ok!(AlgorithmsModule::disable_algo(RuntimeOrigin::signed(owner.clone()), algorithm_id));
+        System::assert_last_event(Event::<Test>::AlgorithmDisabled { algorithm_id }.into());
+        assert_eq!(Balances::reserved_balance(&owner), reserved - algorithm.deposit);
+        assert_eq!(CodeInfoOf::<Test>::get(code_hash).unwrap().refcount, 0);
+
+        assert_noop!(
+            AlgorithmsModule::run_algo_for(RuntimeOrigin::signed(owner.clone()), issuer_hash, subject(), algorithm_id, BoundedVec::default()),
+            Error::<Test>::AlgoDisabled
+        );
+        assert_noop!(
+            AlgorithmsModule::update_algo(RuntimeOrigin::signed(owner.clone()), algorithm_id, Some(b"Again".to_vec()), None, None, None),
+            Error::<Test>::AlgoDisabled
+        );
+        assert_noop!(AlgorithmsModule::disable_algo(RuntimeOrigin::signed(owner), algorithm_id), Error::<Test>::AlgoDisabled);
+    });
+}
+
+#[test]
+fn algorithms_change_owner_once_the_new_owner_accepts() {
+    new_test_ext().execute_with(|| {
+        let (owner, new_owner) = (account(1), account(2));
+        let algorithm_id = approved_algorithm(&owner, H256::repeat_byte(1));
+        let deposit = Algorithms::<Test>::get(algorithm_id).unwrap().deposit;
+        let owner_reserved = Balances::reserved_balance(&owner);
+
+        assert_noop!(
+            AlgorithmsModule::transfer_algo_ownership(RuntimeOrigin::signed(new_owner.clone()), algorithm_id, new_owner.clone()),
+            Error::<Test>::NotAlgoOwner
+        );
+        assert_noop!(
+            AlgorithmsModule::accept_algo_ownership(RuntimeOrigin::signed(new_owner.clone()), algorithm_id),
+            Error::<Test>::NotPendingOwner
+        );
+
+        assert_ok!(AlgorithmsModule::transfer_algo_ownership(RuntimeOrigin::signed(owner.clone()), algorithm_id, new_owner.clone()));
+        System::assert_last_event(
+            Event::<Test>::AlgorithmOwnershipOffered { algorithm_id, from: owner.clone(), to: new_owner.clone() }.into(),
+        );
+        // The offer alone changes nothing
+        assert_eq!(Algorithms::<Test>::get(algorithm_id).unwrap().owner, owner);
+        assert_noop!(
+            AlgorithmsModule::accept_algo_ownership(RuntimeOrigin::signed(account(3)), algorithm_id),
+            Error::<Test>::NotPendingOwner
+        );
+
+        assert_ok!(AlgorithmsModule::accept_algo_ownership(RuntimeOrigin::signed(new_owner.clone()), algorithm_id));
+        System::assert_last_event(
+            Event::<Test>::AlgorithmOwnershipTransferred { algorithm_id, from: owner.clone(), to: new_owner.clone() }.into(),
+        );
+        assert_eq!(Algorithms::<Test>::get(algorithm_id).unwrap().owner, new_owner);
+        assert_eq!(Balances::reserved_balance(&owner), owner_reserved - deposit);
+        assert_eq!(Balances::reserved_balance(&new_owner), deposit);
+        assert!(PendingOwners::<Test>::get(algorithm_id).is_none());
+
+        // A withdrawn offer can't be accepted
+        assert_ok!(AlgorithmsModule::transfer_algo_ownership(RuntimeOrigin::signed(new_owner.clone()), algorithm_id, owner.clone()));
+        assert_ok!(AlgorithmsModule::transfer_algo_ownership(RuntimeOrigin::signed(new_owner.clone()), algorithm_id, new_owner));
+        assert_noop!(
+            AlgorithmsModule::accept_algo_ownership(RuntimeOrigin::signed(owner), algorithm_id),
+            Error::<Test>::NotPendingOwner
+        );
+    });
+}
+
+#[test]
+fn uploaded_code_is_removable_once_no_algorithm_uses_it() {
+    new_test_ext().execute_with(|| {
+        let (uploader, owner) = (account(1), account(2));
+        let code = calc_module();
+        let code_hash = BlakeTwo256::hash(&code);
+
+        assert_ok!(AlgorithmsModule::upload_code(RuntimeOrigin::signed(uploader.clone()), code.clone()));
+        System::assert_last_event(Event::<Test>::CodeUploaded { code_hash, owner: uploader.clone() }.into());
+        assert_eq!(Balances::reserved_balance(&uploader), code.len() as u128);
+        assert_noop!(
+            AlgorithmsModule::upload_code(RuntimeOrigin::signed(owner.clone()), code.clone()),
+            Error::<Test>::CodeAlreadyExists
+        );
+
+        // Anyone may create algorithms from uploaded code
+        let algorithm_id = NextAlgoId::<Test>::get();
+        assert_ok!(AlgorithmsModule::create_algo(
+            RuntimeOrigin::signed(owner.clone()),
+            b"Algo".to_vec(),
+            Vec::new(),
+            vec![AlgoInput { issuer: IssuerSelector::Caller, schema_hash: H256::repeat_byte(1) }],
+            MissingInputPolicy::RequireAll,
+            code_hash,
+            None,
+            None,
+            None,
+        ));
+        assert_eq!(Balances::reserved_balance(&owner), 1_000 + code.len() as u128);
+        assert_eq!(CodeInfoOf::<Test>::get(code_hash).unwrap().refcount, 1);
+        assert_noop!(
+            AlgorithmsModule::create_algo(
+                RuntimeOrigin::signed(owner.clone()),
+                Vec::new(),
+                Vec::new(),
+                Vec::new(),
+                MissingInputPolicy::RequireAll,
+                H256::repeat_byte(9),
+                None,
+                None,
+                None,
+            ),
+            Error::<Test>::CodeNotFound
+        );
+
+        assert_noop!(AlgorithmsModule::remove_code(RuntimeOrigin::signed(uploader.clone()), code_hash), Error::<Test>::CodeInUse);
+        assert_ok!(AlgorithmsModule::disable_algo(RuntimeOrigin::signed(owner.clone()), algorithm_id));
+
+        assert_noop!(AlgorithmsModule::remove_code(RuntimeOrigin::signed(owner), code_hash), Error::<Test>::NotCodeOwner);
+        assert_ok!(AlgorithmsModule::remove_code(RuntimeOrigin::signed(uploader.clone()), code_hash));
+        System::assert_last_event(Event::<Test>::CodeRemoved { code_hash }.into());
+        assert_eq!(Balances::reserved_balance(&uploader), 0);
+        assert!(CodeByHash::<Test>::get(code_hash).is_none());
+        assert_noop!(AlgorithmsModule::remove_code(RuntimeOrigin::signed(uploader), code_hash), Error::<Test>::CodeNotFound);
+    });
+}
+
+#[test]
+fn algorithms_with_an_output_schema_return_data_laid_out_as_it() {
+    new_test_ext().execute_with(|| {
+        let (owner, controller) = (account(1), account(2));
+        let (issuer_hash, schema_hash) = attested_issuer(&controller, 42);
+        let output_schema = u64_schema(&controller, issuer_hash, b"bonus");
+        let save = |code: Vec<u8>| {
+            AlgorithmsModule::save_algo_with_inputs(
+                RuntimeOrigin::signed(owner.clone()),
+                b"Algo".to_vec(),
+                Vec::new(),
+                vec![AlgoInput { issuer: IssuerSelector::Caller, schema_hash }],
+                MissingInputPolicy::RequireAll,
+                code,
+                None,
+                Some(output_schema),
+                None,
+            )
+        };
+
+        // Algorithms with an output schema are run through `calc_output`
+        assert_noop!(save(calc_module()), Error::<Test>::MissingEntryPoint);
+
+        let returning = NextAlgoId::<Test>::get();
+        assert_ok!(save(wasm(
+            r#"(module
+                (import "host" "return_data" (func $return_data (param i32 i32)))
+                (import "env" "memory" (memory 1))
+                (func (export "calc_output") (result i64)
+                    (i64.store (i32.const 100) (i64.const 7))
+                    (call $return_data (i32.const 100) (i32.const 8))
+                    (i64.load (i32.const 0))))"#,
+        )));
+        assert_ok!(AlgorithmsModule::approve_algo(RuntimeOrigin::root(), returning));
+        run(&owner, issuer_hash, returning);
+
+        let data = Some(7u64.to_le_bytes().to_vec());
+        assert_eq!(score(returning, issuer_hash).unwrap().data, data);
+        System::assert_last_event(
+            Event::<Test>::AlgoResult { algorithm_id: returning, result: 42, issuer_hash, account_id: subject(), data }.into(),
+        );
+
+        let silent = NextAlgoId::<Test>::get();
+        assert_ok!(save(wasm(
+            r#"(module
+                (import "env" "memory" (memory 1))
+                (func (export "calc_output") (result i64) (i64.load (i32.const 0))))"#,
+        )));
+        assert_ok!(AlgorithmsModule::approve_algo(RuntimeOrigin::root(), silent));
+        run(&owner, issuer_hash, silent);
+
+        assert!(score(silent, issuer_hash).is_none());
+        assert_eq!(last_failure(silent), Some((DispatchError::from(Error::<Test>::MissingReturnData), None)));
+    });
+}
+
+#[test]
+fn algorithms_with_a_param_schema_are_run_with_params_laid_out_as_it() {
+    new_test_ext().execute_with(|| {
+        let (owner, controller) = (account(1), account(2));
+        let (issuer_hash, schema_hash) = attested_issuer(&controller, 42);
+        let param_schema = u64_schema(&controller, issuer_hash, b"weight");
+
+        let algorithm_id = NextAlgoId::<Test>::get();
+        assert_ok!(AlgorithmsModule::save_algo_with_inputs(
+            RuntimeOrigin::signed(owner.clone()),
+            b"Algo".to_vec(),
+            Vec::new(),
+            vec![AlgoInput { issuer: IssuerSelector::Caller, schema_hash }],
+            MissingInputPolicy::RequireAll,
+            // Input plus parameter plus the length of the parameters
+            wasm(
+                r#"(module
+                    (import "host" "params_len" (func $params_len (result i32)))
+                    (import "host" "read_params" (func $read_params (param i32)))
+                    (import "env" "memory" (memory 1))
+                    (func (export "calc") (result i64)
+                        (call $read_params (i32.const 200))
+                        (i64.add
+                            (i64.add (i64.load (i32.const 0)) (i64.load (i32.const 200)))
+                            (i64.extend_i32_u (call $params_len)))))"#,
+            ),
+            None,
+            None,
+            Some(param_schema),
+        ));
+        assert_ok!(AlgorithmsModule::approve_algo(RuntimeOrigin::root(), algorithm_id));
+
+        let run_with = |params: Vec<u8>| {
+            AlgorithmsModule::run_algo_for(
+                RuntimeOrigin::signed(owner.clone()),
+                issuer_hash,
+                subject(),
+                algorithm_id,
+                BoundedVec::truncate_from(params),
+            )
+        };
+        assert_noop!(run_with(Vec::new()), Error::<Test>::ParamsMismatch);
+        assert_noop!(run_with(vec![0; 4]), Error::<Test>::ParamsMismatch);
+
+        assert_ok!(run_with(100u64.to_le_bytes().to_vec()));
+        assert_eq!(score(algorithm_id, issuer_hash).unwrap().result, 150);
+
+        // Algorithms without a param schema take no parameters
+        let plain = approved_algorithm(&owner, schema_hash);
+        assert_noop!(
+            AlgorithmsModule::run_algo_for(
+                RuntimeOrigin::signed(owner.clone()),
+                issuer_hash,
+                subject(),
+                plain,
+                BoundedVec::truncate_from(vec![1]),
+            ),
+            Error::<Test>::ParamsMismatch
+        );
+
+        let text = vec![(b"label".to_vec(), CredType::Text)];
+        assert_ok!(Credentials::create_schema(RuntimeOrigin::signed(controller.clone()), issuer_hash, text.clone()));
+        let text_schema = Credentials::schema_hash(&Credentials::bound_schema(text).unwrap());
+        assert_noop!(
+            AlgorithmsModule::save_algo_with_inputs(
+                RuntimeOrigin::signed(owner),
+                Vec::new(),
+                Vec::new(),
+                Vec::new(),
+                MissingInputPolicy::RequireAll,
+                calc_module(),
+                None,
+                None,
+                Some(text_schema),
+            ),
+            Error::<Test>::SchemaHasText
+        );
+    });
+}
+
+#[test]
+fn print_str_logs_without_failing_the_run() {
+    new_test_ext().execute_with(|| {
+        let code = wasm(
+            r#"(module
+                (import "host" "print_str" (func $print_str (param i32 i32)))
+                (import "env" "memory" (memory 1))
+                (data (i32.const 100) "hi")
+                (func (export "calc") (result i64)
+                    (call $print_str (i32.const 100) (i32.const 2))
+                    ;; Out of bounds, so nothing is logged
+                    (call $print_str (i32.const 65535) (i32.const 100))
+                    (i64.const 1)))"#,
+        );
+
+        let output = AlgorithmsModule::run_code(code, Vec::new(), Vec::new(), Vec::new(), 10_000, None).unwrap();
+        assert_eq!((output.result, output.data), (1, None));
+    });
+}
+
+#[test]
+fn aborts_are_reported_with_their_decoded_message_and_location() {
+    new_test_ext().execute_with(|| {
+        let (owner, controller) = (account(1), account(2));
+        let (issuer_hash, schema_hash) = attested_issuer(&controller, 42);
+        // AssemblyScript strings: UTF-16, with their byte length in the four bytes before them
+        let code = wasm(
+            r#"(module
+                (import "env" "abort" (func $abort (param i32 i32 i32 i32)))
+                (import "env" "memory" (memory 1))
+                (data (i32.const 96) "\04\00\00\00h\00i\00")
+                (data (i32.const 116) "\08\00\00\00a\00.\00t\00s\00")
+                (func (export "calc") (result i64)
+                    (call $abort (i32.const 100) (i32.const 120) (i32.const 3) (i32.const 7))
+                    (i64.const 0)))"#,
+        );
+        let abort = AbortInfo { message: b"hi".to_vec(), file: b"a.ts".to_vec(), line: 3, column: 7 };
+
+        let failure = AlgorithmsModule::run_code(code.clone(), Vec::new(), Vec::new(), Vec::new(), 10_000, None).unwrap_err();
+        assert!(matches!(failure.error, Error::<Test>::AlgoAborted));
+        assert_eq!(failure.abort, Some(abort.clone()));
+
+        let inputs = vec![AlgoInput { issuer: IssuerSelector::Caller, schema_hash }];
+        let algorithm_id = algorithm_with_inputs(&owner, inputs, MissingInputPolicy::RequireAll, code);
+        run(&owner, issuer_hash, algorithm_id);
+
+        assert!(score(algorithm_id, issuer_hash).is_none());
+        assert_eq!(last_failure(algorithm_id), Some((DispatchError::from(Error::<Test>::AlgoAborted), Some(abort))));
+    });
+}
+
+#[test]
+fn requested_runs_are_stored_once_enough_authorities_agree() {
+    new_test_ext().execute_with(|| {
+        let (owner, controller) = (account(1), account(2));
+        let (issuer_hash, schema_hash) = attested_issuer(&controller, 42);
+        let algorithm_id = approved_algorithm(&owner, schema_hash);
+        let authorities = BoundedVec::truncate_from(vec![account(1), account(3), account(4)]);
+        assert_noop!(
+            AlgorithmsModule::set_offchain_authorities(RuntimeOrigin::signed(owner.clone()), authorities.clone()),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(AlgorithmsModule::set_offchain_authorities(RuntimeOrigin::root(), authorities));
+
+        let reserved = Balances::reserved_balance(&controller);
+        let request_id = NextRunRequestId::<Test>::get();
+        assert_ok!(AlgorithmsModule::request_algo_run(
+            RuntimeOrigin::signed(controller.clone()),
+            issuer_hash,
+            subject(),
+            algorithm_id,
+            BoundedVec::default(),
+        ));
+        System::assert_last_event(
+            Event::<Test>::AlgoRunRequested { request_id, algorithm_id, issuer_hash, account_id: subject() }.into(),
+        );
+        assert_eq!(Balances::reserved_balance(&controller), reserved + 100);
+
+        let outcome = |result| RunOutcome::Success { result, data: None, inputs: vec![] };
+        let submit = |authority: u8, result| {
+            AlgorithmsModule::submit_algo_run(RuntimeOrigin::signed(account(authority)), request_id, outcome(result))
+        };
+        assert_noop!(submit(2, 42), Error::<Test>::NotOffchainAuthority);
+        assert_ok!(submit(1, 42));
+        assert_noop!(submit(1, 42), Error::<Test>::AlreadySubmitted);
+
+        // Two outcomes, but they disagree
+        assert_ok!(submit(3, 7));
+        assert!(score(algorithm_id, issuer_hash).is_none());
+        assert!(RunRequests::<Test>::contains_key(request_id));
+
+        assert_ok!(submit(4, 42));
+        assert_eq!(score(algorithm_id, issuer_hash).unwrap().result, 42);
+        assert!(!RunRequests::<Test>::contains_key(request_id));
+        assert!(RunSubmissions::<Test>::get(request_id).is_empty());
+        assert_eq!(Balances::reserved_balance(&controller), reserved);
+        assert_noop!(submit(3, 42), Error::<Test>::RunRequestNotFound);
+    });
+}
+
+#[test]
+fn run_requests_expire_after_the_timeout() {
+    new_test_ext().execute_with(|| {
+        let (owner, controller) = (account(1), account(2));
+        let (issuer_hash, schema_hash) = attested_issuer(&controller, 42);
+        let algorithm_id = approved_algorithm(&owner, schema_hash);
+
+        let reserved = Balances::reserved_balance(&controller);
+        let request_id = NextRunRequestId::<Test>::get();
+        assert_ok!(AlgorithmsModule::request_algo_run(
+            RuntimeOrigin::signed(controller.clone()),
+            issuer_hash,
+            subject(),
+            algorithm_id,
+            BoundedVec::default(),
+        ));
+
+        AlgorithmsModule::on_initialize(20);
+        assert!(RunRequests::<Test>::contains_key(request_id));
+
+        System::set_block_number(21);
+        AlgorithmsModule::on_initialize(21);
+        System::assert_last_event(Event::<Test>::AlgoRunExpired { request_id }.into());
+        assert!(!RunRequests::<Test>::contains_key(request_id));
+        assert_eq!(Balances::reserved_balance(&controller), reserved);
+    });
+}
+
+#[test]
+fn batch_runs_report_each_subject_without_failing() {
+    new_test_ext().execute_with(|| {
+        let (owner, controller) = (account(1), account(2));
+        let (issuer_hash, schema_hash) = attested_issuer(&controller, 42);
+        let algorithm_id = approved_algorithm(&owner, schema_hash);
+        // Nothing was attested for the second subject
+        let accounts = BoundedVec::truncate_from(vec![subject(), vec![6; 20]]);
+
+        assert_ok!(AlgorithmsModule::run_algo_batch(
+            RuntimeOrigin::signed(owner),
+            issuer_hash,
+            algorithm_id,
+            accounts,
+            BoundedVec::default(),
+        ));
+
+        assert_eq!(score(algorithm_id, issuer_hash).unwrap().result, 42);
+        assert_eq!(last_failure(algorithm_id), Some((DispatchError::from(Error::<Test>::AttestationNotFound), None)));
+        System::assert_last_event(
+            Event::<Test>::AlgoBatchRun { algorithm_id, issuer_hash, succeeded: 1, failed: 1 }.into(),
+        );
+    });
+}
+
+#[test]
+fn schedules_run_their_subjects_every_interval_until_cancelled() {
+    new_test_ext().execute_with(|| {
+        let (owner, controller) = (account(1), account(2));
+        let (issuer_hash, schema_hash) = attested_issuer(&controller, 42);
+        let algorithm_id = approved_algorithm(&owner, schema_hash);
+        let reserved = Balances::reserved_balance(&owner);
+        let signed = || RuntimeOrigin::signed(owner.clone());
+
+        assert_noop!(
+            AlgorithmsModule::create_schedule(signed(), issuer_hash, algorithm_id, BoundedVec::default(), 9),
+            Error::<Test>::ScheduleIntervalTooShort
+        );
+        let schedule_id = NextScheduleId::<Test>::get();
+        assert_ok!(AlgorithmsModule::create_schedule(signed(), issuer_hash, algorithm_id, BoundedVec::default(), 10));
+        assert_eq!(Balances::reserved_balance(&owner), reserved + 100);
+
+        assert_noop!(
+            AlgorithmsModule::add_scheduled_subjects(
+                RuntimeOrigin::signed(controller),
+                schedule_id,
+                BoundedVec::truncate_from(vec![subject()]),
+            ),
+            Error::<Test>::NotScheduleOwner
+        );
+        // Subjects are only counted once
+        assert_ok!(AlgorithmsModule::add_scheduled_subjects(
+            signed(),
+            schedule_id,
+            BoundedVec::truncate_from(vec![subject(), subject()]),
+        ));
+        assert_eq!(Schedules::<Test>::get(schedule_id).unwrap().subjects, 1);
+
+        AlgorithmsModule::on_initialize(10);
+        AlgorithmsModule::on_idle(10, frame_support::weights::Weight::MAX);
+        assert!(score(algorithm_id, issuer_hash).is_none());
+
+        System::set_block_number(11);
+        AlgorithmsModule::on_initialize(11);
+        AlgorithmsModule::on_idle(11, frame_support::weights::Weight::MAX);
+        assert_eq!(score(algorithm_id, issuer_hash).unwrap().block_number, 11);
+        System::assert_last_event(Event::<Test>::ScheduledRunCompleted { schedule_id }.into());
+        assert_eq!(Schedules::<Test>::get(schedule_id).unwrap().next_run, 21);
+        assert!(ScheduledRunQueue::<Test>::get().is_empty());
+
+        assert_ok!(AlgorithmsModule::cancel_schedule(signed(), schedule_id));
+        System::assert_last_event(Event::<Test>::ScheduleCancelled { schedule_id }.into());
+        assert_eq!(Balances::reserved_balance(&owner), reserved);
+        assert!(ScheduledSubjects::<Test>::get(schedule_id, subject_address()).is_none());
+
+        // Nothing is run once cancelled
+        System::set_block_number(21);
+        AlgorithmsModule::on_initialize(21);
+        assert!(ScheduledRunQueue::<Test>::get().is_empty());
+    });
+}
+
+#[test]
+fn refresh_policies_mark_dirty_or_recompute_scores_on_new_attestations() {
+    new_test_ext().execute_with(|| {
+        let (owner, controller) = (account(1), account(2));
+        let (issuer_hash, schema_hash) = attested_issuer(&controller, 42);
+        let algorithm_id = approved_algorithm(&owner, schema_hash);
+        let key = (algorithm_id, issuer_hash, subject_address());
+        run(&owner, issuer_hash, algorithm_id);
+        let reserved = Balances::reserved_balance(&owner);
+
+        assert_ok!(AlgorithmsModule::set_refresh_policy(RuntimeOrigin::signed(owner.clone()), algorithm_id, RefreshPolicy::MarkDirty));
+        System::assert_last_event(Event::<Test>::RefreshPolicySet { algorithm_id, policy: RefreshPolicy::MarkDirty }.into());
+        assert_eq!(Balances::reserved_balance(&owner), reserved + 100);
+
+        attest(&controller, issuer_hash, schema_hash, 7);
+        System::assert_has_event(
+            Event::<Test>::ScoresMarkedDirty {
+                algorithm_id,
+                account_id: subject_address().to_bytes(),
+                issuer_hashes: vec![issuer_hash],
+            }
+            .into(),
+        );
+        assert!(DirtyScores::<Test>::contains_key(&key));
+        assert!(!RecomputeQueue::<Test>::contains_key(&key));
+        assert_eq!(AlgorithmsModule::score_of(algorithm_id, issuer_hash, subject()), None);
+
+        run(&owner, issuer_hash, algorithm_id);
+        assert!(!DirtyScores::<Test>::contains_key(&key));
+        assert_eq!(AlgorithmsModule::score_of(algorithm_id, issuer_hash, subject()).unwrap().result, 7);
+
+        // Switching policies keeps the one deposit
+        assert_ok!(AlgorithmsModule::set_refresh_policy(RuntimeOrigin::signed(owner.clone()), algorithm_id, RefreshPolicy::Recompute));
+        assert_eq!(Balances::reserved_balance(&owner), reserved + 100);
+
+        attest(&controller, issuer_hash, schema_hash, 9);
+        assert!(RecomputeQueue::<Test>::contains_key(&key));
+        AlgorithmsModule::on_idle(1, frame_support::weights::Weight::MAX);
+        assert!(!RecomputeQueue::<Test>::contains_key(&key));
+        assert_eq!(AlgorithmsModule::score_of(algorithm_id, issuer_hash, subject()).unwrap().result, 9);
+
+        assert_ok!(AlgorithmsModule::set_refresh_policy(RuntimeOrigin::signed(owner.clone()), algorithm_id, RefreshPolicy::Keep));
+        assert_eq!(Balances::reserved_balance(&owner), reserved);
+
+        attest(&controller, issuer_hash, schema_hash, 11);
+        assert!(!DirtyScores::<Test>::contains_key(&key));
+        assert_eq!(AlgorithmsModule::score_of(algorithm_id, issuer_hash, subject()).unwrap().result, 9);
+    });
+}
+
+#[test]
+fn run_algo_for_pays_the_run_and_data_fees() {
+    new_test_ext().execute_with(|| {
+        let (owner, controller, payee, payer) = (account(1), account(2), account(3), account(4));
+        let (issuer_hash, schema_hash) = attested_issuer(&controller, 42);
+        let algorithm_id = approved_algorithm(&owner, schema_hash);
+
+        assert_ok!(AlgorithmsModule::set_run_fee(RuntimeOrigin::signed(owner.clone()), algorithm_id, Some(50)));
+        assert_ok!(AlgorithmsModule::set_data_fee(
+            RuntimeOrigin::signed(controller.clone()),
+            issuer_hash,
+            schema_hash,
+            Some(DataFee { payee: payee.clone(), amount: 30 }),
+        ));
+
+        let owner_balance = Balances::free_balance(&owner);
+        let payee_balance = Balances::free_balance(&payee);
+        let payer_balance = Balances::free_balance(&payer);
+
+        assert_ok!(AlgorithmsModule::run_algo_for(
+            RuntimeOrigin::signed(payer.clone()),
+            issuer_hash,
+            subject(),
+            algorithm_id,
+            BoundedVec::default(),
+        ));
+
+        assert_eq!(Balances::free_balance(&owner), owner_balance + 50);
+        assert_eq!(Balances::free_balance(&payee), payee_balance + 30);
+        assert_eq!(Balances::free_balance(&payer), payer_balance - 80);
+        System::assert_has_event(
+            Event::<Test>::RevenueSplit { algorithm_id, payer, total: 80, shares: vec![(owner, 50), (payee, 30)] }.into(),
+        );
+
+        let subject = AcquirerAddress::Ethereum(H160::repeat_byte(5));
+        assert_eq!(Scores::<Test>::get((algorithm_id, issuer_hash, subject)).unwrap().result, 42);
+    });
+}
+
+#[test]
+fn run_algo_for_skips_fees_paid_to_the_payer() {
+    new_test_ext().execute_with(|| {
+        let (owner, controller) = (account(1), account(2));
+        let (issuer_hash, schema_hash) = attested_issuer(&controller, 42);
+        let algorithm_id = approved_algorithm(&owner, schema_hash);
+
+        assert_ok!(AlgorithmsModule::set_run_fee(RuntimeOrigin::signed(owner.clone()), algorithm_id, Some(50)));
+        assert_ok!(AlgorithmsModule::set_data_fee(
+            RuntimeOrigin::signed(controller.clone()),
+            issuer_hash,
+            schema_hash,
+            Some(DataFee { payee: controller.clone(), amount: 30 }),
+        ));
+
+        let owner_balance = Balances::free_balance(&owner);
+        let controller_balance = Balances::free_balance(&controller);
+
+        assert_ok!(AlgorithmsModule::run_algo_for(
+            RuntimeOrigin::signed(owner.clone()),
+            issuer_hash,
+            subject(),
+            algorithm_id,
+            BoundedVec::default(),
+        ));
+
+        assert_eq!(Balances::free_balance(&owner), owner_balance - 30);
+        assert_eq!(Balances::free_balance(&controller), controller_balance + 30);
+        System::assert_has_event(
+            Event::<Test>::RevenueSplit { algorithm_id, payer: owner, total: 30, shares: vec![(controller, 30)] }.into(),
+        );
+    });
+}
+
+#[test]
+fn run_algo_for_pays_no_fees_unless_it_pays_them_all() {
+    new_test_ext().execute_with(|| {
+        let (owner, controller, payer) = (account(1), account(2), account(9));
+        let (issuer_hash, schema_hash) = attested_issuer(&controller, 42);
+        let algorithm_id = approved_algorithm(&owner, schema_hash);
+
+        assert_ok!(AlgorithmsModule::set_run_fee(RuntimeOrigin::signed(owner.clone()), algorithm_id, Some(50)));
+        assert_ok!(AlgorithmsModule::set_data_fee(
+            RuntimeOrigin::signed(controller.clone()),
+            issuer_hash,
+            schema_hash,
+            Some(DataFee { payee: controller.clone(), amount: 30 }),
+        ));
+
+        // Enough for the run fee, but not for both while staying alive
+        assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(account(4)), payer.clone(), 60));
+
+        assert_noop!(
+            AlgorithmsModule::run_algo_for(
+                RuntimeOrigin::signed(payer.clone()),
+                issuer_hash,
+                subject(),
+                algorithm_id,
+                BoundedVec::default(),
+            ),
+            Error::<Test>::InsufficientBalance
+        );
+        assert_eq!(Balances::free_balance(&payer), 60);
+    });
+}
+
+#[test]
+fn set_params_checks_and_applies_new_params() {
+    new_test_ext().execute_with(|| {
+        let owner = account(1);
+        let defaults = Parameters::<Test>::get();
+        assert_eq!(defaults.default_gas_limit, 100_000);
+        let params = PalletParams::<Test> { default_gas_limit: 50_000, algorithm_deposit: 2_000, ..defaults.clone() };
+
+        assert_noop!(AlgorithmsModule::set_params(RuntimeOrigin::signed(owner.clone()), params.clone()), DispatchError::BadOrigin);
+        for invalid in [
+            PalletParams::<Test> { gas_costs: GasCosts { basic_op: 0, ..defaults.gas_costs.clone() }, ..defaults.clone() },
+            PalletParams::<Test> { default_gas_limit: 1_000_001, ..defaults.clone() },
+            PalletParams::<Test> { schedule_deposit: 0, ..defaults.clone() },
+            // Above the total issuance of the four endowed accounts
+            PalletParams::<Test> { run_request_deposit: 4_000_001, ..defaults.clone() },
+        ] {
+            assert_noop!(AlgorithmsModule::set_params(RuntimeOrigin::root(), invalid), Error::<Test>::InvalidParams);
+        }
+
+        assert_ok!(AlgorithmsModule::set_params(RuntimeOrigin::root(), params.clone()));
+        System::assert_last_event(Event::<Test>::ParamsSet { params: params.clone() }.into());
+        assert_eq!(Parameters::<Test>::get(), params);
+
+        let code = calc_module();
+        let algorithm_id = approved_algorithm(&owner, H256::repeat_byte(1));
+        let algorithm = Algorithms::<Test>::get(algorithm_id).unwrap();
+        assert_eq!(algorithm.gas_limit, 50_000);
+        assert_eq!(algorithm.deposit, 2_000 + code.len() as u128);
+    });
+}
+
+#[test]
+fn algorithms_wait_for_approval_before_they_run() {
+    new_test_ext().execute_with(|| {
+        let (owner, controller) = (account(1), account(2));
+        let (issuer_hash, schema_hash) = attested_issuer(&controller, 42);
+        let run_for = |algorithm_id| {
+            AlgorithmsModule::run_algo_for(RuntimeOrigin::signed(owner.clone()), issuer_hash, subject(), algorithm_id, BoundedVec::default())
+        };
+
+        let algorithm_id = NextAlgoId::<Test>::get();
+        assert_ok!(AlgorithmsModule::save_algo(RuntimeOrigin::signed(owner.clone()), vec![schema_hash], calc_module(), None));
+        assert_eq!(Algorithms::<Test>::get(algorithm_id).unwrap().status, AlgoStatus::PendingApproval);
+        assert_noop!(run_for(algorithm_id), Error::<Test>::AlgoNotApproved);
+
+        assert_noop!(AlgorithmsModule::approve_algo(RuntimeOrigin::signed(owner.clone()), algorithm_id), DispatchError::BadOrigin);
+        assert_ok!(AlgorithmsModule::approve_algo(RuntimeOrigin::root(), algorithm_id));
+        System::assert_last_event(Event::<Test>::AlgorithmApproved { algorithm_id }.into());
+        assert_noop!(AlgorithmsModule::approve_algo(RuntimeOrigin::root(), algorithm_id), Error::<Test>::AlgoNotPendingApproval);
+        assert_ok!(run_for(algorithm_id));
+
+        // Metadata changes keep the approval
+        assert_ok!(AlgorithmsModule::update_algo(RuntimeOrigin::signed(owner.clone()), algorithm_id, Some(b"Renamed".to_vec()), None, None, None));
+        assert_eq!(Algorithms::<Test>::get(algorithm_id).unwrap().status, AlgoStatus::Active);
+
+        // A new module doesn't
+        let code = sum_module();
+        let code_hash = BlakeTwo256::hash(&code);
+        assert_ok!(AlgorithmsModule::upload_code(RuntimeOrigin::signed(owner.clone()), code));
+        assert_ok!(AlgorithmsModule::update_algo(RuntimeOrigin::signed(owner.clone()), algorithm_id, None, None, Some(code_hash), None));
+        assert_eq!(Algorithms::<Test>::get(algorithm_id).unwrap().status, AlgoStatus::PendingApproval);
+        assert_noop!(run_for(algorithm_id), Error::<Test>::AlgoNotApproved);
+
+        let reserved = Balances::reserved_balance(&owner);
+        let deposit = Algorithms::<Test>::get(algorithm_id).unwrap().deposit;
+        assert_noop!(AlgorithmsModule::reject_algo(RuntimeOrigin::signed(owner.clone()), algorithm_id), DispatchError::BadOrigin);
+        assert_ok!(AlgorithmsModule::reject_algo(RuntimeOrigin::root(), algorithm_id));
+        System::assert_last_event(Event::<Test>::AlgorithmRejected { algorithm_id }.into());
+        assert_eq!(Algorithms::<Test>::get(algorithm_id).unwrap().status, AlgoStatus::Disabled);
+        assert_eq!(Balances::reserved_balance(&owner), reserved - deposit);
+        assert_eq!(CodeInfoOf::<Test>::get(code_hash).unwrap().refcount, 0);
+        assert_noop!(run_for(algorithm_id), Error::<Test>::AlgoDisabled);
+        assert_noop!(AlgorithmsModule::reject_algo(RuntimeOrigin::root(), algorithm_id), Error::<Test>::AlgoNotPendingApproval);
+    });
+}
+
+#[test]
+fn algorithms_need_no_approval_unless_required() {
+    new_test_ext().execute_with(|| {
+        RequireApproval::set(false);
+        let (owner, controller) = (account(1), account(2));
+        let (issuer_hash, schema_hash) = attested_issuer(&controller, 42);
+
+        let algorithm_id = NextAlgoId::<Test>::get();
+        assert_ok!(AlgorithmsModule::save_algo(RuntimeOrigin::signed(owner.clone()), vec![schema_hash], calc_module(), None));
+        assert_eq!(Algorithms::<Test>::get(algorithm_id).unwrap().status, AlgoStatus::Active);
+        assert_ok!(AlgorithmsModule::run_algo_for(
+            RuntimeOrigin::signed(owner.clone()),
+            issuer_hash,
+            subject(),
+            algorithm_id,
+            BoundedVec::default(),
+        ));
+
+        // Nor does a new module
+        let code = wasm(
+            r#"(module
+                (import "env" "memory" (memory 1))
+                (func (export "calc") (result i64) (i64.const 7)))"#,
+        );
+        let code_hash = BlakeTwo256::hash(&code);
+        assert_ok!(AlgorithmsModule::upload_code(RuntimeOrigin::signed(owner.clone()), code));
+        assert_ok!(AlgorithmsModule::update_algo(RuntimeOrigin::signed(owner), algorithm_id, None, None, Some(code_hash), None));
+        assert_eq!(Algorithms::<Test>::get(algorithm_id).unwrap().status, AlgoStatus::Active);
+        assert_noop!(
+            AlgorithmsModule::approve_algo(RuntimeOrigin::root(), algorithm_id),
+            Error::<Test>::AlgoNotPendingApproval
+        );
+    });
+}
+
+#[test]
+fn inputs_with_a_tier_skip_issuers_verified_below_it() {
+    new_test_ext().execute_with(|| {
+        use pallet_issuers::VerificationTier;
+
+        let (owner, controller) = (account(1), account(2));
+        let acme = create_issuer(&controller, b"Acme");
+        let beta = create_issuer(&controller, b"Beta");
+        let schema_hash = u64_schema(&controller, acme, b"value");
+        attest(&controller, acme, schema_hash, 5);
+        attest(&controller, beta, schema_hash, 10);
+
+        let inputs = vec![AlgoInput { issuer: IssuerSelector::AnyOf(BoundedVec::truncate_from(vec![beta, acme])), schema_hash }];
+        let algorithm_id = algorithm_with_inputs(&owner, inputs, MissingInputPolicy::RequireAll, calc_module());
+        let set_input_tier = |who: &AccountId, input_index, tier| {
+            AlgorithmsModule::set_input_tier(RuntimeOrigin::signed(who.clone()), algorithm_id, input_index, tier)
+        };
+
+        assert_noop!(set_input_tier(&controller, 0, VerificationTier::Verified), Error::<Test>::NotAlgoOwner);
+        assert_noop!(set_input_tier(&owner, 1, VerificationTier::Verified), Error::<Test>::InvalidInputIndex);
+        assert_ok!(set_input_tier(&owner, 0, VerificationTier::Verified));
+        System::assert_last_event(
+            Event::<Test>::InputTierSet { algorithm_id, input_index: 0, tier: VerificationTier::Verified }.into(),
+        );
+
+        // Neither issuer is verified yet
+        assert_noop!(
+            AlgorithmsModule::run_algo_for(RuntimeOrigin::signed(owner.clone()), acme, subject(), algorithm_id, BoundedVec::default()),
+            Error::<Test>::IssuerTierTooLow
+        );
+
+        assert_ok!(Issuers::set_tier(RuntimeOrigin::root(), acme, VerificationTier::Official));
+        run(&owner, acme, algorithm_id);
+        let verified = score(algorithm_id, acme).unwrap();
+        assert_eq!(verified.result, 5);
+        assert_eq!(input_issuers(&verified), vec![Some(acme)]);
+
+        // `Unverified` lifts the requirement, so Beta is read first again
+        assert_ok!(set_input_tier(&owner, 0, VerificationTier::Unverified));
+        assert!(!InputTiers::<Test>::contains_key(algorithm_id, 0));
+        run(&owner, acme, algorithm_id);
+        assert_eq!(input_issuers(&score(algorithm_id, acme).unwrap()), vec![Some(beta)]);
+    });
+}
+
+#[test]
+fn outcomes_of_a_replaced_module_are_not_stored() {
+    new_test_ext().execute_with(|| {
+        let (owner, controller, requester) = (account(1), account(2), account(3));
+        let (issuer_hash, schema_hash) = attested_issuer(&controller, 42);
+        let algorithm_id = approved_algorithm(&owner, schema_hash);
+        let authorities = vec![account(1), account(4)];
+        assert_ok!(AlgorithmsModule::set_offchain_authorities(RuntimeOrigin::root(), BoundedVec::truncate_from(authorities.clone())));
+
+        let request_id = NextRunRequestId::<Test>::get();
+        assert_ok!(AlgorithmsModule::request_algo_run(
+            RuntimeOrigin::signed(requester),
+            issuer_hash,
+            subject(),
+            algorithm_id,
+            BoundedVec::default(),
+        ));
+
+        let code = wasm(
+            r#"(module
+                (import "env" "memory" (memory 1))
+                (func (export "calc") (result i64) (i64.const 7)))"#,
+        );
+        let code_hash = BlakeTwo256::hash(&code);
+        assert_ok!(AlgorithmsModule::upload_code(RuntimeOrigin::signed(owner.clone()), code));
+        assert_ok!(AlgorithmsModule::update_algo(RuntimeOrigin::signed(owner), algorithm_id, None, None, Some(code_hash), None));
+        assert_ok!(AlgorithmsModule::approve_algo(RuntimeOrigin::root(), algorithm_id));
+
+        for authority in authorities {
+            assert_ok!(AlgorithmsModule::submit_algo_run(
+                RuntimeOrigin::signed(authority),
+                request_id,
+                RunOutcome::Success { result: 42, data: None, inputs: vec![] },
+            ));
+        }
+
+        let subject = AcquirerAddress::Ethereum(H160::repeat_byte(5));
+        assert!(Scores::<Test>::get((algorithm_id, issuer_hash, subject)).is_none());
+        System::assert_has_event(
+            Event::<Test>::AlgoFailed {
+                algorithm_id,
+                issuer_hash,
+                account_id: vec![5; 20],
+                error: Error::<Test>::AlgoCodeChanged.into(),
+                gas_used: 0,
+                abort: None,
+            }
+            .into(),
+        );
+    });
+}
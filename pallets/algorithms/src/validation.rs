@@ -0,0 +1,158 @@
+//! Upload-time checks that a module only relies on what `run_code` provides.
+
+use super::*;
+use frame_support::{ensure, traits::Get};
+use sp_std::vec::Vec;
+use wasmparser::{ExternalKind, MemoryType, Parser, Payload, Type, TypeRef, ValType};
+
+/// Functions a module may import from `host`, with their parameter and result types.
+const HOST_FUNCTIONS: &[(&str, &[ValType], &[ValType])] = &[
+    ("print", &[ValType::I32], &[]),
+    // A function of its own, as modules already import `print` with this signature
+    ("print_str", &[ValType::I32, ValType::I32], &[]),
+    ("input_present", &[ValType::I32], &[ValType::I32]),
+    ("fixed_mul", &[ValType::I64, ValType::I64], &[ValType::I64]),
+    ("fixed_div", &[ValType::I64, ValType::I64], &[ValType::I64]),
+    ("sqrt", &[ValType::I64], &[ValType::I64]),
+    ("ln", &[ValType::I64], &[ValType::I64]),
+    ("params_len", &[], &[ValType::I32]),
+    ("read_params", &[ValType::I32], &[]),
+    ("return_data", &[ValType::I32, ValType::I32], &[]),
+];
+
+/// Parameter types of `env.abort(msg, file, line, col)`.
+const ABORT_PARAMS: &[ValType] = &[ValType::I32, ValType::I32, ValType::I32, ValType::I32];
+
+/// Exports `run_code` may call, all of type `() -> i64`.
+const ENTRY_POINTS: &[&str] = &["calc", "calc_output"];
+
+impl<T: Config> Pallet<T> {
+    /// Check `code` only imports host functions, `env.abort` and `env.memory` with the
+    /// signatures `run_code` defines them with, exports at least one entry point, has no
+    /// start function and stays within `MaxMemoryPages` and `ModuleLimits`.
+    pub(crate) fn check_module(code: &[u8]) -> Result<(), Error<T>> {
+        let limits = T::ModuleLimits::get();
+
+        let mut types = Vec::new();
+        // Type index of every function, imported ones first
+        let mut func_types = Vec::new();
+        let mut entry_points = 0u32;
+
+        for payload in Parser::new(0).parse_all(code) {
+            match payload.map_err(|_| Error::<T>::InvalidWasmProvided)? {
+                Payload::TypeSection(reader) => {
+                    for ty in reader {
+                        let Type::Func(func_type) = ty.map_err(|_| Error::<T>::InvalidWasmProvided)?;
+                        types.push(func_type);
+                    }
+                },
+                Payload::ImportSection(reader) => {
+                    for import in reader {
+                        let import = import.map_err(|_| Error::<T>::InvalidWasmProvided)?;
+
+                        let (type_index, params, results) = match (import.module, import.name, import.ty) {
+                            ("env", "memory", TypeRef::Memory(memory)) => {
+                                Self::check_memory(&memory, true)?;
+                                continue;
+                            },
+                            ("env", "abort", TypeRef::Func(type_index)) => (type_index, ABORT_PARAMS, &[][..]),
+                            ("host", name, TypeRef::Func(type_index)) => {
+                                let (_, params, results) = HOST_FUNCTIONS.iter()
+                                    .find(|(host_name, _, _)| *host_name == name)
+                                    .ok_or(Error::<T>::ForbiddenImport)?;
+                                (type_index, *params, *results)
+                            },
+                            _ => return Err(Error::<T>::ForbiddenImport),
+                        };
+
+                        let func_type = types.get(type_index as usize).ok_or(Error::<T>::InvalidWasmProvided)?;
+                        ensure!(
+                            func_type.params() == params && func_type.results() == results,
+                            Error::<T>::InvalidImportSignature
+                        );
+                        func_types.push(type_index);
+                    }
+                },
+                Payload::FunctionSection(reader) => {
+                    ensure!(reader.count() <= limits.functions, Error::<T>::TooManyFunctions);
+                    for type_index in reader {
+                        func_types.push(type_index.map_err(|_| Error::<T>::InvalidWasmProvided)?);
+                    }
+                },
+                Payload::TableSection(reader) => {
+                    ensure!(reader.count() <= limits.tables, Error::<T>::TooManyTables);
+                    for table in reader {
+                        let table = table.map_err(|_| Error::<T>::InvalidWasmProvided)?;
+                        ensure!(
+                            table.initial <= limits.table_elements
+                                && table.maximum.map_or(true, |maximum| maximum <= limits.table_elements),
+                            Error::<T>::TableTooLarge
+                        );
+                    }
+                },
+                Payload::MemorySection(reader) => {
+                    for memory in reader {
+                        Self::check_memory(&memory.map_err(|_| Error::<T>::InvalidWasmProvided)?, false)?;
+                    }
+                },
+                Payload::GlobalSection(reader) => {
+                    ensure!(reader.count() <= limits.globals, Error::<T>::TooManyGlobals);
+                },
+                Payload::ExportSection(reader) => {
+                    for export in reader {
+                        let export = export.map_err(|_| Error::<T>::InvalidWasmProvided)?;
+                        if !ENTRY_POINTS.contains(&export.name) {
+                            continue;
+                        }
+
+                        ensure!(export.kind == ExternalKind::Func, Error::<T>::InvalidEntryPoint);
+                        let func_type = func_types.get(export.index as usize)
+                            .and_then(|type_index| types.get(*type_index as usize))
+                            .ok_or(Error::<T>::InvalidWasmProvided)?;
+                        ensure!(
+                            func_type.params().is_empty() && func_type.results() == [ValType::I64],
+                            Error::<T>::InvalidEntryPoint
+                        );
+                        entry_points += 1;
+                    }
+                },
+                Payload::StartSection { .. } => return Err(Error::<T>::StartFunctionNotAllowed),
+                // Exception handling
+                Payload::TagSection(_) => return Err(Error::<T>::UnsupportedFeature),
+                _ => {},
+            }
+        }
+
+        ensure!(entry_points > 0, Error::<T>::MissingEntryPoint);
+
+        Ok(())
+    }
+
+    /// Whether `code`, which passed `check_module`, exports the entry point `name`.
+    pub(crate) fn exports_entry_point(code: &[u8], name: &str) -> bool {
+        Parser::new(0).parse_all(code).any(|payload| match payload {
+            Ok(Payload::ExportSection(reader)) => reader.into_iter()
+                .any(|export| export.map_or(false, |export| export.name == name && export.kind == ExternalKind::Func)),
+            _ => false,
+        })
+    }
+
+    /// The memory `run_code` provides has exactly `MaxMemoryPages`, so an imported memory
+    /// may not ask for more, nor cap its size below that, or it wouldn't link. A module's
+    /// own memory must also cap how far it can grow.
+    fn check_memory(memory: &MemoryType, imported: bool) -> Result<(), Error<T>> {
+        ensure!(!memory.shared && !memory.memory64, Error::<T>::UnsupportedFeature);
+
+        let max_pages = T::MaxMemoryPages::get() as u64;
+        if imported {
+            ensure!(memory.maximum.map_or(true, |maximum| maximum >= max_pages), Error::<T>::MemoryTooSmall);
+        }
+        let within_limit = match memory.maximum {
+            Some(maximum) => maximum <= max_pages,
+            None => imported,
+        };
+        ensure!(memory.initial <= max_pages && within_limit, Error::<T>::MemoryTooLarge);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,806 @@
+//! Weights for `pallet_algorithms`
+//!
+//! NOT YET BENCHMARKED: these are estimates, counting the storage each call accesses and
+//! scaling execution times from `pallet_issuers`, until the benchmarks in `benchmarking.rs`
+//! are run on reference hardware with the command below, which overwrites this file.
+//!
+//! `execute` weighs running a module, per thousand units of gas (`k`).
+
+// Command:
+// target/release/solochain-template-node
+// benchmark
+// pallet
+// --chain=dev
+// --steps=100
+// --repeat=50
+// --pallet=pallet-algorithms
+// --extrinsic=*
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --output=./pallets/algorithms/src/weights.rs
+// --template=frame-weight-template.hbs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for `pallet_algorithms`.
+pub trait WeightInfo {
+	fn save_algo(n: u32, c: u32, ) -> Weight;
+	fn run_algo_for(n: u32, ) -> Weight;
+	fn save_algo_with_inputs(n: u32, c: u32, ) -> Weight;
+	fn update_algo() -> Weight;
+	fn disable_algo() -> Weight;
+	fn transfer_algo_ownership() -> Weight;
+	fn upload_code(c: u32, ) -> Weight;
+	fn remove_code() -> Weight;
+	fn create_algo(n: u32, ) -> Weight;
+	fn request_algo_run() -> Weight;
+	fn set_offchain_authorities(a: u32, ) -> Weight;
+	fn create_schedule() -> Weight;
+	fn add_scheduled_subjects(s: u32, ) -> Weight;
+	fn remove_scheduled_subjects(s: u32, ) -> Weight;
+	fn cancel_schedule(s: u32, ) -> Weight;
+	fn set_refresh_policy(n: u32, ) -> Weight;
+	fn set_run_fee() -> Weight;
+	fn set_data_fee() -> Weight;
+	fn set_params() -> Weight;
+	fn accept_algo_ownership() -> Weight;
+	fn approve_algo() -> Weight;
+	fn reject_algo() -> Weight;
+	fn set_input_tier() -> Weight;
+	fn execute(k: u32, ) -> Weight;
+}
+
+/// Weights for `pallet_algorithms` using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `AlgorithmsModule::CodeByHash` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::CodeByHash` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::Parameters` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::Parameters` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::CodeInfoOf` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::CodeInfoOf` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::NextAlgoId` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::NextAlgoId` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::Algorithms` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::Algorithms` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `n` is `[0, 10]`.
+	/// The range of component `c` is `[64, 25000]`.
+	fn save_algo(n: u32, c: u32, ) -> Weight {
+		Weight::from_parts(96_300_000, 3985)
+			.saturating_add(Weight::from_parts(1_150_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(9_800, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(Weight::from_parts(0, 1).saturating_mul(c.into()))
+	}
+	/// Storage: `AlgorithmsModule::Algorithms` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::Algorithms` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::ScoreIssuers` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::ScoreIssuers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::InputTiers` (r:10 w:0)
+	/// Proof: `AlgorithmsModule::InputTiers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::IssuerTiers` (r:10 w:0)
+	/// Proof: `IssuersModule::IssuerTiers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `CredentialsModule::Schemas` (r:10 w:0)
+	/// Proof: `CredentialsModule::Schemas` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `CredentialsModule::Attestations` (r:10 w:0)
+	/// Proof: `CredentialsModule::Attestations` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::CodeByHash` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::CodeByHash` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::RunFees` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::RunFees` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::DataFees` (r:10 w:0)
+	/// Proof: `AlgorithmsModule::DataFees` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:12 w:12)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::DirtyScores` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::DirtyScores` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::RecomputeQueue` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::RecomputeQueue` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::Scores` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::Scores` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `n` is `[0, 10]`.
+	fn run_algo_for(n: u32, ) -> Weight {
+		Weight::from_parts(84_600_000, 29875)
+			.saturating_add(Weight::from_parts(31_200_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().reads((5_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 7485).saturating_mul(n.into()))
+	}
+	/// Storage: `AlgorithmsModule::CodeByHash` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::CodeByHash` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::Parameters` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::Parameters` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `CredentialsModule::Schemas` (r:11 w:0)
+	/// Proof: `CredentialsModule::Schemas` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::CodeInfoOf` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::CodeInfoOf` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::NextAlgoId` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::NextAlgoId` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::Algorithms` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::Algorithms` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `n` is `[0, 10]`.
+	/// The range of component `c` is `[64, 25000]`.
+	fn save_algo_with_inputs(n: u32, c: u32, ) -> Weight {
+		Weight::from_parts(112_500_000, 7185)
+			.saturating_add(Weight::from_parts(2_940_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(9_800, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(Weight::from_parts(0, 1).saturating_mul(c.into()))
+	}
+	/// Storage: `AlgorithmsModule::Algorithms` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::Algorithms` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::CodeByHash` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::CodeByHash` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::CodeInfoOf` (r:2 w:2)
+	/// Proof: `AlgorithmsModule::CodeInfoOf` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::Parameters` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::Parameters` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn update_algo() -> Weight {
+		Weight::from_parts(88_100_000, 31395)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `AlgorithmsModule::Algorithms` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::Algorithms` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::RefreshPolicies` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::RefreshPolicies` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::SchemaWatchers` (r:10 w:10)
+	/// Proof: `AlgorithmsModule::SchemaWatchers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::RefreshDeposits` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::RefreshDeposits` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::CodeInfoOf` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::CodeInfoOf` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::PendingOwners` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::PendingOwners` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn disable_algo() -> Weight {
+		Weight::from_parts(71_900_000, 16860)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(11_u64))
+	}
+	/// Storage: `AlgorithmsModule::Algorithms` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::Algorithms` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::PendingOwners` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::PendingOwners` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn transfer_algo_ownership() -> Weight {
+		Weight::from_parts(21_700_000, 4485)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `AlgorithmsModule::CodeByHash` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::CodeByHash` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::Parameters` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::Parameters` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::CodeInfoOf` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::CodeInfoOf` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `c` is `[64, 25000]`.
+	fn upload_code(c: u32, ) -> Weight {
+		Weight::from_parts(41_800_000, 3705)
+			.saturating_add(Weight::from_parts(9_750, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(Weight::from_parts(0, 1).saturating_mul(c.into()))
+	}
+	/// Storage: `AlgorithmsModule::CodeInfoOf` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::CodeInfoOf` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::CodeByHash` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::CodeByHash` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn remove_code() -> Weight {
+		Weight::from_parts(38_200_000, 3875)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `AlgorithmsModule::Parameters` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::Parameters` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `CredentialsModule::Schemas` (r:11 w:0)
+	/// Proof: `CredentialsModule::Schemas` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::CodeByHash` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::CodeByHash` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::CodeInfoOf` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::CodeInfoOf` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::NextAlgoId` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::NextAlgoId` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::Algorithms` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::Algorithms` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `n` is `[0, 10]`.
+	fn create_algo(n: u32, ) -> Weight {
+		Weight::from_parts(79_400_000, 30445)
+			.saturating_add(Weight::from_parts(2_910_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `AlgorithmsModule::Algorithms` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::Algorithms` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `CredentialsModule::Schemas` (r:1 w:0)
+	/// Proof: `CredentialsModule::Schemas` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::CounterForRunRequests` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::CounterForRunRequests` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::Parameters` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::Parameters` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::NextRunRequestId` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::NextRunRequestId` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::RunRequestExpiries` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::RunRequestExpiries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::RunRequests` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::RunRequests` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn request_algo_run() -> Weight {
+		Weight::from_parts(58_300_000, 5185)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `AlgorithmsModule::OffchainAuthorities` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::OffchainAuthorities` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// The range of component `a` is `[1, 10]`.
+	fn set_offchain_authorities(a: u32, ) -> Weight {
+		Weight::from_parts(9_300_000, 0)
+			.saturating_add(Weight::from_parts(1_050_000, 0).saturating_mul(a.into()))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `AlgorithmsModule::Algorithms` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::Algorithms` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `CredentialsModule::Schemas` (r:1 w:0)
+	/// Proof: `CredentialsModule::Schemas` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::CounterForSchedules` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::CounterForSchedules` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::Parameters` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::Parameters` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::NextScheduleId` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::NextScheduleId` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::ScheduleAgenda` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::ScheduleAgenda` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::Schedules` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::Schedules` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn create_schedule() -> Weight {
+		Weight::from_parts(59_700_000, 5225)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `AlgorithmsModule::Schedules` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::Schedules` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::ScheduledSubjects` (r:100 w:100)
+	/// Proof: `AlgorithmsModule::ScheduledSubjects` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `s` is `[1, 100]`.
+	fn add_scheduled_subjects(s: u32, ) -> Weight {
+		Weight::from_parts(24_100_000, 4445)
+			.saturating_add(Weight::from_parts(9_400_000, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(s.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(s.into())))
+			.saturating_add(Weight::from_parts(0, 2520).saturating_mul(s.into()))
+	}
+	/// Storage: `AlgorithmsModule::Schedules` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::Schedules` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::ScheduledSubjects` (r:100 w:100)
+	/// Proof: `AlgorithmsModule::ScheduledSubjects` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `s` is `[1, 100]`.
+	fn remove_scheduled_subjects(s: u32, ) -> Weight {
+		Weight::from_parts(23_600_000, 4695)
+			.saturating_add(Weight::from_parts(8_900_000, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(s.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(s.into())))
+			.saturating_add(Weight::from_parts(0, 2520).saturating_mul(s.into()))
+	}
+	/// Storage: `AlgorithmsModule::Schedules` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::Schedules` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::ScheduledSubjects` (r:0 w:1000)
+	/// Proof: `AlgorithmsModule::ScheduledSubjects` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::ScheduleAgenda` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::ScheduleAgenda` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::ScheduledRunQueue` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::ScheduledRunQueue` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::CounterForSchedules` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::CounterForSchedules` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `s` is `[0, 1000]`.
+	fn cancel_schedule(s: u32, ) -> Weight {
+		Weight::from_parts(45_200_000, 4875)
+			.saturating_add(Weight::from_parts(1_380_000, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(s.into())))
+	}
+	/// Storage: `AlgorithmsModule::Algorithms` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::Algorithms` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::RefreshPolicies` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::RefreshPolicies` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::RefreshDeposits` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::RefreshDeposits` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::SchemaWatchers` (r:10 w:10)
+	/// Proof: `AlgorithmsModule::SchemaWatchers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `n` is `[0, 10]`.
+	fn set_refresh_policy(n: u32, ) -> Weight {
+		Weight::from_parts(43_800_000, 4555)
+			.saturating_add(Weight::from_parts(6_300_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2675).saturating_mul(n.into()))
+	}
+	/// Storage: `AlgorithmsModule::Algorithms` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::Algorithms` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::RunFees` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::RunFees` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_run_fee() -> Weight {
+		Weight::from_parts(20_900_000, 4485)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `IssuersModule::Issuers` (r:1 w:0)
+	/// Proof: `IssuersModule::Issuers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `CredentialsModule::Schemas` (r:1 w:0)
+	/// Proof: `CredentialsModule::Schemas` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::DataFees` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::DataFees` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_data_fee() -> Weight {
+		Weight::from_parts(27_300_000, 4775)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `AlgorithmsModule::Parameters` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::Parameters` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn set_params() -> Weight {
+		Weight::from_parts(12_400_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `AlgorithmsModule::PendingOwners` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::PendingOwners` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::Algorithms` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::Algorithms` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::RefreshDeposits` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::RefreshDeposits` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn accept_algo_ownership() -> Weight {
+		Weight::from_parts(63_500_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `AlgorithmsModule::Algorithms` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::Algorithms` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn approve_algo() -> Weight {
+		Weight::from_parts(19_800_000, 4485)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `AlgorithmsModule::Algorithms` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::Algorithms` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::RefreshPolicies` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::RefreshPolicies` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::SchemaWatchers` (r:10 w:10)
+	/// Proof: `AlgorithmsModule::SchemaWatchers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::RefreshDeposits` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::RefreshDeposits` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::CodeInfoOf` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::CodeInfoOf` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::PendingOwners` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::PendingOwners` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn reject_algo() -> Weight {
+		Weight::from_parts(70_600_000, 16860)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(11_u64))
+	}
+	/// Storage: `AlgorithmsModule::Algorithms` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::Algorithms` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::InputTiers` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::InputTiers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_input_tier() -> Weight {
+		Weight::from_parts(20_400_000, 4485)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// The range of component `k` is `[0, 25000]`.
+	fn execute(k: u32, ) -> Weight {
+		Weight::from_parts(2_100_000, 0)
+			.saturating_add(Weight::from_parts(1_900_000, 0).saturating_mul(k.into()))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	/// Storage: `AlgorithmsModule::CodeByHash` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::CodeByHash` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::Parameters` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::Parameters` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::CodeInfoOf` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::CodeInfoOf` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::NextAlgoId` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::NextAlgoId` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::Algorithms` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::Algorithms` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `n` is `[0, 10]`.
+	/// The range of component `c` is `[64, 25000]`.
+	fn save_algo(n: u32, c: u32, ) -> Weight {
+		Weight::from_parts(96_300_000, 3985)
+			.saturating_add(Weight::from_parts(1_150_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(9_800, 0).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(Weight::from_parts(0, 1).saturating_mul(c.into()))
+	}
+	/// Storage: `AlgorithmsModule::Algorithms` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::Algorithms` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::ScoreIssuers` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::ScoreIssuers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::InputTiers` (r:10 w:0)
+	/// Proof: `AlgorithmsModule::InputTiers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::IssuerTiers` (r:10 w:0)
+	/// Proof: `IssuersModule::IssuerTiers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `CredentialsModule::Schemas` (r:10 w:0)
+	/// Proof: `CredentialsModule::Schemas` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `CredentialsModule::Attestations` (r:10 w:0)
+	/// Proof: `CredentialsModule::Attestations` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::CodeByHash` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::CodeByHash` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::RunFees` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::RunFees` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::DataFees` (r:10 w:0)
+	/// Proof: `AlgorithmsModule::DataFees` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:12 w:12)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::DirtyScores` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::DirtyScores` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::RecomputeQueue` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::RecomputeQueue` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::Scores` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::Scores` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `n` is `[0, 10]`.
+	fn run_algo_for(n: u32, ) -> Weight {
+		Weight::from_parts(84_600_000, 29875)
+			.saturating_add(Weight::from_parts(31_200_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().reads((5_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 7485).saturating_mul(n.into()))
+	}
+	/// Storage: `AlgorithmsModule::CodeByHash` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::CodeByHash` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::Parameters` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::Parameters` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `CredentialsModule::Schemas` (r:11 w:0)
+	/// Proof: `CredentialsModule::Schemas` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::CodeInfoOf` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::CodeInfoOf` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::NextAlgoId` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::NextAlgoId` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::Algorithms` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::Algorithms` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `n` is `[0, 10]`.
+	/// The range of component `c` is `[64, 25000]`.
+	fn save_algo_with_inputs(n: u32, c: u32, ) -> Weight {
+		Weight::from_parts(112_500_000, 7185)
+			.saturating_add(Weight::from_parts(2_940_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(9_800, 0).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(Weight::from_parts(0, 1).saturating_mul(c.into()))
+	}
+	/// Storage: `AlgorithmsModule::Algorithms` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::Algorithms` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::CodeByHash` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::CodeByHash` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::CodeInfoOf` (r:2 w:2)
+	/// Proof: `AlgorithmsModule::CodeInfoOf` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::Parameters` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::Parameters` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn update_algo() -> Weight {
+		Weight::from_parts(88_100_000, 31395)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `AlgorithmsModule::Algorithms` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::Algorithms` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::RefreshPolicies` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::RefreshPolicies` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::SchemaWatchers` (r:10 w:10)
+	/// Proof: `AlgorithmsModule::SchemaWatchers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::RefreshDeposits` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::RefreshDeposits` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::CodeInfoOf` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::CodeInfoOf` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::PendingOwners` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::PendingOwners` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn disable_algo() -> Weight {
+		Weight::from_parts(71_900_000, 16860)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(11_u64))
+	}
+	/// Storage: `AlgorithmsModule::Algorithms` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::Algorithms` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::PendingOwners` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::PendingOwners` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn transfer_algo_ownership() -> Weight {
+		Weight::from_parts(21_700_000, 4485)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `AlgorithmsModule::CodeByHash` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::CodeByHash` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::Parameters` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::Parameters` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::CodeInfoOf` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::CodeInfoOf` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `c` is `[64, 25000]`.
+	fn upload_code(c: u32, ) -> Weight {
+		Weight::from_parts(41_800_000, 3705)
+			.saturating_add(Weight::from_parts(9_750, 0).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(Weight::from_parts(0, 1).saturating_mul(c.into()))
+	}
+	/// Storage: `AlgorithmsModule::CodeInfoOf` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::CodeInfoOf` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::CodeByHash` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::CodeByHash` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn remove_code() -> Weight {
+		Weight::from_parts(38_200_000, 3875)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `AlgorithmsModule::Parameters` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::Parameters` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `CredentialsModule::Schemas` (r:11 w:0)
+	/// Proof: `CredentialsModule::Schemas` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::CodeByHash` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::CodeByHash` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::CodeInfoOf` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::CodeInfoOf` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::NextAlgoId` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::NextAlgoId` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::Algorithms` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::Algorithms` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `n` is `[0, 10]`.
+	fn create_algo(n: u32, ) -> Weight {
+		Weight::from_parts(79_400_000, 30445)
+			.saturating_add(Weight::from_parts(2_910_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `AlgorithmsModule::Algorithms` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::Algorithms` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `CredentialsModule::Schemas` (r:1 w:0)
+	/// Proof: `CredentialsModule::Schemas` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::CounterForRunRequests` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::CounterForRunRequests` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::Parameters` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::Parameters` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::NextRunRequestId` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::NextRunRequestId` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::RunRequestExpiries` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::RunRequestExpiries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::RunRequests` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::RunRequests` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn request_algo_run() -> Weight {
+		Weight::from_parts(58_300_000, 5185)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `AlgorithmsModule::OffchainAuthorities` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::OffchainAuthorities` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// The range of component `a` is `[1, 10]`.
+	fn set_offchain_authorities(a: u32, ) -> Weight {
+		Weight::from_parts(9_300_000, 0)
+			.saturating_add(Weight::from_parts(1_050_000, 0).saturating_mul(a.into()))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `AlgorithmsModule::Algorithms` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::Algorithms` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `CredentialsModule::Schemas` (r:1 w:0)
+	/// Proof: `CredentialsModule::Schemas` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::CounterForSchedules` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::CounterForSchedules` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::Parameters` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::Parameters` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::NextScheduleId` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::NextScheduleId` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::ScheduleAgenda` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::ScheduleAgenda` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::Schedules` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::Schedules` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn create_schedule() -> Weight {
+		Weight::from_parts(59_700_000, 5225)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `AlgorithmsModule::Schedules` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::Schedules` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::ScheduledSubjects` (r:100 w:100)
+	/// Proof: `AlgorithmsModule::ScheduledSubjects` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `s` is `[1, 100]`.
+	fn add_scheduled_subjects(s: u32, ) -> Weight {
+		Weight::from_parts(24_100_000, 4445)
+			.saturating_add(Weight::from_parts(9_400_000, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(s.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(s.into())))
+			.saturating_add(Weight::from_parts(0, 2520).saturating_mul(s.into()))
+	}
+	/// Storage: `AlgorithmsModule::Schedules` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::Schedules` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::ScheduledSubjects` (r:100 w:100)
+	/// Proof: `AlgorithmsModule::ScheduledSubjects` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `s` is `[1, 100]`.
+	fn remove_scheduled_subjects(s: u32, ) -> Weight {
+		Weight::from_parts(23_600_000, 4695)
+			.saturating_add(Weight::from_parts(8_900_000, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(s.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(s.into())))
+			.saturating_add(Weight::from_parts(0, 2520).saturating_mul(s.into()))
+	}
+	/// Storage: `AlgorithmsModule::Schedules` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::Schedules` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::ScheduledSubjects` (r:0 w:1000)
+	/// Proof: `AlgorithmsModule::ScheduledSubjects` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::ScheduleAgenda` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::ScheduleAgenda` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::ScheduledRunQueue` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::ScheduledRunQueue` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::CounterForSchedules` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::CounterForSchedules` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `s` is `[0, 1000]`.
+	fn cancel_schedule(s: u32, ) -> Weight {
+		Weight::from_parts(45_200_000, 4875)
+			.saturating_add(Weight::from_parts(1_380_000, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(s.into())))
+	}
+	/// Storage: `AlgorithmsModule::Algorithms` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::Algorithms` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::RefreshPolicies` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::RefreshPolicies` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::RefreshDeposits` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::RefreshDeposits` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::SchemaWatchers` (r:10 w:10)
+	/// Proof: `AlgorithmsModule::SchemaWatchers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `n` is `[0, 10]`.
+	fn set_refresh_policy(n: u32, ) -> Weight {
+		Weight::from_parts(43_800_000, 4555)
+			.saturating_add(Weight::from_parts(6_300_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2675).saturating_mul(n.into()))
+	}
+	/// Storage: `AlgorithmsModule::Algorithms` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::Algorithms` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::RunFees` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::RunFees` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_run_fee() -> Weight {
+		Weight::from_parts(20_900_000, 4485)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `IssuersModule::Issuers` (r:1 w:0)
+	/// Proof: `IssuersModule::Issuers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `CredentialsModule::Schemas` (r:1 w:0)
+	/// Proof: `CredentialsModule::Schemas` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::DataFees` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::DataFees` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_data_fee() -> Weight {
+		Weight::from_parts(27_300_000, 4775)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `AlgorithmsModule::Parameters` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::Parameters` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn set_params() -> Weight {
+		Weight::from_parts(12_400_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `AlgorithmsModule::PendingOwners` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::PendingOwners` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::Algorithms` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::Algorithms` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::RefreshDeposits` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::RefreshDeposits` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn accept_algo_ownership() -> Weight {
+		Weight::from_parts(63_500_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `AlgorithmsModule::Algorithms` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::Algorithms` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn approve_algo() -> Weight {
+		Weight::from_parts(19_800_000, 4485)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `AlgorithmsModule::Algorithms` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::Algorithms` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::RefreshPolicies` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::RefreshPolicies` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::SchemaWatchers` (r:10 w:10)
+	/// Proof: `AlgorithmsModule::SchemaWatchers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::RefreshDeposits` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::RefreshDeposits` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::CodeInfoOf` (r:1 w:1)
+	/// Proof: `AlgorithmsModule::CodeInfoOf` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::PendingOwners` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::PendingOwners` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn reject_algo() -> Weight {
+		Weight::from_parts(70_600_000, 16860)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(11_u64))
+	}
+	/// Storage: `AlgorithmsModule::Algorithms` (r:1 w:0)
+	/// Proof: `AlgorithmsModule::Algorithms` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AlgorithmsModule::InputTiers` (r:0 w:1)
+	/// Proof: `AlgorithmsModule::InputTiers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_input_tier() -> Weight {
+		Weight::from_parts(20_400_000, 4485)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// The range of component `k` is `[0, 25000]`.
+	fn execute(k: u32, ) -> Weight {
+		Weight::from_parts(2_100_000, 0)
+			.saturating_add(Weight::from_parts(1_900_000, 0).saturating_mul(k.into()))
+	}
+}
@@ -185,6 +185,33 @@ mod benchmarks {
         Ok(())
     }
 
+    #[benchmark]
+    fn set_schema_min_tier() -> Result<(), BenchmarkError> {
+        let origin = <T as pallet_issuers::Config>::VerifierOrigin::try_successful_origin()
+            .map_err(|_| BenchmarkError::Weightless)?;
+        let caller: T::AccountId = whitelisted_caller();
+        let issuer_hash = create_test_issuer::<T>(caller.clone());
+
+        let schema = generate_schema_fields::<T>(1, 1);
+        let schema_hash = calculate_schema_hash::<T>(&schema);
+
+        Pallet::<T>::create_schema(
+            RawOrigin::Signed(caller).into(),
+            issuer_hash,
+            schema
+        )?;
+
+        #[extrinsic_call]
+        set_schema_min_tier(origin as T::RuntimeOrigin, schema_hash, pallet_issuers::VerificationTier::Verified);
+
+        ensure!(
+            SchemaMinTiers::<T>::get(schema_hash) == pallet_issuers::VerificationTier::Verified,
+            "Tier was not set"
+        );
+
+        Ok(())
+    }
+
     impl_benchmark_test_suite!(
         Pallet,
         crate::tests::new_test_ext(),
@@ -9,6 +9,7 @@ mod benchmarking;
 pub use benchmarking::*;
 pub mod weights;
 pub use weights::WeightInfo as CredentialsWeightInfo;
+pub mod migrations;
 
 pub mod tests;
 
@@ -24,13 +25,14 @@ pub mod pallet {
 	use sp_core::{ H160 };
 	use sp_runtime::AccountId32;
 	use sp_runtime::traits::{ Hash };
+	use serde::{ Deserialize, Serialize };
 
 	use ed25519_dalek::VerifyingKey;
 
 	use super::CredentialsWeightInfo;
-	use pallet_issuers::Issuers;
+	use pallet_issuers::{ Issuers, VerificationTier };
 
-	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, Serialize, Deserialize)]
 	#[scale_info(skip_type_params(T))]
 	pub enum CredType {
 		Char,
@@ -57,6 +59,43 @@ pub mod pallet {
 		Solana(String),
 	}
 
+	impl AcquirerAddress {
+		/// Bytes `parse_acquirer_address` parses back into this address.
+		pub fn to_bytes(&self) -> Vec<u8> {
+			match self {
+				AcquirerAddress::Substrate(account_id) => account_id.to_ss58check().into_bytes(),
+				AcquirerAddress::Ethereum(address) => address.as_bytes().to_vec(),
+				AcquirerAddress::Solana(address) => address.clone().into_bytes(),
+			}
+		}
+	}
+
+	/// Handler for an attestation being created or updated, e.g. to refresh values derived
+	/// from it.
+	pub trait OnAttestationChanged<Hash> {
+		/// Called once attestation `attestation_index` of `acquirer_address` for `issuer_hash`
+		/// and `schema_hash` is stored. Returns the weight used, at most `max_weight()`.
+		fn on_attestation_changed(
+			acquirer_address: &AcquirerAddress,
+			issuer_hash: Hash,
+			schema_hash: Hash,
+			attestation_index: u32
+		) -> Weight;
+
+		/// Most weight `on_attestation_changed` may use.
+		fn max_weight() -> Weight;
+	}
+
+	impl<Hash> OnAttestationChanged<Hash> for () {
+		fn on_attestation_changed(_: &AcquirerAddress, _: Hash, _: Hash, _: u32) -> Weight {
+			Weight::zero()
+		}
+
+		fn max_weight() -> Weight {
+			Weight::zero()
+		}
+	}
+
 	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
 	pub enum SizeInBytes {
 		Limited(u8),
@@ -90,7 +129,11 @@ pub mod pallet {
 		T::MaxSchemaFields
 	>;
 
+	/// The in-code storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
@@ -106,6 +149,9 @@ pub mod pallet {
 		type MaxSchemaFieldSize: Get<u32>;
 
 		type CredentialsWeightInfo: CredentialsWeightInfo;
+
+		/// Told about every attestation created or updated.
+		type OnAttestationChanged: OnAttestationChanged<Self::Hash>;
 	}
 
 	#[pallet::storage]
@@ -129,6 +175,54 @@ pub mod pallet {
 		OptionQuery
 	>;
 
+	/// Tier an issuer needs to attest schemas that require more than `Unverified`.
+	#[pallet::storage]
+	pub type SchemaMinTiers<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::Hash,
+		VerificationTier,
+		ValueQuery
+	>;
+
+	#[pallet::genesis_config]
+	#[derive(frame_support::DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		/// Schemas to create, each with the issuer creating it. They get the hash
+		/// `create_schema` would give them.
+		pub schemas: Vec<(T::Hash, Vec<(Vec<u8>, CredType)>)>,
+		/// Attestations to add, as `(issuer_hash, schema_hash, for_account, attestation)`
+		/// like the arguments of `attest`.
+		pub attestations: Vec<(T::Hash, T::Hash, Vec<u8>, Vec<Vec<u8>>)>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			for (issuer_hash, schema) in &self.schemas {
+				assert!(Issuers::<T>::contains_key(issuer_hash), "Genesis schema issuer not found");
+
+				let schema = Pallet::<T>::bound_schema(schema.clone()).expect("Genesis schema too large");
+				let schema_hash = Pallet::<T>::schema_hash(&schema);
+				assert!(!Schemas::<T>::contains_key(schema_hash), "Duplicate genesis schema");
+
+				Schemas::<T>::insert(schema_hash, schema);
+			}
+
+			for (issuer_hash, schema_hash, for_account, attestation) in &self.attestations {
+				assert!(Issuers::<T>::contains_key(issuer_hash), "Genesis attestation issuer not found");
+
+				let acquirer_address = Pallet::<T>::parse_acquirer_address(for_account.clone())
+					.expect("Invalid genesis attestation account");
+				let schema = Schemas::<T>::get(schema_hash).expect("Genesis attestation schema not found");
+				let attestation = Pallet::<T>::validate_attestation(&schema, attestation)
+					.expect("Genesis attestation doesn't match its schema");
+
+				Attestations::<T>::append((acquirer_address, issuer_hash, schema_hash), attestation);
+			}
+		}
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -151,6 +245,10 @@ pub mod pallet {
 			attestation_index: u32,
 			attestation: CredAttestation<T>,
 		},
+		SchemaMinTierSet {
+			schema_hash: T::Hash,
+			tier: VerificationTier,
+		},
 	}
 
 	#[pallet::error]
@@ -164,6 +262,7 @@ pub mod pallet {
 		AttestationNotFound,
 		InvalidAttestationIndex,
     InvalidHashFormat,
+		IssuerTierTooLow,
 	}
 
 	#[pallet::call]
@@ -185,36 +284,9 @@ pub mod pallet {
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			ensure!(
-				schema.len() <= (T::MaxSchemaFields::get() as usize),
-				Error::<T>::TooManySchemaFields
-			);
+			let bounded_schema = Self::bound_schema(schema)?;
 
-			let mut bounded_schema = CredSchema::<T>::default();
-
-			for (vec, cred_type) in schema.clone() {
-				ensure!(
-					vec.len() <= (T::MaxSchemaFieldSize::get() as usize),
-					Error::<T>::SchemaFieldTooLarge
-				);
-				let bounded_vec = BoundedVec::<u8, T::MaxSchemaFieldSize>
-					::try_from(vec)
-					.map_err(|_| Error::<T>::SchemaFieldTooLarge)?;
-				bounded_schema
-					.try_push((bounded_vec, cred_type))
-					.map_err(|_| Error::<T>::TooManySchemaFields)?;
-			}
-
-			let bytes: Vec<u8> = bounded_schema
-				.iter()
-				.flat_map(|(vec, cred_type)| {
-					let mut bytes = vec.to_vec();
-					bytes.extend_from_slice(&cred_type.encode());
-					bytes
-				})
-				.collect();
-
-			let schema_hash = <T as Config>::Hashing::hash(&bytes);
+			let schema_hash = Self::schema_hash(&bounded_schema);
 
 			ensure!(!Schemas::<T>::contains_key(schema_hash), Error::<T>::SchemaAlreadyExists);
 
@@ -249,6 +321,7 @@ pub mod pallet {
 				.unwrap_or(0) as u32;
 			let address_type = 1u32; // Default to most expensive case
 			T::CredentialsWeightInfo::attest(field_count, max_value_size, address_type)
+				.saturating_add(T::OnAttestationChanged::max_weight())
 		})]
 		pub fn attest(
 			origin: OriginFor<T>,
@@ -256,7 +329,7 @@ pub mod pallet {
 			schema_hash: T::Hash,
 			for_account: Vec<u8>,
 			attestation: Vec<Vec<u8>>
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 
 			let acquirer_address = Self::parse_acquirer_address(for_account)?;
@@ -267,6 +340,7 @@ pub mod pallet {
 			ensure!(issuer.controllers.contains(&who), pallet_issuers::Error::<T>::NotAuthorized);
 
 			let schema = Schemas::<T>::get(schema_hash).ok_or(Error::<T>::SchemaNotFound)?;
+			Self::ensure_tier(issuer_hash, schema_hash)?;
 
 			let attestation = Self::validate_attestation(&schema, &attestation).ok_or(
 				Error::<T>::InvalidFormat
@@ -287,6 +361,19 @@ pub mod pallet {
 				existing_attestations
 			);
 
+			let hook_weight = T::OnAttestationChanged::on_attestation_changed(
+				&acquirer_address,
+				issuer_hash,
+				schema_hash,
+				attestation_index
+			);
+
+			let max_value_size = attestation
+				.iter()
+				.map(|v| v.len())
+				.max()
+				.unwrap_or(0) as u32;
+
 			Self::deposit_event(Event::AttestationCreated {
 				issuer_hash,
 				account_id: acquirer_address,
@@ -295,7 +382,13 @@ pub mod pallet {
 				attestation_index,
 			});
 
-			Ok(())
+			Ok(
+				Some(
+					T::CredentialsWeightInfo::attest(schema.len() as u32, max_value_size, 1).saturating_add(
+						hook_weight
+					)
+				).into()
+			)
 		}
 
 		#[pallet::call_index(3)]
@@ -309,6 +402,7 @@ pub mod pallet {
 				.unwrap_or(0) as u32;
 			// Assume worst case - max attestations
 			T::CredentialsWeightInfo::update_attestation(field_count, max_value_size, 100)
+				.saturating_add(T::OnAttestationChanged::max_weight())
 		})]
 		pub fn update_attestation(
 			origin: OriginFor<T>,
@@ -328,6 +422,7 @@ pub mod pallet {
 			ensure!(issuer.controllers.contains(&who), pallet_issuers::Error::<T>::NotAuthorized);
 
 			let schema = Schemas::<T>::get(schema_hash).ok_or(Error::<T>::SchemaNotFound)?;
+			Self::ensure_tier(issuer_hash, schema_hash)?;
 
 			let validated_attestation = Self::validate_attestation(&schema, &new_attestation).ok_or(
 				Error::<T>::InvalidFormat
@@ -349,6 +444,13 @@ pub mod pallet {
 				attestations.clone()
 			);
 
+			let hook_weight = T::OnAttestationChanged::on_attestation_changed(
+				&acquirer_address,
+				issuer_hash,
+				schema_hash,
+				attestation_index
+			);
+
 			Self::deposit_event(Event::AttestationUpdated {
 				issuer_hash,
 				account_id: acquirer_address,
@@ -367,13 +469,85 @@ pub mod pallet {
 							.max()
 							.unwrap_or(0) as u32,
 						attestations.len() as u32
-					)
+					).saturating_add(hook_weight)
 				).into()
 			)
 		}
+
+		/// Require issuers to be verified at `tier` or above to attest `schema_hash`.
+		/// `Unverified` lifts the requirement.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::CredentialsWeightInfo::set_schema_min_tier())]
+		pub fn set_schema_min_tier(
+			origin: OriginFor<T>,
+			schema_hash: T::Hash,
+			tier: VerificationTier
+		) -> DispatchResult {
+			<T as pallet_issuers::Config>::VerifierOrigin::ensure_origin(origin)?;
+
+			ensure!(Schemas::<T>::contains_key(schema_hash), Error::<T>::SchemaNotFound);
+
+			if tier == VerificationTier::Unverified {
+				SchemaMinTiers::<T>::remove(schema_hash);
+			} else {
+				SchemaMinTiers::<T>::insert(schema_hash, tier);
+			}
+
+			Self::deposit_event(Event::SchemaMinTierSet { schema_hash, tier });
+
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// Check `issuer_hash` is verified at the tier `schema_hash` requires of attesters.
+		fn ensure_tier(issuer_hash: T::Hash, schema_hash: T::Hash) -> Result<(), Error<T>> {
+			ensure!(
+				pallet_issuers::Pallet::<T>::tier_of(issuer_hash) >= SchemaMinTiers::<T>::get(schema_hash),
+				Error::<T>::IssuerTierTooLow
+			);
+			Ok(())
+		}
+
+		/// `schema` with its field count and field names checked against the bounds.
+		pub fn bound_schema(schema: Vec<(Vec<u8>, CredType)>) -> Result<CredSchema<T>, Error<T>> {
+			ensure!(
+				schema.len() <= (T::MaxSchemaFields::get() as usize),
+				Error::<T>::TooManySchemaFields
+			);
+
+			let mut bounded_schema = CredSchema::<T>::default();
+
+			for (vec, cred_type) in schema {
+				ensure!(
+					vec.len() <= (T::MaxSchemaFieldSize::get() as usize),
+					Error::<T>::SchemaFieldTooLarge
+				);
+				let bounded_vec = BoundedVec::<u8, T::MaxSchemaFieldSize>
+					::try_from(vec)
+					.map_err(|_| Error::<T>::SchemaFieldTooLarge)?;
+				bounded_schema
+					.try_push((bounded_vec, cred_type))
+					.map_err(|_| Error::<T>::TooManySchemaFields)?;
+			}
+
+			Ok(bounded_schema)
+		}
+
+		/// Key of `schema` in `Schemas`.
+		pub fn schema_hash(schema: &CredSchema<T>) -> T::Hash {
+			let bytes: Vec<u8> = schema
+				.iter()
+				.flat_map(|(vec, cred_type)| {
+					let mut bytes = vec.to_vec();
+					bytes.extend_from_slice(&cred_type.encode());
+					bytes
+				})
+				.collect();
+
+			<T as Config>::Hashing::hash(&bytes)
+		}
+
 		pub fn validate_attestation(
 			schema: &CredSchema<T>,
 			attestation: &Vec<Vec<u8>>
@@ -0,0 +1,79 @@
+//! Storage migrations for `pallet_credentials`.
+
+use super::*;
+use frame_support::{
+	migrations::VersionedMigration,
+	pallet_prelude::*,
+	traits::UncheckedOnRuntimeUpgrade,
+};
+use sp_std::{ marker::PhantomData, vec::Vec };
+#[cfg(feature = "try-runtime")]
+use sp_runtime::TryRuntimeError;
+
+/// Issuers moved from name hashes to ids. Attestations keyed by an issuer's old hash move to
+/// its id, as recorded by `pallet_issuers::migrations::v2`, which must run first.
+pub mod v1 {
+	use super::*;
+
+	pub struct InnerMigrateV0ToV1<T>(PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut reads = 0u64;
+			let mut moved = 0u64;
+
+			let keys = Attestations::<T>
+				::iter_keys()
+				.filter(|(_, issuer_hash, _)| {
+					reads += 2;
+					pallet_issuers::migrations::v2::LegacyIssuerIds::<T>::contains_key(issuer_hash)
+				})
+				.collect::<Vec<_>>();
+
+			for (acquirer_address, issuer_hash, schema_hash) in keys {
+				let Some(attestations) = Attestations::<T>::take((
+					acquirer_address.clone(),
+					issuer_hash,
+					schema_hash,
+				)) else {
+					continue;
+				};
+
+				let issuer_id = pallet_issuers::migrations::v2::resolve_legacy_id::<T>(issuer_hash);
+				Attestations::<T>::insert((acquirer_address, issuer_id, schema_hash), attestations);
+				reads += 2;
+				moved += 1;
+			}
+
+			log::info!(target: "credentials", "Moved {} attestation lists to issuer ids", moved);
+
+			T::DbWeight::get().reads_writes(reads, moved * 2)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			Ok((Attestations::<T>::iter_keys().count() as u64).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let count = u64::decode(&mut &state[..]).map_err(|_| "Invalid pre-upgrade state")?;
+			ensure!(Attestations::<T>::iter_values().count() as u64 == count, "Attestations lost migrating to v1");
+			ensure!(
+				!Attestations::<T>::iter_keys().any(|(_, issuer_hash, _)| {
+					pallet_issuers::migrations::v2::LegacyIssuerIds::<T>::contains_key(issuer_hash)
+				}),
+				"Attestations left at a legacy issuer hash"
+			);
+			Ok(())
+		}
+	}
+
+	pub type MigrateV0ToV1<T> = VersionedMigration<
+		0,
+		1,
+		InnerMigrateV0ToV1<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
@@ -3,28 +3,21 @@
 
 use super::*;
 use crate::{
-	self as pallet_credentials
+	self as pallet_credentials,
+	migrations::*,
 };
-use pallet_issuers;
-use pallet_balances;
 
-use codec::{Decode, Encode};
 use frame_support::{
-	assert_err, assert_noop, assert_ok, derive_impl, parameter_types,
-	traits::{ConstU32, ConstU64},
+	assert_ok, derive_impl, parameter_types,
+	traits::{ConstU128, ConstU32, ConstU64, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
 	BoundedVec,
 };
-use frame_system::EnsureRoot;
 use sp_core::H256;
-use sp_io::crypto::{sr25519_generate, sr25519_sign};
 use sp_runtime::{
-	traits::{BadOrigin, BlakeTwo256, IdentifyAccount, IdentityLookup, Verify},
-	BuildStorage, MultiSignature, MultiSigner,
+	traits::{BlakeTwo256, IdentifyAccount, IdentityLookup, Verify},
+	AccountId32, BuildStorage, MultiSignature,
 };
 
-use frame_support::traits::{tokens::ExistenceRequirement, Currency};
-
-type AccountIdOf<Test> = <Test as frame_system::Config>::AccountId;
 pub type AccountPublic = <MultiSignature as Verify>::Signer;
 pub type AccountId = <AccountPublic as IdentifyAccount>::AccountId;
 
@@ -106,6 +99,13 @@ impl pallet_issuers::Config for Test {
     type Currency = Balances;
 
     type IssuerRegistryDeposit = IssuerRegistryDeposit;
+    type ParamsOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type VerifierOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type MaxProfileFieldLength = ConstU32<256>;
+    type ProfileDepositPerByte = ConstU128<10_000_000>;
+    type MaxRecoveryAccounts = ConstU32<10>;
+    type RecoveryChallengePeriod = ConstU64<100>;
+    type RecoveryDeposit = ConstU128<1_000>;
 }
 
 impl pallet_credentials::Config  for Test {
@@ -114,8 +114,46 @@ impl pallet_credentials::Config  for Test {
 
 	type MaxSchemaFields = MaxSchemaFields;
 	type MaxSchemaFieldSize = MaxSchemaFieldSize;
+
+	type CredentialsWeightInfo = ();
+	type OnAttestationChanged = ();
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	frame_system::GenesisConfig::<Test>::default().build_storage().unwrap().into()
-}
\ No newline at end of file
+}
+
+/// Run migration `M` as a runtime upgrade would, with its try-runtime checks when the
+/// feature is on.
+fn run_migration<M: OnRuntimeUpgrade>() {
+	#[cfg(feature = "try-runtime")]
+	assert_ok!(M::try_on_runtime_upgrade(true));
+	#[cfg(not(feature = "try-runtime"))]
+	M::on_runtime_upgrade();
+}
+
+fn attestation(value: u8) -> Vec<CredAttestation<Test>> {
+	vec![BoundedVec::truncate_from(vec![BoundedVec::truncate_from(vec![value])])]
+}
+
+#[test]
+fn migration_to_v1_moves_attestations_to_issuer_ids() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<CredentialsModule>();
+
+		let acquirer = AcquirerAddress::Substrate(AccountId32::new([5; 32]));
+		let schema_hash = H256::repeat_byte(9);
+		let [legacy_hash, issuer_id, other_issuer] = [1u8, 2, 3].map(H256::repeat_byte);
+		pallet_issuers::migrations::v2::LegacyIssuerIds::<Test>::insert(legacy_hash, issuer_id);
+		Attestations::<Test>::insert((acquirer.clone(), legacy_hash, schema_hash), attestation(1));
+		Attestations::<Test>::insert((acquirer.clone(), other_issuer, schema_hash), attestation(2));
+
+		run_migration::<v1::MigrateV0ToV1<Test>>();
+
+		assert_eq!(Attestations::<Test>::get((acquirer.clone(), legacy_hash, schema_hash)), None);
+		assert_eq!(Attestations::<Test>::get((acquirer.clone(), issuer_id, schema_hash)), Some(attestation(1)));
+		// Issuers created after the move to ids have nothing to resolve
+		assert_eq!(Attestations::<Test>::get((acquirer, other_issuer, schema_hash)), Some(attestation(2)));
+		assert_eq!(CredentialsModule::on_chain_storage_version(), 1);
+	});
+}
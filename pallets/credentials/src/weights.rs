@@ -32,6 +32,7 @@ pub trait WeightInfo {
 	fn create_schema(f: u32, s: u32, ) -> Weight;
 	fn attest(f: u32, s: u32, a: u32, ) -> Weight;
 	fn update_attestation(f: u32, s: u32, n: u32, ) -> Weight;
+	fn set_schema_min_tier() -> Weight;
 }
 
 /// Weights for `pallet_credentials` using the Substrate node and recommended hardware.
@@ -104,6 +105,19 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(Weight::from_parts(0, 297).saturating_mul(n.into()))
 			.saturating_add(Weight::from_parts(0, 63).saturating_mul(s.into()))
 	}
+	/// Storage: `CredentialsModule::Schemas` (r:1 w:0)
+	/// Proof: `CredentialsModule::Schemas` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `CredentialsModule::SchemaMinTiers` (r:0 w:1)
+	/// Proof: `CredentialsModule::SchemaMinTiers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_schema_min_tier() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `313`
+		//  Estimated: `3778`
+		// Minimum execution time: 12_000_000 picoseconds.
+		Weight::from_parts(12_000_000, 3778)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -175,4 +189,17 @@ impl WeightInfo for () {
 			.saturating_add(Weight::from_parts(0, 297).saturating_mul(n.into()))
 			.saturating_add(Weight::from_parts(0, 63).saturating_mul(s.into()))
 	}
+	/// Storage: `CredentialsModule::Schemas` (r:1 w:0)
+	/// Proof: `CredentialsModule::Schemas` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `CredentialsModule::SchemaMinTiers` (r:0 w:1)
+	/// Proof: `CredentialsModule::SchemaMinTiers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_schema_min_tier() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `313`
+		//  Estimated: `3778`
+		// Minimum execution time: 12_000_000 picoseconds.
+		Weight::from_parts(12_000_000, 3778)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
\ No newline at end of file
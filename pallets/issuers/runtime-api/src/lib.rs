@@ -0,0 +1,21 @@
+//! Runtime API definition for `pallet_issuers`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+pub use pallet_issuers::{IssuerInfo, ProfileInfo, VerificationTier};
+
+sp_api::decl_runtime_apis! {
+    pub trait IssuersApi<AccountId, Hash> where
+        AccountId: Codec,
+        Hash: Codec,
+    {
+        /// Issuer `issuer_hash` with its controllers, verification tier and profile.
+        fn issuer(issuer_hash: Hash) -> Option<IssuerInfo<AccountId, Hash>>;
+
+        /// Id of the issuer currently named `name`, compared in canonical form.
+        fn issuer_by_name(name: Vec<u8>) -> Option<Hash>;
+    }
+}
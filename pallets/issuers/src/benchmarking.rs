@@ -2,21 +2,24 @@
 
 use super::*;
 
-use frame_benchmarking::{v2::*, whitelisted_caller, BenchmarkError};
-use frame_support::{BoundedVec, ensure, traits::Get};
+use frame_benchmarking::{v2::*, account, whitelisted_caller, BenchmarkError};
+use frame_support::{BoundedVec, ensure, traits::{Currency, Get}};
 use frame_system::RawOrigin;
 use sp_std::vec;
 use sp_std::vec::Vec;
-use sp_runtime::traits::Hash;
+use sp_runtime::traits::{Hash, Saturating};
 
 
 #[benchmarks]
 mod benchmarks {
     use super::*;
 
+    /// The whitelisted caller and `n - 1` other controllers, as duplicates are dropped.
     fn generate_controllers<T: Config>(n: u32) -> Vec<T::AccountId> {
         let caller: T::AccountId = whitelisted_caller();
-        vec![caller.clone(); n as usize]
+        let mut controllers = vec![caller];
+        controllers.extend((1..n).map(|i| account("controller", i, 0)));
+        controllers
     }
 
     // Benchmark `create_issuer` extrinsic
@@ -24,14 +27,14 @@ mod benchmarks {
     fn create_issuer(n: Linear<1, { T::MaxNameLength::get() - 1 }>, c: Linear<1, { T::MaxControllers::get() - 1}>) -> Result<(), BenchmarkError> {
         // Setup: Create test data
         let caller: T::AccountId = whitelisted_caller();
-        let name: Vec<u8> = vec![1; n as usize];
+        let name: Vec<u8> = vec![b'a'; n as usize];
         let controllers = generate_controllers::<T>(c);
 
         #[extrinsic_call]
         create_issuer(RawOrigin::Signed(caller), name.clone(), controllers);
 
         // Verify the issuer was created
-        let hash = <T as Config>::Hashing::hash(&name);
+        let hash = Pallet::<T>::issuer_by_name(&name).ok_or("Issuer name was not indexed.")?;
         ensure!(Issuers::<T>::contains_key(hash), "Issuer did not get created.");
 
         Ok(())
@@ -73,9 +76,10 @@ mod benchmarks {
 
         let stored_issuer = Issuers::<T>::get(hash);
 
+        let stored_controllers = stored_issuer.unwrap().controllers;
         ensure!(
-            stored_issuer.unwrap().controllers ==
-            BoundedVec::<T::AccountId, T::MaxControllers>::try_from(new_controllers_clone).unwrap(),
+            stored_controllers.len() == new_controllers_clone.len() &&
+            new_controllers_clone.iter().all(|controller| stored_controllers.contains(controller)),
             "Controllers were not updated correctly"
         );
 
@@ -83,6 +87,297 @@ mod benchmarks {
 
     }
 
+    // Benchmark `set_registry_deposit` extrinsic
+    #[benchmark]
+    fn set_registry_deposit() -> Result<(), BenchmarkError> {
+        let origin = T::ParamsOrigin::try_successful_origin()
+            .map_err(|_| BenchmarkError::Weightless)?;
+        let deposit = T::IssuerRegistryDeposit::get();
+        // The deposit can't be above the total issuance
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, deposit.saturating_add(deposit));
+
+        #[extrinsic_call]
+        set_registry_deposit(origin as T::RuntimeOrigin, deposit);
+
+        ensure!(RegistryDeposit::<T>::get() == deposit, "Deposit was not set");
+
+        Ok(())
+    }
+
+    // Benchmark `set_tier` extrinsic
+    #[benchmark]
+    fn set_tier() -> Result<(), BenchmarkError> {
+        let origin = T::VerifierOrigin::try_successful_origin()
+            .map_err(|_| BenchmarkError::Weightless)?;
+
+        let caller: T::AccountId = whitelisted_caller();
+        let name: Vec<u8> = vec![1; T::MaxNameLength::get() as usize];
+        let hash = <T as Config>::Hashing::hash(&name);
+        Issuers::<T>::insert(hash, Issuer::<T> {
+            name: BoundedVec::try_from(name).unwrap(),
+            controllers: BoundedVec::try_from(vec![caller]).unwrap(),
+        });
+
+        #[extrinsic_call]
+        set_tier(origin as T::RuntimeOrigin, hash, VerificationTier::Verified);
+
+        ensure!(IssuerTiers::<T>::get(hash) == VerificationTier::Verified, "Tier was not set");
+
+        Ok(())
+    }
+
+    fn create_controlled_issuer<T: Config>(controller: &T::AccountId) -> T::Hash {
+        let name: Vec<u8> = vec![1; T::MaxNameLength::get() as usize];
+        let hash = <T as Config>::Hashing::hash(&name);
+        Issuers::<T>::insert(hash, Issuer::<T> {
+            name: BoundedVec::try_from(name).unwrap(),
+            controllers: BoundedVec::try_from(vec![controller.clone()]).unwrap(),
+        });
+        hash
+    }
+
+    // Benchmark `set_profile` extrinsic, replacing an existing profile
+    #[benchmark]
+    fn set_profile(b: Linear<0, { T::MaxProfileFieldLength::get() * 4 }>) -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        let hash = create_controlled_issuer::<T>(&caller);
+        T::Currency::make_free_balance_be(
+            &caller,
+            T::Currency::minimum_balance()
+                .saturating_add(T::ProfileDepositPerByte::get().saturating_mul((T::MaxProfileFieldLength::get() * 8 + 64).into())),
+        );
+
+        let field = |len: u32| vec![b'a'; len as usize];
+        let max = T::MaxProfileFieldLength::get();
+        Pallet::<T>::set_profile(
+            RawOrigin::Signed(caller.clone()).into(),
+            hash,
+            field(max),
+            Some(hash),
+            field(max),
+            field(max),
+            field(max),
+        )?;
+
+        let quarter = b / 4;
+        #[extrinsic_call]
+        set_profile(
+            RawOrigin::Signed(caller),
+            hash,
+            field(b - quarter * 3),
+            Some(hash),
+            field(quarter),
+            field(quarter),
+            field(quarter),
+        );
+
+        ensure!(
+            IssuerProfiles::<T>::get(hash).map(|profile| profile.website.len()) == Some((b - quarter * 3) as usize),
+            "Profile was not set"
+        );
+
+        Ok(())
+    }
+
+    // Benchmark `clear_profile` extrinsic
+    #[benchmark]
+    fn clear_profile() -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        let hash = create_controlled_issuer::<T>(&caller);
+        T::Currency::make_free_balance_be(
+            &caller,
+            T::Currency::minimum_balance()
+                .saturating_add(T::ProfileDepositPerByte::get().saturating_mul((T::MaxProfileFieldLength::get() * 4 + 64).into())),
+        );
+
+        let field = vec![b'a'; T::MaxProfileFieldLength::get() as usize];
+        Pallet::<T>::set_profile(
+            RawOrigin::Signed(caller.clone()).into(),
+            hash,
+            field.clone(),
+            Some(hash),
+            field.clone(),
+            field.clone(),
+            field,
+        )?;
+
+        #[extrinsic_call]
+        clear_profile(RawOrigin::Signed(caller), hash);
+
+        ensure!(!IssuerProfiles::<T>::contains_key(hash), "Profile was not cleared");
+
+        Ok(())
+    }
+
+    // Benchmark `rename_issuer` extrinsic
+    #[benchmark]
+    fn rename_issuer(n: Linear<1, { T::MaxNameLength::get() }>) -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        let old_name: Vec<u8> = vec![b'a'; T::MaxNameLength::get() as usize];
+        let hash = Pallet::<T>::name_hash(&old_name).unwrap();
+        Issuers::<T>::insert(hash, Issuer::<T> {
+            name: BoundedVec::try_from(old_name).unwrap(),
+            controllers: BoundedVec::try_from(vec![caller.clone()]).unwrap(),
+        });
+        IssuerNameIndex::<T>::insert(hash, hash);
+        // Worst case: the tier is reset
+        IssuerTiers::<T>::insert(hash, VerificationTier::Verified);
+
+        let name: Vec<u8> = vec![b'b'; n as usize];
+
+        #[extrinsic_call]
+        rename_issuer(RawOrigin::Signed(caller), hash, name.clone());
+
+        ensure!(Pallet::<T>::issuer_by_name(&name) == Some(hash), "Issuer was not renamed");
+        ensure!(Pallet::<T>::tier_of(hash) == VerificationTier::Unverified, "Tier was not reset");
+
+        Ok(())
+    }
+
+    fn recovery_accounts<T: Config>(n: u32) -> Vec<T::AccountId> {
+        (0..n).map(|i| account("recovery", i, 0)).collect()
+    }
+
+    /// Recovery accounts for `hash`, their deposit reserved by the whitelisted caller, who
+    /// can afford one more.
+    fn setup_recovery<T: Config>(hash: T::Hash) -> Vec<T::AccountId> {
+        let depositor: T::AccountId = whitelisted_caller();
+        let deposit = T::RecoveryDeposit::get();
+        T::Currency::make_free_balance_be(
+            &depositor,
+            T::Currency::minimum_balance().saturating_add(deposit.saturating_mul(2u32.into())),
+        );
+        T::Currency::reserve(&depositor, deposit).unwrap();
+
+        let accounts = recovery_accounts::<T>(T::MaxRecoveryAccounts::get());
+        RecoveryConfigs::<T>::insert(hash, RecoveryConfig::<T> {
+            accounts: BoundedVec::try_from(accounts.clone()).unwrap(),
+            threshold: T::MaxRecoveryAccounts::get(),
+            depositor,
+            deposit,
+        });
+        accounts
+    }
+
+    fn setup_active_recovery<T: Config>(hash: T::Hash, accounts: &[T::AccountId], approvals: usize) {
+        ActiveRecoveries::<T>::insert(hash, ActiveRecovery::<T> {
+            new_controllers: BoundedVec::try_from(recovery_accounts::<T>(T::MaxControllers::get())).unwrap(),
+            initiated_at: frame_system::Pallet::<T>::block_number(),
+            approvals: BoundedVec::try_from(accounts[..approvals].to_vec()).unwrap(),
+        });
+    }
+
+    // Benchmark `set_recovery` extrinsic, cancelling a reset in progress
+    #[benchmark]
+    fn set_recovery(r: Linear<0, { T::MaxRecoveryAccounts::get() }>) -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        let hash = create_controlled_issuer::<T>(&caller);
+        let accounts = setup_recovery::<T>(hash);
+        setup_active_recovery::<T>(hash, &accounts, 1);
+
+        let new_accounts = recovery_accounts::<T>(r);
+
+        #[extrinsic_call]
+        set_recovery(RawOrigin::Signed(caller), hash, new_accounts, r.min(1));
+
+        ensure!(!ActiveRecoveries::<T>::contains_key(hash), "Reset was not cancelled");
+        ensure!(RecoveryConfigs::<T>::contains_key(hash) == (r > 0), "Recovery was not set");
+
+        Ok(())
+    }
+
+    // Benchmark `initiate_recovery` extrinsic
+    #[benchmark]
+    fn initiate_recovery(c: Linear<0, { T::MaxControllers::get() }>) -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        let hash = create_controlled_issuer::<T>(&caller);
+        let accounts = setup_recovery::<T>(hash);
+
+        let new_controllers: Vec<T::AccountId> = (0..c).map(|i| account("controller", i, 0)).collect();
+
+        #[extrinsic_call]
+        initiate_recovery(RawOrigin::Signed(accounts[0].clone()), hash, new_controllers);
+
+        ensure!(ActiveRecoveries::<T>::contains_key(hash), "Reset was not started");
+
+        Ok(())
+    }
+
+    // Benchmark `approve_recovery` extrinsic
+    #[benchmark]
+    fn approve_recovery() -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        let hash = create_controlled_issuer::<T>(&caller);
+        let accounts = setup_recovery::<T>(hash);
+        let last = accounts.len() - 1;
+        setup_active_recovery::<T>(hash, &accounts, last);
+
+        #[extrinsic_call]
+        approve_recovery(RawOrigin::Signed(accounts[last].clone()), hash);
+
+        ensure!(
+            ActiveRecoveries::<T>::get(hash).map(|recovery| recovery.approvals.len()) == Some(accounts.len()),
+            "Reset was not approved"
+        );
+
+        Ok(())
+    }
+
+    // Benchmark `cancel_recovery` extrinsic
+    #[benchmark]
+    fn cancel_recovery() -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        let hash = create_controlled_issuer::<T>(&caller);
+        let accounts = setup_recovery::<T>(hash);
+        setup_active_recovery::<T>(hash, &accounts, accounts.len());
+
+        #[extrinsic_call]
+        cancel_recovery(RawOrigin::Signed(caller), hash);
+
+        ensure!(!ActiveRecoveries::<T>::contains_key(hash), "Reset was not cancelled");
+
+        Ok(())
+    }
+
+    // Benchmark `finish_recovery` extrinsic, moving the registry deposit
+    #[benchmark]
+    fn finish_recovery(c: Linear<0, { T::MaxControllers::get() }>) -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        let hash = create_controlled_issuer::<T>(&caller);
+        let accounts = setup_recovery::<T>(hash);
+        let deposit = T::RecoveryDeposit::get();
+        IssuerDeposits::<T>::insert(hash, (caller.clone(), deposit));
+
+        let mut new_controllers: Vec<T::AccountId> = (0..c).map(|i| account("controller", i, 0)).collect();
+        new_controllers.sort();
+        if let Some(controller) = new_controllers.first() {
+            T::Currency::make_free_balance_be(controller, T::Currency::minimum_balance());
+        }
+        ActiveRecoveries::<T>::insert(hash, ActiveRecovery::<T> {
+            new_controllers: BoundedVec::try_from(new_controllers.clone()).unwrap(),
+            initiated_at: frame_system::Pallet::<T>::block_number(),
+            approvals: BoundedVec::try_from(accounts).unwrap(),
+        });
+        frame_system::Pallet::<T>::set_block_number(
+            frame_system::Pallet::<T>::block_number() + T::RecoveryChallengePeriod::get(),
+        );
+
+        #[extrinsic_call]
+        finish_recovery(RawOrigin::Signed(caller.clone()), hash);
+
+        ensure!(
+            Issuers::<T>::get(hash).map(|issuer| issuer.controllers.into_inner()) == Some(new_controllers.clone()),
+            "Controllers were not reset"
+        );
+        ensure!(
+            IssuerDeposits::<T>::get(hash).map(|(depositor, _)| depositor) == new_controllers.first().cloned().or(Some(caller)),
+            "Registry deposit was not moved"
+        );
+
+        Ok(())
+    }
+
     impl_benchmark_test_suite!(
         IssuersModule,
         crate::tests::new_test_ext(),
@@ -10,17 +10,22 @@ mod benchmarking;
 pub use benchmarking::*;
 
 pub mod weights;
+pub mod migrations;
+
+#[cfg(test)]
+mod tests;
 
 #[frame_support::pallet]
 pub mod pallet {
     use frame_support::pallet_prelude::{*, OptionQuery};
     use frame_system::pallet_prelude::*;
     use frame_support::{
-      traits::{Currency, ReservableCurrency, Get},
+      traits::{BalanceStatus, Currency, ReservableCurrency, Get},
     };
-    use sp_runtime::traits::Hash;
+    use sp_runtime::traits::{Hash, Saturating, TrailingZeroInput, Zero};
     use sp_std::collections::btree_set::BTreeSet;
     use sp_std::prelude::*;
+    use serde::{Deserialize, Serialize};
 
     use super::*;
 
@@ -33,7 +38,87 @@ pub mod pallet {
         pub controllers: BoundedVec<T::AccountId, T::MaxControllers>,
     }
 
+    /// Public profile of an issuer. Off-chain tools check `domain_proof` against the DNS TXT
+    /// record or `/.well-known` file of `website` to confirm the issuer controls it.
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct IssuerProfile<T: Config> {
+        pub website: BoundedVec<u8, T::MaxProfileFieldLength>,
+        /// Hash of the logo image, served from `website` or any content-addressed store.
+        pub logo_hash: Option<T::Hash>,
+        pub description_uri: BoundedVec<u8, T::MaxProfileFieldLength>,
+        pub contact: BoundedVec<u8, T::MaxProfileFieldLength>,
+        /// DNS TXT value or `/.well-known` token published by the issuer.
+        pub domain_proof: BoundedVec<u8, T::MaxProfileFieldLength>,
+        /// Controller who reserved `deposit` for this profile.
+        pub depositor: T::AccountId,
+        pub deposit: BalanceOf<T>,
+    }
+
+    /// Accounts able to reset the controllers of an issuer whose controller keys are lost.
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct RecoveryConfig<T: Config> {
+        pub accounts: BoundedVec<T::AccountId, T::MaxRecoveryAccounts>,
+        /// Approvals from `accounts` a reset needs, including the one starting it.
+        pub threshold: u32,
+        /// Controller who reserved `deposit` for these accounts.
+        pub depositor: T::AccountId,
+        pub deposit: BalanceOf<T>,
+    }
+
+    /// A controller reset in progress. Controllers can cancel it until `challenge_period`
+    /// blocks after `initiated_at`.
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct ActiveRecovery<T: Config> {
+        pub new_controllers: BoundedVec<T::AccountId, T::MaxControllers>,
+        pub initiated_at: BlockNumberFor<T>,
+        pub approvals: BoundedVec<T::AccountId, T::MaxRecoveryAccounts>,
+    }
+
+    /// Profile fields of an issuer, as returned by the runtime API.
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub struct ProfileInfo<Hash> {
+        pub website: Vec<u8>,
+        pub logo_hash: Option<Hash>,
+        pub description_uri: Vec<u8>,
+        pub contact: Vec<u8>,
+        pub domain_proof: Vec<u8>,
+    }
+
+    /// An issuer with its tier and profile, as returned by the runtime API.
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub struct IssuerInfo<AccountId, Hash> {
+        pub name: Vec<u8>,
+        pub controllers: Vec<AccountId>,
+        pub tier: VerificationTier,
+        pub profile: Option<ProfileInfo<Hash>>,
+    }
+
+    /// How far an issuer's identity has been checked. Tiers are ordered, so a requirement
+    /// of `Verified` is met by `Verified` and `Official` issuers.
+    #[derive(
+        Clone, Copy, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, Default, RuntimeDebug, TypeInfo,
+        MaxEncodedLen, Serialize, Deserialize,
+    )]
+    pub enum VerificationTier {
+        /// Registered by anyone, under any free name.
+        #[default]
+        Unverified,
+        /// Shown to control the domain or account the name refers to.
+        DomainVerified,
+        /// Identity of the organisation behind the issuer checked.
+        Verified,
+        /// The body the name refers to, vouched for by governance.
+        Official,
+    }
+
+    /// The in-code storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
@@ -54,22 +139,145 @@ pub mod pallet {
 
         type WeightInfo: WeightInfo;
 
-        /// The amount that needs to be deposited to create an issuer
+        /// The amount that needs to be deposited to create an issuer, until
+        /// `set_registry_deposit` changes it
         #[pallet::constant] 
         type IssuerRegistryDeposit: Get<BalanceOf<Self>>;
+
+        /// Origin allowed to change the registry deposit
+        type ParamsOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Origin allowed to set verification tiers, e.g. governance or a set of registrars
+        type VerifierOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Maximum length of each text field of an issuer profile
+        #[pallet::constant]
+        type MaxProfileFieldLength: Get<u32>;
+
+        /// Deposit reserved per byte of profile data
+        #[pallet::constant]
+        type ProfileDepositPerByte: Get<BalanceOf<Self>>;
+
+        /// Maximum number of recovery accounts of an issuer
+        #[pallet::constant]
+        type MaxRecoveryAccounts: Get<u32>;
+
+        /// Blocks controllers have to cancel a controller reset before it can be finished
+        #[pallet::constant]
+        type RecoveryChallengePeriod: Get<BlockNumberFor<Self>>;
+
+        /// Deposit reserved to set the recovery accounts of an issuer
+        #[pallet::constant]
+        type RecoveryDeposit: Get<BalanceOf<Self>>;
     }
 
     type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+    /// Issuers by id. Ids are derived from the creating account and `NextIssuerNonce` (see
+    /// [`Pallet::issuer_id`]), so they stay the same whatever the issuer is named.
     #[pallet::storage]
     pub type Issuers<T: Config> =
     StorageMap<_, Blake2_128Concat, T::Hash, Issuer<T>, OptionQuery>;
 
+    /// Issuer id by hash of its canonical name (see [`Pallet::canonical_name`]).
+    #[pallet::storage]
+    pub type IssuerNameIndex<T: Config> =
+    StorageMap<_, Blake2_128Concat, T::Hash, T::Hash, OptionQuery>;
+
+    /// Number of issuers created so far, used to derive the id of the next one.
+    #[pallet::storage]
+    pub type NextIssuerNonce<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// Recovery accounts of issuers that set some.
+    #[pallet::storage]
+    pub type RecoveryConfigs<T: Config> =
+    StorageMap<_, Blake2_128Concat, T::Hash, RecoveryConfig<T>, OptionQuery>;
+
+    /// Controller resets in progress.
+    #[pallet::storage]
+    pub type ActiveRecoveries<T: Config> =
+    StorageMap<_, Blake2_128Concat, T::Hash, ActiveRecovery<T>, OptionQuery>;
+
+    #[pallet::type_value]
+    pub fn DefaultRegistryDeposit<T: Config>() -> BalanceOf<T> { T::IssuerRegistryDeposit::get() }
+
+    /// Deposit reserved to create an issuer.
+    #[pallet::storage]
+    pub type RegistryDeposit<T: Config> =
+    StorageValue<_, BalanceOf<T>, ValueQuery, DefaultRegistryDeposit<T>>;
+
+    /// Account that reserved the registry deposit of an issuer, and the amount. Issuers
+    /// created at genesis or before deposits were recorded have none.
+    #[pallet::storage]
+    pub type IssuerDeposits<T: Config> =
+    StorageMap<_, Blake2_128Concat, T::Hash, (T::AccountId, BalanceOf<T>), OptionQuery>;
+
+    /// Verification tier of issuers above `Unverified`.
+    #[pallet::storage]
+    pub type IssuerTiers<T: Config> =
+    StorageMap<_, Blake2_128Concat, T::Hash, VerificationTier, ValueQuery>;
+
+    /// Profiles of issuers that set one.
+    #[pallet::storage]
+    pub type IssuerProfiles<T: Config> =
+    StorageMap<_, Blake2_128Concat, T::Hash, IssuerProfile<T>, OptionQuery>;
+
+    #[pallet::genesis_config]
+    #[derive(frame_support::DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// Issuers to create, by name, with their controllers. No deposit is reserved for them.
+        /// They get ids as if created by the zero account, see [`Pallet::genesis_issuer_id`].
+        pub issuers: Vec<(Vec<u8>, Vec<T::AccountId>)>,
+        /// Tiers of genesis issuers, by name.
+        pub tiers: Vec<(Vec<u8>, VerificationTier)>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        fn build(&self) {
+            for (name, controllers) in &self.issuers {
+                let (name, canonical) = Pallet::<T>::canonical_name(name)
+                    .expect("Invalid genesis issuer name");
+                let name_hash = <T as Config>::Hashing::hash(&canonical);
+                assert!(!IssuerNameIndex::<T>::contains_key(name_hash), "Duplicate genesis issuer");
+
+                let name = BoundedVec::<u8, T::MaxNameLength>::try_from(name)
+                    .expect("Genesis issuer name too long");
+                let controllers = BoundedVec::<T::AccountId, T::MaxControllers>::try_from(
+                    controllers.iter().cloned().collect::<BTreeSet<_>>().into_iter().collect::<Vec<_>>(),
+                )
+                    .expect("Too many genesis issuer controllers");
+
+                let hash = Pallet::<T>::next_issuer_id(&Pallet::<T>::zero_account());
+                Issuers::<T>::insert(hash, Issuer::<T> { name, controllers });
+                IssuerNameIndex::<T>::insert(name_hash, hash);
+            }
+
+            for (name, tier) in &self.tiers {
+                let hash = Pallet::<T>::issuer_by_name(name)
+                    .expect("Genesis tier issuer not found");
+                IssuerTiers::<T>::insert(hash, tier);
+            }
+        }
+    }
+
     #[pallet::event]
     #[pallet::generate_deposit(pub (super) fn deposit_event)]
     pub enum Event<T: Config> {
         IssuerCreated { hash: T::Hash, issuer_name: BoundedVec<u8, T::MaxNameLength> , controllers_identified: BoundedVec<T::AccountId, T::MaxControllers>},
         IssuerUpdated { hash: T::Hash, issuer_name: BoundedVec<u8, T::MaxNameLength> , controllers_identified: BoundedVec<T::AccountId, T::MaxControllers>},
+        IssuerRenamed { hash: T::Hash, old_name: BoundedVec<u8, T::MaxNameLength>, issuer_name: BoundedVec<u8, T::MaxNameLength> },
+        RegistryDepositSet { deposit: BalanceOf<T> },
+        IssuerTierSet { hash: T::Hash, tier: VerificationTier },
+        IssuerProfileSet { hash: T::Hash, profile: IssuerProfile<T> },
+        IssuerProfileCleared { hash: T::Hash },
+        RecoverySet { hash: T::Hash, accounts: BoundedVec<T::AccountId, T::MaxRecoveryAccounts>, threshold: u32 },
+        RecoveryRemoved { hash: T::Hash },
+        RecoveryInitiated { hash: T::Hash, initiator: T::AccountId, new_controllers: BoundedVec<T::AccountId, T::MaxControllers>, challenge_ends: BlockNumberFor<T> },
+        RecoveryApproved { hash: T::Hash, account: T::AccountId, approvals: u32 },
+        RecoveryCancelled { hash: T::Hash, by: T::AccountId },
+        /// Controllers replaced by those of the finished reset.
+        RecoveryFinished { hash: T::Hash, controllers_identified: BoundedVec<T::AccountId, T::MaxControllers> },
     }
 
     // Errors inform users that something went wrong.
@@ -79,9 +287,25 @@ pub mod pallet {
         IssuerNotFound,
         NotAuthorized,
         IssuerNameTooLong,
+        /// Issuer names must be non-empty ASCII without control characters.
+        InvalidIssuerName,
         TooManyControllers,
 
-        InsufficientBalance
+        InsufficientBalance,
+        ProfileFieldTooLong,
+        ProfileNotFound,
+        TooManyRecoveryAccounts,
+        /// Threshold must be between 1 and the number of recovery accounts.
+        InvalidRecoveryThreshold,
+        RecoveryNotConfigured,
+        NotRecoveryAccount,
+        RecoveryAlreadyActive,
+        NoActiveRecovery,
+        AlreadyApproved,
+        ChallengePeriodNotOver,
+        NotEnoughApprovals,
+        /// Registry deposits must be non-zero and at most the total issuance.
+        InvalidRegistryDeposit,
     }
 
 
@@ -96,13 +320,12 @@ pub mod pallet {
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
 
-            //TODO: trim trailing spaces from spaces from issuer name
-
             ensure!(name.len() <= T::MaxNameLength::get() as usize, Error::<T>::IssuerNameTooLong);
 
-            let hash = <T as Config>::Hashing::hash(&name);
+            let (name, canonical) = Self::canonical_name(&name)?;
+            let name_hash = <T as Config>::Hashing::hash(&canonical);
 
-            ensure!(!Issuers::<T>::contains_key(hash), Error::<T>::IssuerAlreadyExists);
+            ensure!(!IssuerNameIndex::<T>::contains_key(name_hash), Error::<T>::IssuerAlreadyExists);
 
             ensure!(controllers.len() <= T::MaxControllers::get() as usize, Error::<T>::TooManyControllers);
 
@@ -118,11 +341,14 @@ pub mod pallet {
             let controllers_identified =  BoundedVec::<T::AccountId, T::MaxControllers>::try_from(unique_controllers)
             .map_err(|_| Error::<T>::TooManyControllers)?;
 
-            T::Currency::reserve(&who, T::IssuerRegistryDeposit::get().into())
+            let deposit = RegistryDeposit::<T>::get();
+            T::Currency::reserve(&who, deposit)
             .map_err(|_| Error::<T>::InsufficientBalance)?;
 
-            // let issuer = Issuer::<T> { name: issuer_name.clone(), controllers: controllers_identified.clone() };
+            let hash = Self::next_issuer_id(&who);
             Issuers::<T>::insert(hash, Issuer::<T> { name: issuer_name.clone(), controllers: controllers_identified.clone() });
+            IssuerNameIndex::<T>::insert(name_hash, hash);
+            IssuerDeposits::<T>::insert(hash, (who, deposit));
             Self::deposit_event(Event::IssuerCreated { hash, issuer_name: issuer_name.clone(), controllers_identified: controllers_identified.clone() });
 
             Ok(Some(T::WeightInfo::create_issuer(issuer_name.len() as u32, controllers_identified.len() as u32)).into())
@@ -139,24 +365,17 @@ pub mod pallet {
 
             let mut issuer = Issuers::<T>::get(hash)
                 .ok_or(Error::<T>::IssuerNotFound)?;
-            let hash = hash;
-
 
             ensure!(issuer.controllers.contains(&who), Error::<T>::NotAuthorized);
 
-            // if let Some(name) = name {
-            //     ensure!(name.len() <= T::MaxNameLength::get() as usize, Error::<T>::IssuerNameTooLong);
-            //     hash = <T as Config>::Hashing::hash(&name);
-            //     ensure!(!Issuers::<T>::contains_key(hash), Error::<T>::IssuerAlreadyExists);
-            //     issuer.name = BoundedVec::<u8, T::MaxNameLength>::try_from(name)
-            //     .map_err(|_| Error::<T>::IssuerNameTooLong)?;
-            // }
-
-            // TODO: Duplicates in controllers
-
             if let Some(controllers) = controllers {
                 ensure!(controllers.len() <= T::MaxControllers::get() as usize, Error::<T>::TooManyControllers);
-                issuer.controllers = BoundedVec::<T::AccountId, T::MaxControllers>::try_from(controllers)
+                let unique_controllers: Vec<T::AccountId> = controllers
+                    .into_iter()
+                    .collect::<BTreeSet<_>>()
+                    .into_iter()
+                    .collect();
+                issuer.controllers = BoundedVec::<T::AccountId, T::MaxControllers>::try_from(unique_controllers)
                 .map_err(|_| Error::<T>::TooManyControllers)?;
             }
 
@@ -166,5 +385,430 @@ pub mod pallet {
 
             Ok(Some(T::WeightInfo::edit_controllers(issuer.controllers.len() as u32)).into())
         }
+
+        /// Change the deposit reserved to create an issuer. Issuers already created keep the
+        /// deposit they reserved. The deposit must be non-zero and at most the total issuance.
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::set_registry_deposit())]
+        pub fn set_registry_deposit(origin: OriginFor<T>, deposit: BalanceOf<T>) -> DispatchResult {
+            T::ParamsOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                !deposit.is_zero() && deposit <= T::Currency::total_issuance(),
+                Error::<T>::InvalidRegistryDeposit
+            );
+            RegistryDeposit::<T>::put(deposit);
+
+            Self::deposit_event(Event::RegistryDepositSet { deposit });
+
+            Ok(())
+        }
+
+        /// Set the verification tier of an issuer. `Unverified` clears it.
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::set_tier())]
+        pub fn set_tier(origin: OriginFor<T>, hash: T::Hash, tier: VerificationTier) -> DispatchResult {
+            T::VerifierOrigin::ensure_origin(origin)?;
+
+            ensure!(Issuers::<T>::contains_key(hash), Error::<T>::IssuerNotFound);
+
+            if tier == VerificationTier::Unverified {
+                IssuerTiers::<T>::remove(hash);
+            } else {
+                IssuerTiers::<T>::insert(hash, tier);
+            }
+
+            Self::deposit_event(Event::IssuerTierSet { hash, tier });
+
+            Ok(())
+        }
+
+        /// Set the profile of an issuer, replacing any previous one. The caller reserves
+        /// `ProfileDepositPerByte` for every byte of profile data; the deposit of the
+        /// previous profile is returned to whoever reserved it.
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::set_profile(T::MaxProfileFieldLength::get() * 4))]
+        pub fn set_profile(
+            origin: OriginFor<T>,
+            hash: T::Hash,
+            website: Vec<u8>,
+            logo_hash: Option<T::Hash>,
+            description_uri: Vec<u8>,
+            contact: Vec<u8>,
+            domain_proof: Vec<u8>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let issuer = Issuers::<T>::get(hash)
+                .ok_or(Error::<T>::IssuerNotFound)?;
+            ensure!(issuer.controllers.contains(&who), Error::<T>::NotAuthorized);
+
+            let bytes = website.len() + description_uri.len() + contact.len() + domain_proof.len();
+            let bound = |field: Vec<u8>| BoundedVec::<u8, T::MaxProfileFieldLength>::try_from(field)
+                .map_err(|_| Error::<T>::ProfileFieldTooLong);
+            let website = bound(website)?;
+            let description_uri = bound(description_uri)?;
+            let contact = bound(contact)?;
+            let domain_proof = bound(domain_proof)?;
+
+            let deposit = T::ProfileDepositPerByte::get()
+                .saturating_mul(((bytes + logo_hash.encoded_size()) as u32).into());
+
+            if let Some(previous) = IssuerProfiles::<T>::get(hash) {
+                T::Currency::unreserve(&previous.depositor, previous.deposit);
+            }
+            T::Currency::reserve(&who, deposit)
+                .map_err(|_| Error::<T>::InsufficientBalance)?;
+
+            let profile = IssuerProfile::<T> {
+                website,
+                logo_hash,
+                description_uri,
+                contact,
+                domain_proof,
+                depositor: who,
+                deposit,
+            };
+            IssuerProfiles::<T>::insert(hash, profile.clone());
+
+            Self::deposit_event(Event::IssuerProfileSet { hash, profile });
+
+            Ok(Some(T::WeightInfo::set_profile(bytes as u32)).into())
+        }
+
+        /// Remove the profile of an issuer and return its deposit.
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::clear_profile())]
+        pub fn clear_profile(origin: OriginFor<T>, hash: T::Hash) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let issuer = Issuers::<T>::get(hash)
+                .ok_or(Error::<T>::IssuerNotFound)?;
+            ensure!(issuer.controllers.contains(&who), Error::<T>::NotAuthorized);
+
+            let profile = IssuerProfiles::<T>::take(hash)
+                .ok_or(Error::<T>::ProfileNotFound)?;
+            T::Currency::unreserve(&profile.depositor, profile.deposit);
+
+            Self::deposit_event(Event::IssuerProfileCleared { hash });
+
+            Ok(())
+        }
+
+        /// Rename an issuer. Its id, and with it its schemas and attestations, stays the same;
+        /// only the name index moves to the new name, freeing the old one. A verification
+        /// tier was granted to the old name, so the issuer goes back to `Unverified`.
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::rename_issuer(T::MaxNameLength::get()))]
+        pub fn rename_issuer(
+            origin: OriginFor<T>,
+            hash: T::Hash,
+            name: Vec<u8>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let mut issuer = Issuers::<T>::get(hash)
+                .ok_or(Error::<T>::IssuerNotFound)?;
+            ensure!(issuer.controllers.contains(&who), Error::<T>::NotAuthorized);
+
+            ensure!(name.len() <= T::MaxNameLength::get() as usize, Error::<T>::IssuerNameTooLong);
+
+            let (name, canonical) = Self::canonical_name(&name)?;
+            let name_hash = <T as Config>::Hashing::hash(&canonical);
+
+            // Changing only case or spacing keeps the same index entry.
+            ensure!(
+                IssuerNameIndex::<T>::get(name_hash).map_or(true, |owner| owner == hash),
+                Error::<T>::IssuerAlreadyExists
+            );
+
+            if let Some(old_hash) = Self::name_hash(&issuer.name) {
+                if IssuerNameIndex::<T>::get(old_hash) == Some(hash) {
+                    IssuerNameIndex::<T>::remove(old_hash);
+                }
+            }
+            IssuerNameIndex::<T>::insert(name_hash, hash);
+
+            let issuer_name = BoundedVec::<u8, T::MaxNameLength>::try_from(name)
+                .map_err(|_| Error::<T>::IssuerNameTooLong)?;
+            let old_name = sp_std::mem::replace(&mut issuer.name, issuer_name.clone());
+            Issuers::<T>::insert(hash, issuer);
+
+            Self::deposit_event(Event::IssuerRenamed { hash, old_name, issuer_name: issuer_name.clone() });
+
+            if IssuerTiers::<T>::take(hash) != VerificationTier::Unverified {
+                Self::deposit_event(Event::IssuerTierSet { hash, tier: VerificationTier::Unverified });
+            }
+
+            Ok(Some(T::WeightInfo::rename_issuer(issuer_name.len() as u32)).into())
+        }
+
+        /// Set the accounts able to reset the controllers of an issuer, and how many of them
+        /// must approve a reset. The caller reserves `RecoveryDeposit`, and the deposit of
+        /// the previous accounts is returned to whoever reserved it. No accounts removes
+        /// recovery and returns its deposit. Any reset in progress is cancelled.
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::set_recovery(T::MaxRecoveryAccounts::get()))]
+        pub fn set_recovery(
+            origin: OriginFor<T>,
+            hash: T::Hash,
+            accounts: Vec<T::AccountId>,
+            threshold: u32,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let issuer = Issuers::<T>::get(hash)
+                .ok_or(Error::<T>::IssuerNotFound)?;
+            ensure!(issuer.controllers.contains(&who), Error::<T>::NotAuthorized);
+
+            let accounts = BoundedVec::<T::AccountId, T::MaxRecoveryAccounts>::try_from(
+                accounts.into_iter().collect::<BTreeSet<_>>().into_iter().collect::<Vec<_>>(),
+            )
+                .map_err(|_| Error::<T>::TooManyRecoveryAccounts)?;
+
+            let account_count = accounts.len() as u32;
+            let previous = RecoveryConfigs::<T>::get(hash);
+            if accounts.is_empty() {
+                ensure!(previous.is_some(), Error::<T>::RecoveryNotConfigured);
+            } else {
+                ensure!(threshold >= 1 && threshold <= account_count, Error::<T>::InvalidRecoveryThreshold);
+            }
+
+            if ActiveRecoveries::<T>::take(hash).is_some() {
+                Self::deposit_event(Event::RecoveryCancelled { hash, by: who.clone() });
+            }
+
+            if let Some(previous) = previous {
+                T::Currency::unreserve(&previous.depositor, previous.deposit);
+            }
+
+            if accounts.is_empty() {
+                RecoveryConfigs::<T>::remove(hash);
+                Self::deposit_event(Event::RecoveryRemoved { hash });
+            } else {
+                let deposit = T::RecoveryDeposit::get();
+                T::Currency::reserve(&who, deposit)
+                    .map_err(|_| Error::<T>::InsufficientBalance)?;
+                RecoveryConfigs::<T>::insert(hash, RecoveryConfig::<T> {
+                    accounts: accounts.clone(),
+                    threshold,
+                    depositor: who,
+                    deposit,
+                });
+                Self::deposit_event(Event::RecoverySet { hash, accounts, threshold });
+            }
+
+            Ok(Some(T::WeightInfo::set_recovery(account_count)).into())
+        }
+
+        /// Start resetting the controllers of an issuer to `new_controllers`, approving the
+        /// reset. Only one reset can be in progress at a time.
+        #[pallet::call_index(8)]
+        #[pallet::weight(T::WeightInfo::initiate_recovery(T::MaxControllers::get()))]
+        pub fn initiate_recovery(
+            origin: OriginFor<T>,
+            hash: T::Hash,
+            new_controllers: Vec<T::AccountId>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            ensure!(Issuers::<T>::contains_key(hash), Error::<T>::IssuerNotFound);
+            let config = RecoveryConfigs::<T>::get(hash)
+                .ok_or(Error::<T>::RecoveryNotConfigured)?;
+            ensure!(config.accounts.contains(&who), Error::<T>::NotRecoveryAccount);
+            ensure!(!ActiveRecoveries::<T>::contains_key(hash), Error::<T>::RecoveryAlreadyActive);
+
+            let new_controllers = BoundedVec::<T::AccountId, T::MaxControllers>::try_from(
+                new_controllers.into_iter().collect::<BTreeSet<_>>().into_iter().collect::<Vec<_>>(),
+            )
+                .map_err(|_| Error::<T>::TooManyControllers)?;
+
+            let initiated_at = frame_system::Pallet::<T>::block_number();
+            let mut approvals = BoundedVec::<T::AccountId, T::MaxRecoveryAccounts>::new();
+            approvals.try_push(who.clone())
+                .map_err(|_| Error::<T>::TooManyRecoveryAccounts)?;
+            ActiveRecoveries::<T>::insert(hash, ActiveRecovery::<T> {
+                new_controllers: new_controllers.clone(),
+                initiated_at,
+                approvals,
+            });
+
+            Self::deposit_event(Event::RecoveryInitiated {
+                hash,
+                initiator: who,
+                new_controllers: new_controllers.clone(),
+                challenge_ends: initiated_at.saturating_add(T::RecoveryChallengePeriod::get()),
+            });
+
+            Ok(Some(T::WeightInfo::initiate_recovery(new_controllers.len() as u32)).into())
+        }
+
+        /// Approve the controller reset in progress for an issuer.
+        #[pallet::call_index(9)]
+        #[pallet::weight(T::WeightInfo::approve_recovery())]
+        pub fn approve_recovery(origin: OriginFor<T>, hash: T::Hash) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let config = RecoveryConfigs::<T>::get(hash)
+                .ok_or(Error::<T>::RecoveryNotConfigured)?;
+            ensure!(config.accounts.contains(&who), Error::<T>::NotRecoveryAccount);
+
+            let mut recovery = ActiveRecoveries::<T>::get(hash)
+                .ok_or(Error::<T>::NoActiveRecovery)?;
+            ensure!(!recovery.approvals.contains(&who), Error::<T>::AlreadyApproved);
+            recovery.approvals.try_push(who.clone())
+                .map_err(|_| Error::<T>::TooManyRecoveryAccounts)?;
+
+            let approvals = recovery.approvals.len() as u32;
+            ActiveRecoveries::<T>::insert(hash, recovery);
+
+            Self::deposit_event(Event::RecoveryApproved { hash, account: who, approvals });
+
+            Ok(())
+        }
+
+        /// Cancel the controller reset in progress for an issuer. Open to its controllers,
+        /// at any time before the reset is finished.
+        #[pallet::call_index(10)]
+        #[pallet::weight(T::WeightInfo::cancel_recovery())]
+        pub fn cancel_recovery(origin: OriginFor<T>, hash: T::Hash) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let issuer = Issuers::<T>::get(hash)
+                .ok_or(Error::<T>::IssuerNotFound)?;
+            ensure!(issuer.controllers.contains(&who), Error::<T>::NotAuthorized);
+
+            ActiveRecoveries::<T>::take(hash)
+                .ok_or(Error::<T>::NoActiveRecovery)?;
+
+            Self::deposit_event(Event::RecoveryCancelled { hash, by: who });
+
+            Ok(())
+        }
+
+        /// Replace the controllers of an issuer with those of its controller reset, once the
+        /// challenge period is over and enough recovery accounts approved it. Open to anyone.
+        /// Unless its depositor is among the new controllers, the registry deposit of the
+        /// issuer moves to the first of them, as the depositor's keys may be the ones lost.
+        #[pallet::call_index(11)]
+        #[pallet::weight(T::WeightInfo::finish_recovery(T::MaxControllers::get()))]
+        pub fn finish_recovery(origin: OriginFor<T>, hash: T::Hash) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+
+            let mut issuer = Issuers::<T>::get(hash)
+                .ok_or(Error::<T>::IssuerNotFound)?;
+            let config = RecoveryConfigs::<T>::get(hash)
+                .ok_or(Error::<T>::RecoveryNotConfigured)?;
+            let recovery = ActiveRecoveries::<T>::get(hash)
+                .ok_or(Error::<T>::NoActiveRecovery)?;
+
+            let challenge_ends = recovery.initiated_at.saturating_add(T::RecoveryChallengePeriod::get());
+            ensure!(frame_system::Pallet::<T>::block_number() >= challenge_ends, Error::<T>::ChallengePeriodNotOver);
+            ensure!(recovery.approvals.len() as u32 >= config.threshold, Error::<T>::NotEnoughApprovals);
+
+            ActiveRecoveries::<T>::remove(hash);
+            issuer.controllers = recovery.new_controllers;
+            let controller_count = issuer.controllers.len() as u32;
+            Issuers::<T>::insert(hash, issuer.clone());
+
+            if let (Some((depositor, deposit)), Some(controller)) = (IssuerDeposits::<T>::get(hash), issuer.controllers.first()) {
+                if !issuer.controllers.contains(&depositor) {
+                    // Fails, moving nothing, if the new controller has no account yet
+                    if let Ok(unmoved) = T::Currency::repatriate_reserved(&depositor, controller, deposit, BalanceStatus::Reserved) {
+                        IssuerDeposits::<T>::insert(hash, (controller.clone(), deposit.saturating_sub(unmoved)));
+                    }
+                }
+            }
+
+            Self::deposit_event(Event::RecoveryFinished { hash, controllers_identified: issuer.controllers });
+
+            Ok(Some(T::WeightInfo::finish_recovery(controller_count)).into())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Display and canonical form of an issuer name. Names are ASCII only; surrounding
+        /// whitespace is trimmed and inner runs of whitespace become a single space. The
+        /// canonical form, which names are compared and indexed by, is also lowercased, so
+        /// "Acme", "acme" and " Acme " are the same issuer.
+        pub fn canonical_name(name: &[u8]) -> Result<(Vec<u8>, Vec<u8>), Error<T>> {
+            ensure!(
+                name.iter().all(|c| c.is_ascii_graphic() || c.is_ascii_whitespace()),
+                Error::<T>::InvalidIssuerName
+            );
+
+            let name = name
+                .split(|c| c.is_ascii_whitespace())
+                .filter(|word| !word.is_empty())
+                .collect::<Vec<_>>()
+                .join(&b' ');
+            ensure!(!name.is_empty(), Error::<T>::InvalidIssuerName);
+
+            let canonical = name.to_ascii_lowercase();
+            Ok((name, canonical))
+        }
+
+        /// Hash of the canonical form of `name`, the key of [`IssuerNameIndex`], or `None` if
+        /// `name` is not a valid issuer name.
+        pub fn name_hash(name: &[u8]) -> Option<T::Hash> {
+            let (_, canonical) = Self::canonical_name(name).ok()?;
+            Some(<T as Config>::Hashing::hash(&canonical))
+        }
+
+        /// Id of the issuer currently named `name`, in any case or spacing.
+        pub fn issuer_by_name(name: &[u8]) -> Option<T::Hash> {
+            IssuerNameIndex::<T>::get(Self::name_hash(name)?)
+        }
+
+        /// Id of the issuer created by `creator` when `NextIssuerNonce` is `nonce`.
+        pub fn issuer_id(creator: &T::AccountId, nonce: u64) -> T::Hash {
+            <T as Config>::Hashing::hash_of(&(creator, nonce))
+        }
+
+        /// Id of the `index`th issuer of the genesis config.
+        pub fn genesis_issuer_id(index: u64) -> T::Hash {
+            Self::issuer_id(&Self::zero_account(), index)
+        }
+
+        /// Take the next nonce and return the id of an issuer created by `creator` with it.
+        pub(crate) fn next_issuer_id(creator: &T::AccountId) -> T::Hash {
+            let nonce = NextIssuerNonce::<T>::mutate(|nonce| {
+                let current = *nonce;
+                *nonce = nonce.saturating_add(1);
+                current
+            });
+            Self::issuer_id(creator, nonce)
+        }
+
+        /// Creator of issuers that have none: those of the genesis config and those keyed by
+        /// name before the migration to ids.
+        pub(crate) fn zero_account() -> T::AccountId {
+            T::AccountId::decode(&mut TrailingZeroInput::zeroes())
+                .expect("infinite length input; no invalid inputs for type; qed")
+        }
+
+        /// Verification tier of issuer `hash`, `Unverified` for unknown issuers.
+        pub fn tier_of(hash: T::Hash) -> VerificationTier {
+            IssuerTiers::<T>::get(hash)
+        }
+
+        /// Issuer `hash` with its tier and profile, for the runtime API.
+        pub fn issuer_info(hash: T::Hash) -> Option<IssuerInfo<T::AccountId, T::Hash>> {
+            let issuer = Issuers::<T>::get(hash)?;
+            let profile = IssuerProfiles::<T>::get(hash).map(|profile| ProfileInfo {
+                website: profile.website.into_inner(),
+                logo_hash: profile.logo_hash,
+                description_uri: profile.description_uri.into_inner(),
+                contact: profile.contact.into_inner(),
+                domain_proof: profile.domain_proof.into_inner(),
+            });
+
+            Some(IssuerInfo {
+                name: issuer.name.into_inner(),
+                controllers: issuer.controllers.into_inner(),
+                tier: Self::tier_of(hash),
+                profile,
+            })
+        }
     }
 }
@@ -0,0 +1,218 @@
+//! Storage migrations for `pallet_issuers`.
+
+use super::*;
+use frame_support::{
+    migrations::VersionedMigration,
+    pallet_prelude::*,
+    storage_alias,
+    traits::UncheckedOnRuntimeUpgrade,
+};
+use sp_std::{marker::PhantomData, vec::Vec};
+#[cfg(feature = "try-runtime")]
+use sp_runtime::TryRuntimeError;
+
+/// State a `pre_upgrade` check encoded for its `post_upgrade` check.
+#[cfg(feature = "try-runtime")]
+fn decode_state<S: Decode>(state: &[u8]) -> Result<S, TryRuntimeError> {
+    S::decode(&mut &state[..]).map_err(|_| "Invalid pre-upgrade state".into())
+}
+
+/// Issuers gained [`IssuerNameIndex`]. Existing issuers keep the hash of their raw name and
+/// are indexed by their canonical name; where two names canonicalize to the same one, the
+/// first issuer found keeps the name, and names that are not valid under the current rules
+/// are left out of the index until the issuer is renamed.
+pub mod v1 {
+    use super::*;
+
+    pub struct InnerMigrateV0ToV1<T>(PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut reads = 0u64;
+            let mut indexed = 0u64;
+
+            for (hash, issuer) in Issuers::<T>::iter() {
+                reads += 1;
+
+                let Some(name_hash) = Pallet::<T>::name_hash(&issuer.name) else {
+                    log::warn!(target: "issuers", "Issuer {:?} has an invalid name, not indexed", hash);
+                    continue;
+                };
+
+                reads += 1;
+                if IssuerNameIndex::<T>::contains_key(name_hash) {
+                    log::warn!(target: "issuers", "Issuer {:?} has a duplicate name, not indexed", hash);
+                    continue;
+                }
+
+                IssuerNameIndex::<T>::insert(name_hash, hash);
+                indexed += 1;
+            }
+
+            log::info!(target: "issuers", "Indexed {} issuer names", indexed);
+
+            T::DbWeight::get().reads_writes(reads, indexed)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+            Ok((Issuers::<T>::iter_keys().count() as u64).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+            let count: u64 = decode_state(&state)?;
+            ensure!(Issuers::<T>::iter_values().count() as u64 == count, "Issuers lost migrating to v1");
+            for (hash, issuer) in Issuers::<T>::iter() {
+                if let Some(name_hash) = Pallet::<T>::name_hash(&issuer.name) {
+                    ensure!(IssuerNameIndex::<T>::contains_key(name_hash), "Issuer name not indexed");
+                } else {
+                    ensure!(!IssuerNameIndex::<T>::iter_values().any(|id| id == hash), "Invalid issuer name indexed");
+                }
+            }
+            ensure!(
+                IssuerNameIndex::<T>::iter_values().all(Issuers::<T>::contains_key),
+                "Issuer name indexed for an unknown issuer"
+            );
+            Ok(())
+        }
+    }
+
+    pub type MigrateV0ToV1<T> = VersionedMigration<
+        0,
+        1,
+        InnerMigrateV0ToV1<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Issuers were keyed by the hash of their name. Each one gets an id as if created by the
+/// zero account, in storage order, and moves to it along with its tier, profile and name
+/// index entry. [`v2::LegacyIssuerIds`] records the id of every old key for the migration
+/// of `pallet_credentials` to follow, until [`v3`] clears it.
+pub mod v2 {
+    use super::*;
+
+    /// Id of issuers created before ids were decoupled from names, by the hash of the name
+    /// they were keyed by.
+    #[storage_alias]
+    pub type LegacyIssuerIds<T: Config> =
+        StorageMap<Pallet<T>, Blake2_128Concat, T::Hash, T::Hash, OptionQuery>;
+
+    /// Id of issuer `hash` if it was keyed by the hash of its name, `hash` otherwise.
+    pub fn resolve_legacy_id<T: Config>(hash: T::Hash) -> T::Hash {
+        LegacyIssuerIds::<T>::get(hash).unwrap_or(hash)
+    }
+
+    pub struct InnerMigrateV1ToV2<T>(PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV1ToV2<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut reads = 0u64;
+            let mut writes = 0u64;
+
+            let issuers = Issuers::<T>::drain().collect::<Vec<_>>();
+            let moved = issuers.len();
+            let creator = Pallet::<T>::zero_account();
+
+            for (old_hash, issuer) in issuers {
+                let hash = Pallet::<T>::next_issuer_id(&creator);
+                reads += 3;
+                writes += 5;
+
+                Issuers::<T>::insert(hash, issuer);
+                if let Ok(tier) = IssuerTiers::<T>::try_get(old_hash) {
+                    IssuerTiers::<T>::remove(old_hash);
+                    IssuerTiers::<T>::insert(hash, tier);
+                }
+                if let Some(profile) = IssuerProfiles::<T>::take(old_hash) {
+                    IssuerProfiles::<T>::insert(hash, profile);
+                }
+                LegacyIssuerIds::<T>::insert(old_hash, hash);
+            }
+
+            IssuerNameIndex::<T>::translate_values::<T::Hash, _>(|old_hash| {
+                reads += 2;
+                writes += 1;
+                Some(resolve_legacy_id::<T>(old_hash))
+            });
+
+            log::info!(target: "issuers", "Moved {} issuers to ids", moved);
+
+            T::DbWeight::get().reads_writes(reads, writes)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+            let issuers = Issuers::<T>::iter_keys().count() as u64;
+            let names = IssuerNameIndex::<T>::iter_keys().count() as u64;
+            Ok((issuers, names).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+            let (issuers, names): (u64, u64) = decode_state(&state)?;
+            ensure!(Issuers::<T>::iter_values().count() as u64 == issuers, "Issuers lost migrating to v2");
+            ensure!(LegacyIssuerIds::<T>::iter_values().count() as u64 == issuers, "Legacy issuer ids not recorded");
+            ensure!(
+                LegacyIssuerIds::<T>::iter().all(|(old_hash, hash)| {
+                    !Issuers::<T>::contains_key(old_hash) && Issuers::<T>::contains_key(hash)
+                }),
+                "Issuers not moved to their ids"
+            );
+            ensure!(IssuerNameIndex::<T>::iter_values().count() as u64 == names, "Issuer names lost migrating to v2");
+            ensure!(
+                IssuerNameIndex::<T>::iter_values().all(Issuers::<T>::contains_key)
+                    && IssuerTiers::<T>::iter_keys().all(Issuers::<T>::contains_key)
+                    && IssuerProfiles::<T>::iter_keys().all(Issuers::<T>::contains_key),
+                "Issuer state left at a legacy key"
+            );
+            Ok(())
+        }
+    }
+
+    pub type MigrateV1ToV2<T> = VersionedMigration<
+        1,
+        2,
+        InnerMigrateV1ToV2<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Clears [`v2::LegacyIssuerIds`], once the credentials migration that reads it has run.
+pub mod v3 {
+    use super::*;
+
+    pub struct InnerMigrateV2ToV3<T>(PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV2ToV3<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let removed = v2::LegacyIssuerIds::<T>::clear(u32::MAX, None).unique;
+
+            log::info!(target: "issuers", "Removed {} legacy issuer ids", removed);
+
+            T::DbWeight::get().reads_writes(removed as u64, removed as u64)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+            Ok(Vec::new())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_: Vec<u8>) -> Result<(), TryRuntimeError> {
+            ensure!(v2::LegacyIssuerIds::<T>::iter_keys().next().is_none(), "Legacy issuer ids left");
+            Ok(())
+        }
+    }
+
+    pub type MigrateV2ToV3<T> = VersionedMigration<
+        2,
+        3,
+        InnerMigrateV2ToV3<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
@@ -3,34 +3,36 @@
 
 use super::*;
 use crate::{
-	self as pallet_issuers
+	self as pallet_issuers,
+	migrations::*,
 };
 
-use codec::{Decode, Encode};
 use frame_support::{
-	assert_err, assert_noop, assert_ok, derive_impl, parameter_types,
-	traits::{ConstU32, ConstU64},
+	assert_noop, assert_ok, derive_impl, parameter_types,
+	traits::{ConstU128, ConstU32, ConstU64, GetStorageVersion, OnRuntimeUpgrade, ReservableCurrency, StorageVersion},
 	BoundedVec,
 };
 use frame_system::EnsureRoot;
 use sp_core::H256;
-use sp_io::crypto::{sr25519_generate, sr25519_sign};
 use sp_runtime::{
-	traits::{BadOrigin, BlakeTwo256, IdentifyAccount, IdentityLookup, Verify},
-	BuildStorage, MultiSignature, MultiSigner,
+	traits::{BlakeTwo256, Hash, IdentifyAccount, IdentityLookup, Verify},
+	BuildStorage, DispatchError, MultiSignature,
 };
 
-type AccountIdOf<Test> = <Test as frame_system::Config>::AccountId;
 pub type AccountPublic = <MultiSignature as Verify>::Signer;
 pub type AccountId = <AccountPublic as IdentifyAccount>::AccountId;
 
 type Block = frame_system::mocking::MockBlock<Test>;
 
+type Balance = <Test as pallet_balances::Config>::Balance;
+type AccountData = pallet_balances::AccountData<Balance>;
+
 frame_support::construct_runtime!(
 	pub enum Test
 	{
 		System: frame_system,
-		Issuers: pallet_issuers,
+		IssuersModule: pallet_issuers,
+		Balances: pallet_balances,
 	}
 );
 
@@ -38,33 +40,50 @@ frame_support::construct_runtime!(
 impl frame_system::Config for Test {
 	type AccountId = AccountId;
 	type BaseCallFilter = frame_support::traits::Everything;
-    type BlockWeights = ();
-    type BlockLength = ();
-    type DbWeight = ();
-    type RuntimeOrigin = RuntimeOrigin;
-    type RuntimeCall = RuntimeCall;
-    type Hash = H256;
-    type Hashing = BlakeTwo256;
-    type Lookup = IdentityLookup<Self::AccountId>;
-    type RuntimeEvent = RuntimeEvent;
-    type BlockHashCount = ConstU64<250>;
-    type Version = ();
-    type PalletInfo = PalletInfo;
-    type AccountData = ();
-    type OnNewAccount = ();
-    type OnKilledAccount = ();
-    type SystemWeightInfo = ();
-    type OnSetCode = ();
-    type MaxConsumers = frame_support::traits::ConstU32<16>;
-    type Block = Block;
-    type RuntimeTask = ();
-	
-	
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = AccountData;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+	type Block = Block;
+	type RuntimeTask = ();
 }
 
 parameter_types! {
 	pub const MaxNameLength: u32 = 120;
 	pub const MaxControllers: u32 = 20;
+	pub const IssuerRegistryDeposit: u128 = 1_000;
+	pub const ExistentialDeposit: u128 = 1;
+	pub const MaxFreezes: u32 = 1;
+}
+
+impl pallet_balances::Config for Test {
+	type Balance = u128;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = frame_system::Pallet<Test>;
+	type WeightInfo = ();
+	type MaxLocks = ConstU32<50>;
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type RuntimeHoldReason = ();
+	type RuntimeFreezeReason = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = MaxFreezes;
 }
 
 impl pallet_issuers::Config for Test {
@@ -73,8 +92,529 @@ impl pallet_issuers::Config for Test {
 
 	type MaxNameLength = MaxNameLength;
 	type MaxControllers = MaxControllers;
+
+	type WeightInfo = ();
+	type Currency = Balances;
+
+	type IssuerRegistryDeposit = IssuerRegistryDeposit;
+	type ParamsOrigin = EnsureRoot<Self::AccountId>;
+	type VerifierOrigin = EnsureRoot<Self::AccountId>;
+	type MaxProfileFieldLength = ConstU32<256>;
+	type ProfileDepositPerByte = ConstU128<1>;
+	type MaxRecoveryAccounts = ConstU32<10>;
+	type RecoveryChallengePeriod = ConstU64<10>;
+	type RecoveryDeposit = ConstU128<500>;
+}
+
+fn account(n: u8) -> AccountId {
+	AccountId::new([n; 32])
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	frame_system::GenesisConfig::<Test>::default().build_storage().unwrap().into()
-}
\ No newline at end of file
+	let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: (1..=4).map(|n| (account(n), 1_000_000)).collect(),
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(storage);
+	// Events are only recorded from block 1
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Run migration `M` as a runtime upgrade would, with its try-runtime checks when the
+/// feature is on.
+fn run_migration<M: OnRuntimeUpgrade>() {
+	#[cfg(feature = "try-runtime")]
+	assert_ok!(M::try_on_runtime_upgrade(true));
+	#[cfg(not(feature = "try-runtime"))]
+	M::on_runtime_upgrade();
+}
+
+fn canonical(name: &str) -> Result<(Vec<u8>, Vec<u8>), DispatchError> {
+	Pallet::<Test>::canonical_name(name.as_bytes()).map_err(Into::into)
+}
+
+/// Create an issuer named `name` controlled by `creator`, returning its id.
+fn create(creator: u8, name: &str) -> H256 {
+	let id = Pallet::<Test>::issuer_id(&account(creator), NextIssuerNonce::<Test>::get());
+	assert_ok!(IssuersModule::create_issuer(RuntimeOrigin::signed(account(creator)), name.as_bytes().to_vec(), vec![account(creator)]));
+	id
+}
+
+fn legacy_issuer(name: &str) -> Issuer<Test> {
+	Issuer {
+		name: BoundedVec::truncate_from(name.as_bytes().to_vec()),
+		controllers: BoundedVec::truncate_from(vec![account(1)]),
+	}
+}
+
+#[test]
+fn canonical_name_trims_collapses_and_lowercases() {
+	assert_eq!(canonical("Acme"), Ok((b"Acme".to_vec(), b"acme".to_vec())));
+	assert_eq!(canonical("  Acme \t\n Corp  "), Ok((b"Acme Corp".to_vec(), b"acme corp".to_vec())));
+	assert_eq!(canonical("ACME-Corp.io"), Ok((b"ACME-Corp.io".to_vec(), b"acme-corp.io".to_vec())));
+}
+
+#[test]
+fn canonical_name_rejects_empty_and_non_ascii_names() {
+	for name in ["", "   ", "\t\n", "Acme\0", "Acme\x7f", "Acmé"] {
+		assert_eq!(canonical(name), Err(Error::<Test>::InvalidIssuerName.into()), "{name:?}");
+	}
+}
+
+#[test]
+fn create_issuer_stores_the_display_name_and_indexes_the_canonical_one() {
+	new_test_ext().execute_with(|| {
+		let hash = create(1, "  Acme   Corp ");
+
+		let issuer = Issuers::<Test>::get(hash).unwrap();
+		assert_eq!(issuer.name.into_inner(), b"Acme Corp".to_vec());
+		for name in ["Acme Corp", "acme corp", "ACME\tCORP", " acme  corp"] {
+			assert_eq!(Pallet::<Test>::issuer_by_name(name.as_bytes()), Some(hash), "{name:?}");
+		}
+		assert_eq!(IssuerDeposits::<Test>::get(hash), Some((account(1), 1_000)));
+		assert_eq!(Balances::reserved_balance(account(1)), 1_000);
+	});
+}
+
+#[test]
+fn create_issuer_rejects_names_taken_in_any_case_or_spacing() {
+	new_test_ext().execute_with(|| {
+		create(1, "Acme Corp");
+
+		for name in ["Acme Corp", "ACME CORP", "acme corp", " Acme\tCorp "] {
+			assert_noop!(
+				IssuersModule::create_issuer(RuntimeOrigin::signed(account(2)), name.as_bytes().to_vec(), vec![account(2)]),
+				Error::<Test>::IssuerAlreadyExists
+			);
+		}
+
+		// Dropping the space makes a different name
+		create(2, "AcmeCorp");
+	});
+}
+
+#[test]
+fn create_issuer_rejects_invalid_and_long_names() {
+	new_test_ext().execute_with(|| {
+		for name in [&b""[..], b"  ", b"Acme\0", "Acmé".as_bytes()] {
+			assert_noop!(
+				IssuersModule::create_issuer(RuntimeOrigin::signed(account(1)), name.to_vec(), vec![account(1)]),
+				Error::<Test>::InvalidIssuerName
+			);
+		}
+
+		assert_noop!(
+			IssuersModule::create_issuer(RuntimeOrigin::signed(account(1)), vec![b'a'; 121], vec![account(1)]),
+			Error::<Test>::IssuerNameTooLong
+		);
+	});
+}
+
+#[test]
+fn issuer_ids_do_not_depend_on_names() {
+	new_test_ext().execute_with(|| {
+		let first = create(1, "Acme");
+		let second = create(1, "Globex");
+
+		assert_eq!(first, Pallet::<Test>::issuer_id(&account(1), 0));
+		assert_eq!(second, Pallet::<Test>::issuer_id(&account(1), 1));
+		assert_eq!(NextIssuerNonce::<Test>::get(), 2);
+	});
+}
+
+#[test]
+fn rename_issuer_moves_the_name_index() {
+	new_test_ext().execute_with(|| {
+		let hash = create(1, "Acme");
+
+		assert_ok!(IssuersModule::rename_issuer(RuntimeOrigin::signed(account(1)), hash, b" Globex  Corp".to_vec()));
+
+		assert_eq!(Issuers::<Test>::get(hash).unwrap().name.into_inner(), b"Globex Corp".to_vec());
+		assert_eq!(Pallet::<Test>::issuer_by_name(b"globex corp"), Some(hash));
+		assert_eq!(Pallet::<Test>::issuer_by_name(b"Acme"), None);
+		System::assert_last_event(
+			Event::<Test>::IssuerRenamed {
+				hash,
+				old_name: BoundedVec::truncate_from(b"Acme".to_vec()),
+				issuer_name: BoundedVec::truncate_from(b"Globex Corp".to_vec()),
+			}
+			.into(),
+		);
+
+		// The old name is free again
+		create(2, "ACME");
+	});
+}
+
+#[test]
+fn rename_issuer_allows_changing_case_and_spacing() {
+	new_test_ext().execute_with(|| {
+		let hash = create(1, "Acme Corp");
+
+		assert_ok!(IssuersModule::rename_issuer(RuntimeOrigin::signed(account(1)), hash, b"ACME  corp".to_vec()));
+
+		assert_eq!(Issuers::<Test>::get(hash).unwrap().name.into_inner(), b"ACME corp".to_vec());
+		assert_eq!(Pallet::<Test>::issuer_by_name(b"acme corp"), Some(hash));
+		assert_eq!(IssuerNameIndex::<Test>::iter().count(), 1);
+	});
+}
+
+#[test]
+fn rename_issuer_rejects_names_of_other_issuers() {
+	new_test_ext().execute_with(|| {
+		let acme = create(1, "Acme");
+		create(2, "Globex");
+
+		assert_noop!(
+			IssuersModule::rename_issuer(RuntimeOrigin::signed(account(1)), acme, b"GLOBEX".to_vec()),
+			Error::<Test>::IssuerAlreadyExists
+		);
+		assert_noop!(
+			IssuersModule::rename_issuer(RuntimeOrigin::signed(account(1)), acme, b"Globex\x07".to_vec()),
+			Error::<Test>::InvalidIssuerName
+		);
+		assert_noop!(
+			IssuersModule::rename_issuer(RuntimeOrigin::signed(account(2)), acme, b"Initech".to_vec()),
+			Error::<Test>::NotAuthorized
+		);
+	});
+}
+
+#[test]
+fn rename_issuer_resets_the_verification_tier() {
+	new_test_ext().execute_with(|| {
+		let hash = create(1, "Acme");
+		assert_ok!(IssuersModule::set_tier(RuntimeOrigin::root(), hash, VerificationTier::Verified));
+
+		assert_ok!(IssuersModule::rename_issuer(RuntimeOrigin::signed(account(1)), hash, b"Globex".to_vec()));
+
+		assert_eq!(Pallet::<Test>::tier_of(hash), VerificationTier::Unverified);
+		assert!(!IssuerTiers::<Test>::contains_key(hash));
+		System::assert_last_event(Event::<Test>::IssuerTierSet { hash, tier: VerificationTier::Unverified }.into());
+	});
+}
+
+#[test]
+fn edit_controllers_drops_duplicates() {
+	new_test_ext().execute_with(|| {
+		let hash = create(1, "Acme");
+
+		assert_ok!(IssuersModule::edit_controllers(
+			RuntimeOrigin::signed(account(1)),
+			hash,
+			Some(vec![account(2), account(1), account(2)]),
+		));
+
+		let controllers = Issuers::<Test>::get(hash).unwrap().controllers.into_inner();
+		assert_eq!(controllers.len(), 2);
+		assert!(controllers.contains(&account(1)) && controllers.contains(&account(2)));
+	});
+}
+
+#[test]
+fn migration_to_v1_indexes_canonical_names() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<IssuersModule>();
+
+		let [acme, acme_upper, invalid, globex] = [1u8, 2, 3, 4].map(H256::repeat_byte);
+		Issuers::<Test>::insert(acme, legacy_issuer("Acme"));
+		Issuers::<Test>::insert(acme_upper, legacy_issuer(" ACME "));
+		Issuers::<Test>::insert(invalid, legacy_issuer("Acme\0"));
+		Issuers::<Test>::insert(globex, legacy_issuer("Globex  Corp"));
+
+		run_migration::<v1::MigrateV0ToV1<Test>>();
+
+		// Whichever of the clashing names comes first in storage keeps it
+		let owner = Pallet::<Test>::issuer_by_name(b"acme").unwrap();
+		assert!(owner == acme || owner == acme_upper);
+		assert_eq!(Pallet::<Test>::issuer_by_name(b"globex corp"), Some(globex));
+		assert!(IssuerNameIndex::<Test>::iter_values().all(|hash| hash != invalid));
+		assert_eq!(IssuerNameIndex::<Test>::iter().count(), 2);
+
+		// Issuers keep their keys until the migration to ids
+		assert_eq!(Issuers::<Test>::iter().count(), 4);
+		assert_eq!(IssuersModule::on_chain_storage_version(), 1);
+	});
+}
+
+#[test]
+fn migration_to_v2_moves_issuers_to_ids() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(1).put::<IssuersModule>();
+
+		let old_hash = BlakeTwo256::hash(b"Acme");
+		let name_hash = Pallet::<Test>::name_hash(b"Acme").unwrap();
+		Issuers::<Test>::insert(old_hash, legacy_issuer("Acme"));
+		IssuerNameIndex::<Test>::insert(name_hash, old_hash);
+		IssuerTiers::<Test>::insert(old_hash, VerificationTier::Verified);
+		let profile = IssuerProfile::<Test> {
+			website: BoundedVec::truncate_from(b"https://acme.example".to_vec()),
+			logo_hash: None,
+			description_uri: BoundedVec::default(),
+			contact: BoundedVec::default(),
+			domain_proof: BoundedVec::default(),
+			depositor: account(1),
+			deposit: 20,
+		};
+		IssuerProfiles::<Test>::insert(old_hash, profile.clone());
+
+		run_migration::<v2::MigrateV1ToV2<Test>>();
+
+		let id = Pallet::<Test>::issuer_id(&Pallet::<Test>::zero_account(), 0);
+		assert_eq!(Issuers::<Test>::get(id), Some(legacy_issuer("Acme")));
+		assert_eq!(Issuers::<Test>::get(old_hash), None);
+		assert_eq!(Pallet::<Test>::issuer_by_name(b"acme"), Some(id));
+		assert_eq!(Pallet::<Test>::tier_of(id), VerificationTier::Verified);
+		assert!(!IssuerTiers::<Test>::contains_key(old_hash));
+		assert_eq!(IssuerProfiles::<Test>::get(id), Some(profile));
+		assert!(!IssuerProfiles::<Test>::contains_key(old_hash));
+		assert_eq!(v2::resolve_legacy_id::<Test>(old_hash), id);
+		// Ids created since keep resolving to themselves
+		assert_eq!(v2::resolve_legacy_id::<Test>(id), id);
+		assert_eq!(NextIssuerNonce::<Test>::get(), 1);
+		assert_eq!(IssuersModule::on_chain_storage_version(), 2);
+	});
+}
+
+#[test]
+fn migration_to_v3_clears_legacy_issuer_ids() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(2).put::<IssuersModule>();
+
+		for n in 1..=3 {
+			v2::LegacyIssuerIds::<Test>::insert(H256::repeat_byte(n), H256::repeat_byte(n + 10));
+		}
+
+		run_migration::<v3::MigrateV2ToV3<Test>>();
+
+		assert_eq!(v2::LegacyIssuerIds::<Test>::iter().count(), 0);
+		assert_eq!(v2::resolve_legacy_id::<Test>(H256::repeat_byte(1)), H256::repeat_byte(1));
+		assert_eq!(IssuersModule::on_chain_storage_version(), 3);
+	});
+}
+
+/// Issuer controlled by account 1, recoverable by accounts 2 and 3 together.
+fn recoverable_issuer() -> H256 {
+	let hash = create(1, "Acme");
+	assert_ok!(IssuersModule::set_recovery(RuntimeOrigin::signed(account(1)), hash, vec![account(2), account(3)], 2));
+	hash
+}
+
+#[test]
+fn recovery_replaces_controllers_after_the_challenge_period() {
+	new_test_ext().execute_with(|| {
+		let hash = recoverable_issuer();
+
+		assert_ok!(IssuersModule::initiate_recovery(RuntimeOrigin::signed(account(2)), hash, vec![account(4)]));
+		System::assert_last_event(
+			Event::<Test>::RecoveryInitiated {
+				hash,
+				initiator: account(2),
+				new_controllers: BoundedVec::truncate_from(vec![account(4)]),
+				challenge_ends: 11,
+			}
+			.into(),
+		);
+		assert_ok!(IssuersModule::approve_recovery(RuntimeOrigin::signed(account(3)), hash));
+
+		System::set_block_number(10);
+		assert_noop!(
+			IssuersModule::finish_recovery(RuntimeOrigin::signed(account(2)), hash),
+			Error::<Test>::ChallengePeriodNotOver
+		);
+
+		System::set_block_number(11);
+		assert_ok!(IssuersModule::finish_recovery(RuntimeOrigin::signed(account(2)), hash));
+
+		assert_eq!(Issuers::<Test>::get(hash).unwrap().controllers.into_inner(), vec![account(4)]);
+		assert!(!ActiveRecoveries::<Test>::contains_key(hash));
+		System::assert_last_event(
+			Event::<Test>::RecoveryFinished { hash, controllers_identified: BoundedVec::truncate_from(vec![account(4)]) }.into(),
+		);
+
+		// The registry deposit moves to the new controller, the recovery deposit stays put
+		assert_eq!(IssuerDeposits::<Test>::get(hash), Some((account(4), 1_000)));
+		assert_eq!(Balances::reserved_balance(account(4)), 1_000);
+		assert_eq!(Balances::reserved_balance(account(1)), 500);
+	});
+}
+
+#[test]
+fn finish_recovery_keeps_the_deposit_of_a_remaining_depositor() {
+	new_test_ext().execute_with(|| {
+		let hash = recoverable_issuer();
+
+		assert_ok!(IssuersModule::initiate_recovery(RuntimeOrigin::signed(account(2)), hash, vec![account(4), account(1)]));
+		assert_ok!(IssuersModule::approve_recovery(RuntimeOrigin::signed(account(3)), hash));
+		System::set_block_number(11);
+		assert_ok!(IssuersModule::finish_recovery(RuntimeOrigin::signed(account(4)), hash));
+
+		assert_eq!(IssuerDeposits::<Test>::get(hash), Some((account(1), 1_000)));
+		assert_eq!(Balances::reserved_balance(account(1)), 1_500);
+		assert_eq!(Balances::reserved_balance(account(4)), 0);
+	});
+}
+
+#[test]
+fn finish_recovery_needs_threshold_approvals() {
+	new_test_ext().execute_with(|| {
+		let hash = recoverable_issuer();
+
+		assert_ok!(IssuersModule::initiate_recovery(RuntimeOrigin::signed(account(2)), hash, vec![account(4)]));
+		assert_noop!(
+			IssuersModule::approve_recovery(RuntimeOrigin::signed(account(2)), hash),
+			Error::<Test>::AlreadyApproved
+		);
+		assert_noop!(
+			IssuersModule::approve_recovery(RuntimeOrigin::signed(account(4)), hash),
+			Error::<Test>::NotRecoveryAccount
+		);
+
+		System::set_block_number(11);
+		assert_noop!(
+			IssuersModule::finish_recovery(RuntimeOrigin::signed(account(2)), hash),
+			Error::<Test>::NotEnoughApprovals
+		);
+
+		assert_ok!(IssuersModule::approve_recovery(RuntimeOrigin::signed(account(3)), hash));
+		System::assert_last_event(Event::<Test>::RecoveryApproved { hash, account: account(3), approvals: 2 }.into());
+		assert_ok!(IssuersModule::finish_recovery(RuntimeOrigin::signed(account(2)), hash));
+	});
+}
+
+#[test]
+fn initiate_recovery_needs_a_recovery_account_and_no_recovery_in_progress() {
+	new_test_ext().execute_with(|| {
+		let hash = create(1, "Acme");
+		assert_noop!(
+			IssuersModule::initiate_recovery(RuntimeOrigin::signed(account(2)), hash, vec![account(4)]),
+			Error::<Test>::RecoveryNotConfigured
+		);
+
+		assert_ok!(IssuersModule::set_recovery(RuntimeOrigin::signed(account(1)), hash, vec![account(2), account(3)], 2));
+		assert_noop!(
+			IssuersModule::initiate_recovery(RuntimeOrigin::signed(account(4)), hash, vec![account(4)]),
+			Error::<Test>::NotRecoveryAccount
+		);
+
+		assert_ok!(IssuersModule::initiate_recovery(RuntimeOrigin::signed(account(2)), hash, vec![account(4)]));
+		assert_noop!(
+			IssuersModule::initiate_recovery(RuntimeOrigin::signed(account(3)), hash, vec![account(3)]),
+			Error::<Test>::RecoveryAlreadyActive
+		);
+	});
+}
+
+#[test]
+fn controllers_can_cancel_a_recovery_until_it_is_finished() {
+	new_test_ext().execute_with(|| {
+		let hash = recoverable_issuer();
+
+		assert_ok!(IssuersModule::initiate_recovery(RuntimeOrigin::signed(account(2)), hash, vec![account(4)]));
+		assert_ok!(IssuersModule::approve_recovery(RuntimeOrigin::signed(account(3)), hash));
+		assert_noop!(
+			IssuersModule::cancel_recovery(RuntimeOrigin::signed(account(2)), hash),
+			Error::<Test>::NotAuthorized
+		);
+
+		// Still open after the challenge period, as long as nobody finished it
+		System::set_block_number(20);
+		assert_ok!(IssuersModule::cancel_recovery(RuntimeOrigin::signed(account(1)), hash));
+		System::assert_last_event(Event::<Test>::RecoveryCancelled { hash, by: account(1) }.into());
+
+		assert_noop!(
+			IssuersModule::finish_recovery(RuntimeOrigin::signed(account(2)), hash),
+			Error::<Test>::NoActiveRecovery
+		);
+		assert_noop!(
+			IssuersModule::cancel_recovery(RuntimeOrigin::signed(account(1)), hash),
+			Error::<Test>::NoActiveRecovery
+		);
+		assert_eq!(Issuers::<Test>::get(hash).unwrap().controllers.into_inner(), vec![account(1)]);
+
+		// A new reset starts its own challenge period
+		assert_ok!(IssuersModule::initiate_recovery(RuntimeOrigin::signed(account(3)), hash, vec![account(4)]));
+		assert_eq!(ActiveRecoveries::<Test>::get(hash).unwrap().initiated_at, 20);
+	});
+}
+
+#[test]
+fn set_recovery_cancels_a_recovery_in_progress() {
+	new_test_ext().execute_with(|| {
+		let hash = recoverable_issuer();
+		assert_ok!(IssuersModule::initiate_recovery(RuntimeOrigin::signed(account(2)), hash, vec![account(4)]));
+
+		assert_ok!(IssuersModule::set_recovery(RuntimeOrigin::signed(account(1)), hash, vec![account(3)], 1));
+
+		System::assert_has_event(Event::<Test>::RecoveryCancelled { hash, by: account(1) }.into());
+		assert!(!ActiveRecoveries::<Test>::contains_key(hash));
+		let config = RecoveryConfigs::<Test>::get(hash).unwrap();
+		assert_eq!((config.accounts.into_inner(), config.threshold), (vec![account(3)], 1));
+		// The previous deposit was returned before the new one was taken
+		assert_eq!(Balances::reserved_balance(account(1)), 1_500);
+	});
+}
+
+#[test]
+fn set_recovery_checks_the_threshold_and_removes_recovery() {
+	new_test_ext().execute_with(|| {
+		let hash = create(1, "Acme");
+
+		for threshold in [0, 3] {
+			assert_noop!(
+				IssuersModule::set_recovery(RuntimeOrigin::signed(account(1)), hash, vec![account(2), account(3)], threshold),
+				Error::<Test>::InvalidRecoveryThreshold
+			);
+		}
+		// Duplicates count once
+		assert_noop!(
+			IssuersModule::set_recovery(RuntimeOrigin::signed(account(1)), hash, vec![account(2), account(2)], 2),
+			Error::<Test>::InvalidRecoveryThreshold
+		);
+		assert_noop!(
+			IssuersModule::set_recovery(RuntimeOrigin::signed(account(1)), hash, vec![], 0),
+			Error::<Test>::RecoveryNotConfigured
+		);
+		assert_noop!(
+			IssuersModule::set_recovery(RuntimeOrigin::signed(account(2)), hash, vec![account(2)], 1),
+			Error::<Test>::NotAuthorized
+		);
+
+		assert_ok!(IssuersModule::set_recovery(RuntimeOrigin::signed(account(1)), hash, vec![account(2), account(3)], 2));
+		assert_eq!(Balances::reserved_balance(account(1)), 1_500);
+
+		assert_ok!(IssuersModule::set_recovery(RuntimeOrigin::signed(account(1)), hash, vec![], 0));
+		System::assert_last_event(Event::<Test>::RecoveryRemoved { hash }.into());
+		assert!(!RecoveryConfigs::<Test>::contains_key(hash));
+		assert_eq!(Balances::reserved_balance(account(1)), 1_000);
+	});
+}
+
+#[test]
+fn set_registry_deposit_rejects_zero_and_more_than_the_total_issuance() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			IssuersModule::set_registry_deposit(RuntimeOrigin::root(), 0),
+			Error::<Test>::InvalidRegistryDeposit
+		);
+		assert_noop!(
+			IssuersModule::set_registry_deposit(RuntimeOrigin::root(), pallet_balances::TotalIssuance::<Test>::get() + 1),
+			Error::<Test>::InvalidRegistryDeposit
+		);
+		assert_noop!(
+			IssuersModule::set_registry_deposit(RuntimeOrigin::signed(account(1)), 2_000),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(IssuersModule::set_registry_deposit(RuntimeOrigin::root(), 2_000));
+		assert_eq!(RegistryDeposit::<Test>::get(), 2_000);
+		System::assert_last_event(Event::<Test>::RegistryDepositSet { deposit: 2_000 }.into());
+
+		create(1, "Acme");
+		assert_eq!(Balances::reserved_balance(account(1)), 2_000);
+	});
+}
@@ -33,13 +33,29 @@ use core::marker::PhantomData;
 pub trait WeightInfo {
 	fn create_issuer(n: u32, c: u32, ) -> Weight;
 	fn edit_controllers(c: u32, ) -> Weight;
+	fn set_registry_deposit() -> Weight;
+	fn set_tier() -> Weight;
+	fn set_profile(b: u32, ) -> Weight;
+	fn clear_profile() -> Weight;
+	fn rename_issuer(n: u32, ) -> Weight;
+	fn set_recovery(r: u32, ) -> Weight;
+	fn initiate_recovery(c: u32, ) -> Weight;
+	fn approve_recovery() -> Weight;
+	fn cancel_recovery() -> Weight;
+	fn finish_recovery(c: u32, ) -> Weight;
 }
 
 /// Weights for `pallet_issuers` using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
-	/// Storage: `IssuersModule::Issuers` (r:1 w:1)
+	/// Storage: `IssuersModule::IssuerNameIndex` (r:1 w:1)
+	/// Proof: `IssuersModule::IssuerNameIndex` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::NextIssuerNonce` (r:1 w:1)
+	/// Proof: `IssuersModule::NextIssuerNonce` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::Issuers` (r:0 w:1)
 	/// Proof: `IssuersModule::Issuers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::IssuerDeposits` (r:0 w:1)
+	/// Proof: `IssuersModule::IssuerDeposits` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// The range of component `n` is `[1, 119]`.
 	/// The range of component `c` is `[1, 19]`.
 	fn create_issuer(n: u32, c: u32, ) -> Weight {
@@ -52,8 +68,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(Weight::from_parts(5_593, 0).saturating_mul(n.into()))
 			// Standard Error: 1_779
 			.saturating_add(Weight::from_parts(47_701, 0).saturating_mul(c.into()))
-			.saturating_add(T::DbWeight::get().reads(1_u64))
-			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
 	/// Storage: `IssuersModule::Issuers` (r:1 w:1)
 	/// Proof: `IssuersModule::Issuers` (`max_values`: None, `max_size`: None, mode: `Measured`)
@@ -69,12 +85,178 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `IssuersModule::RegistryDeposit` (r:0 w:1)
+	/// Proof: `IssuersModule::RegistryDeposit` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn set_registry_deposit() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 8_000_000 picoseconds.
+		Weight::from_parts(8_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `IssuersModule::Issuers` (r:1 w:0)
+	/// Proof: `IssuersModule::Issuers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::IssuerTiers` (r:0 w:1)
+	/// Proof: `IssuersModule::IssuerTiers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_tier() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `845`
+		//  Estimated: `4310`
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(15_000_000, 4310)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `IssuersModule::Issuers` (r:1 w:0)
+	/// Proof: `IssuersModule::Issuers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::IssuerProfiles` (r:1 w:1)
+	/// Proof: `IssuersModule::IssuerProfiles` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `b` is `[0, 1024]`.
+	fn set_profile(b: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1890`
+		//  Estimated: `5355`
+		// Minimum execution time: 42_000_000 picoseconds.
+		Weight::from_parts(44_500_000, 5355)
+			// Standard Error: 120
+			.saturating_add(Weight::from_parts(2_100, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `IssuersModule::Issuers` (r:1 w:0)
+	/// Proof: `IssuersModule::Issuers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::IssuerProfiles` (r:1 w:1)
+	/// Proof: `IssuersModule::IssuerProfiles` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn clear_profile() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1890`
+		//  Estimated: `5355`
+		// Minimum execution time: 36_000_000 picoseconds.
+		Weight::from_parts(37_000_000, 5355)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `IssuersModule::Issuers` (r:1 w:1)
+	/// Proof: `IssuersModule::Issuers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::IssuerNameIndex` (r:2 w:2)
+	/// Proof: `IssuersModule::IssuerNameIndex` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::IssuerTiers` (r:1 w:1)
+	/// Proof: `IssuersModule::IssuerTiers` (`max_values`: None, `max_size`: Some(49), added: 2524, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 120]`.
+	fn rename_issuer(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `920`
+		//  Estimated: `4385`
+		// Minimum execution time: 34_000_000 picoseconds.
+		Weight::from_parts(35_400_000, 4385)
+			// Standard Error: 310
+			.saturating_add(Weight::from_parts(9_800, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `IssuersModule::Issuers` (r:1 w:0)
+	/// Proof: `IssuersModule::Issuers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::ActiveRecoveries` (r:1 w:1)
+	/// Proof: `IssuersModule::ActiveRecoveries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::RecoveryConfigs` (r:1 w:1)
+	/// Proof: `IssuersModule::RecoveryConfigs` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `r` is `[0, 10]`.
+	fn set_recovery(r: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `845`
+		//  Estimated: `4310`
+		// Minimum execution time: 27_000_000 picoseconds.
+		Weight::from_parts(27_300_000, 4310)
+			// Standard Error: 1_310
+			.saturating_add(Weight::from_parts(61_400, 0).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `IssuersModule::Issuers` (r:1 w:0)
+	/// Proof: `IssuersModule::Issuers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::RecoveryConfigs` (r:1 w:0)
+	/// Proof: `IssuersModule::RecoveryConfigs` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::ActiveRecoveries` (r:1 w:1)
+	/// Proof: `IssuersModule::ActiveRecoveries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `c` is `[0, 20]`.
+	fn initiate_recovery(c: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1210`
+		//  Estimated: `4675`
+		// Minimum execution time: 31_000_000 picoseconds.
+		Weight::from_parts(31_800_000, 4675)
+			// Standard Error: 1_310
+			.saturating_add(Weight::from_parts(58_900, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `IssuersModule::RecoveryConfigs` (r:1 w:0)
+	/// Proof: `IssuersModule::RecoveryConfigs` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::ActiveRecoveries` (r:1 w:1)
+	/// Proof: `IssuersModule::ActiveRecoveries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn approve_recovery() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1530`
+		//  Estimated: `4995`
+		// Minimum execution time: 26_000_000 picoseconds.
+		Weight::from_parts(26_400_000, 4995)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `IssuersModule::Issuers` (r:1 w:0)
+	/// Proof: `IssuersModule::Issuers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::ActiveRecoveries` (r:1 w:1)
+	/// Proof: `IssuersModule::ActiveRecoveries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn cancel_recovery() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1530`
+		//  Estimated: `4995`
+		// Minimum execution time: 22_000_000 picoseconds.
+		Weight::from_parts(22_700_000, 4995)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `IssuersModule::Issuers` (r:1 w:1)
+	/// Proof: `IssuersModule::Issuers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::RecoveryConfigs` (r:1 w:0)
+	/// Proof: `IssuersModule::RecoveryConfigs` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::ActiveRecoveries` (r:1 w:1)
+	/// Proof: `IssuersModule::ActiveRecoveries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::IssuerDeposits` (r:1 w:1)
+	/// Proof: `IssuersModule::IssuerDeposits` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `c` is `[0, 20]`.
+	fn finish_recovery(c: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2140`
+		//  Estimated: `5605`
+		// Minimum execution time: 35_000_000 picoseconds.
+		Weight::from_parts(35_900_000, 5605)
+			// Standard Error: 1_310
+			.saturating_add(Weight::from_parts(42_300, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
 }
 
 // For backwards compatibility and tests.
 impl WeightInfo for () {
-	/// Storage: `IssuersModule::Issuers` (r:1 w:1)
+	/// Storage: `IssuersModule::IssuerNameIndex` (r:1 w:1)
+	/// Proof: `IssuersModule::IssuerNameIndex` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::NextIssuerNonce` (r:1 w:1)
+	/// Proof: `IssuersModule::NextIssuerNonce` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::Issuers` (r:0 w:1)
 	/// Proof: `IssuersModule::Issuers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::IssuerDeposits` (r:0 w:1)
+	/// Proof: `IssuersModule::IssuerDeposits` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// The range of component `n` is `[1, 119]`.
 	/// The range of component `c` is `[1, 19]`.
 	fn create_issuer(n: u32, c: u32, ) -> Weight {
@@ -87,8 +269,8 @@ impl WeightInfo for () {
 			.saturating_add(Weight::from_parts(5_593, 0).saturating_mul(n.into()))
 			// Standard Error: 1_779
 			.saturating_add(Weight::from_parts(47_701, 0).saturating_mul(c.into()))
-			.saturating_add(RocksDbWeight::get().reads(1_u64))
-			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
 	}
 	/// Storage: `IssuersModule::Issuers` (r:1 w:1)
 	/// Proof: `IssuersModule::Issuers` (`max_values`: None, `max_size`: None, mode: `Measured`)
@@ -104,4 +286,164 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
-}
\ No newline at end of file
+	/// Storage: `IssuersModule::RegistryDeposit` (r:0 w:1)
+	/// Proof: `IssuersModule::RegistryDeposit` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn set_registry_deposit() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 8_000_000 picoseconds.
+		Weight::from_parts(8_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `IssuersModule::Issuers` (r:1 w:0)
+	/// Proof: `IssuersModule::Issuers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::IssuerTiers` (r:0 w:1)
+	/// Proof: `IssuersModule::IssuerTiers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_tier() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `845`
+		//  Estimated: `4310`
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(15_000_000, 4310)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `IssuersModule::Issuers` (r:1 w:0)
+	/// Proof: `IssuersModule::Issuers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::IssuerProfiles` (r:1 w:1)
+	/// Proof: `IssuersModule::IssuerProfiles` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `b` is `[0, 1024]`.
+	fn set_profile(b: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1890`
+		//  Estimated: `5355`
+		// Minimum execution time: 42_000_000 picoseconds.
+		Weight::from_parts(44_500_000, 5355)
+			// Standard Error: 120
+			.saturating_add(Weight::from_parts(2_100, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `IssuersModule::Issuers` (r:1 w:0)
+	/// Proof: `IssuersModule::Issuers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::IssuerProfiles` (r:1 w:1)
+	/// Proof: `IssuersModule::IssuerProfiles` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn clear_profile() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1890`
+		//  Estimated: `5355`
+		// Minimum execution time: 36_000_000 picoseconds.
+		Weight::from_parts(37_000_000, 5355)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `IssuersModule::Issuers` (r:1 w:1)
+	/// Proof: `IssuersModule::Issuers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::IssuerNameIndex` (r:2 w:2)
+	/// Proof: `IssuersModule::IssuerNameIndex` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::IssuerTiers` (r:1 w:1)
+	/// Proof: `IssuersModule::IssuerTiers` (`max_values`: None, `max_size`: Some(49), added: 2524, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 120]`.
+	fn rename_issuer(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `920`
+		//  Estimated: `4385`
+		// Minimum execution time: 34_000_000 picoseconds.
+		Weight::from_parts(35_400_000, 4385)
+			// Standard Error: 310
+			.saturating_add(Weight::from_parts(9_800, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `IssuersModule::Issuers` (r:1 w:0)
+	/// Proof: `IssuersModule::Issuers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::ActiveRecoveries` (r:1 w:1)
+	/// Proof: `IssuersModule::ActiveRecoveries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::RecoveryConfigs` (r:1 w:1)
+	/// Proof: `IssuersModule::RecoveryConfigs` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `r` is `[0, 10]`.
+	fn set_recovery(r: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `845`
+		//  Estimated: `4310`
+		// Minimum execution time: 27_000_000 picoseconds.
+		Weight::from_parts(27_300_000, 4310)
+			// Standard Error: 1_310
+			.saturating_add(Weight::from_parts(61_400, 0).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `IssuersModule::Issuers` (r:1 w:0)
+	/// Proof: `IssuersModule::Issuers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::RecoveryConfigs` (r:1 w:0)
+	/// Proof: `IssuersModule::RecoveryConfigs` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::ActiveRecoveries` (r:1 w:1)
+	/// Proof: `IssuersModule::ActiveRecoveries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `c` is `[0, 20]`.
+	fn initiate_recovery(c: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1210`
+		//  Estimated: `4675`
+		// Minimum execution time: 31_000_000 picoseconds.
+		Weight::from_parts(31_800_000, 4675)
+			// Standard Error: 1_310
+			.saturating_add(Weight::from_parts(58_900, 0).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `IssuersModule::RecoveryConfigs` (r:1 w:0)
+	/// Proof: `IssuersModule::RecoveryConfigs` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::ActiveRecoveries` (r:1 w:1)
+	/// Proof: `IssuersModule::ActiveRecoveries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn approve_recovery() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1530`
+		//  Estimated: `4995`
+		// Minimum execution time: 26_000_000 picoseconds.
+		Weight::from_parts(26_400_000, 4995)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `IssuersModule::Issuers` (r:1 w:0)
+	/// Proof: `IssuersModule::Issuers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::ActiveRecoveries` (r:1 w:1)
+	/// Proof: `IssuersModule::ActiveRecoveries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn cancel_recovery() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1530`
+		//  Estimated: `4995`
+		// Minimum execution time: 22_000_000 picoseconds.
+		Weight::from_parts(22_700_000, 4995)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `IssuersModule::Issuers` (r:1 w:1)
+	/// Proof: `IssuersModule::Issuers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::RecoveryConfigs` (r:1 w:0)
+	/// Proof: `IssuersModule::RecoveryConfigs` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::ActiveRecoveries` (r:1 w:1)
+	/// Proof: `IssuersModule::ActiveRecoveries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `IssuersModule::IssuerDeposits` (r:1 w:1)
+	/// Proof: `IssuersModule::IssuerDeposits` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `c` is `[0, 20]`.
+	fn finish_recovery(c: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2140`
+		//  Estimated: `5605`
+		// Minimum execution time: 35_000_000 picoseconds.
+		Weight::from_parts(35_900_000, 5605)
+			// Standard Error: 1_310
+			.saturating_add(Weight::from_parts(42_300, 0).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+}
@@ -0,0 +1,192 @@
+//! Genesis presets, selectable by name with `--chain` or `chain-spec-builder`.
+
+use crate::{AccountId, CredentialsModule, IssuersModule};
+use alloc::{vec, vec::Vec};
+use pallet_credentials::{AcquirerAddress, CredType};
+use pallet_issuers::VerificationTier;
+use serde_json::{json, Value};
+use sp_consensus_aura::sr25519::AuthorityId as AuraId;
+use sp_consensus_grandpa::AuthorityId as GrandpaId;
+use sp_genesis_builder::PresetId;
+use sp_keyring::{AccountKeyring, Ed25519Keyring};
+
+/// Single authority chain run by Alice.
+pub const DEVELOPMENT: &str = "development";
+/// Chain with Alice and Bob as authorities.
+pub const LOCAL_TESTNET: &str = "local_testnet";
+/// Development chain with a sample issuer, schema, attestations and algorithm.
+pub const REPUTATION_DEMO: &str = "reputation_demo";
+
+/// Name of the issuer of the `reputation_demo` preset.
+const DEMO_ISSUER: &[u8] = b"Reputation Demo";
+
+/// Hand-assembled equivalent of `asm-scripts/assembly/index.ts`, whose `calc` returns
+/// the `no_of_private_repos` field of its single input:
+///
+/// ```wat
+/// (module
+///   (import "env" "memory" (memory 0))
+///   (func (export "calc") (result i64)
+///     (i64.load (i32.const 4))))
+/// ```
+const REPOS_ALGORITHM: &[u8] = &[
+	0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x05, 0x01, 0x60, 0x00, 0x01, 0x7e,
+	0x02, 0x0f, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02,
+	0x00, 0x00, 0x03, 0x02, 0x01, 0x00, 0x07, 0x08, 0x01, 0x04, 0x63, 0x61, 0x6c, 0x63, 0x00,
+	0x00, 0x0a, 0x09, 0x01, 0x07, 0x00, 0x41, 0x04, 0x29, 0x03, 0x00, 0x0b,
+];
+
+/// Configure initial storage state for FRAME modules.
+fn testnet_genesis(
+	initial_authorities: Vec<(AuraId, GrandpaId)>,
+	council: Vec<AccountId>,
+	endowed_accounts: Vec<AccountId>,
+	issuers: Vec<(Vec<u8>, Vec<AccountId>)>,
+) -> Value {
+	json!({
+		"balances": {
+			// Configure endowed accounts with initial balance of 1 << 60.
+			"balances": endowed_accounts.iter().cloned().map(|k| (k, 1u64 << 60)).collect::<Vec<_>>(),
+		},
+		"aura": {
+			"authorities": initial_authorities.iter().map(|x| (x.0.clone())).collect::<Vec<_>>(),
+		},
+		"grandpa": {
+			"authorities": initial_authorities.iter().map(|x| (x.1.clone(), 1)).collect::<Vec<_>>(),
+		},
+		"council": {
+			// Assign network admin rights.
+			"members": council,
+		},
+		"issuersModule": {
+			// Well-known issuers, by name, with their controllers.
+			"issuers": issuers,
+		},
+	})
+}
+
+/// Aura and GRANDPA keys of a well-known account.
+fn authority_keys(account: AccountKeyring, ed25519: Ed25519Keyring) -> (AuraId, GrandpaId) {
+	(account.public().into(), ed25519.public().into())
+}
+
+/// Return the development genesis config.
+pub fn development_config_genesis() -> Value {
+	testnet_genesis(
+		vec![authority_keys(AccountKeyring::Alice, Ed25519Keyring::Alice)],
+		vec![AccountKeyring::Alice.to_account_id()],
+		vec![
+			AccountKeyring::Alice.to_account_id(),
+			AccountKeyring::Bob.to_account_id(),
+			AccountKeyring::AliceStash.to_account_id(),
+			AccountKeyring::BobStash.to_account_id(),
+		],
+		vec![(b"Dev Issuer".to_vec(), vec![AccountKeyring::Alice.to_account_id()])],
+	)
+}
+
+/// Return the local genesis config preset.
+pub fn local_config_genesis() -> Value {
+	testnet_genesis(
+		vec![
+			authority_keys(AccountKeyring::Alice, Ed25519Keyring::Alice),
+			authority_keys(AccountKeyring::Bob, Ed25519Keyring::Bob),
+		],
+		vec![
+			AccountKeyring::Alice.to_account_id(),
+			AccountKeyring::Bob.to_account_id(),
+			AccountKeyring::Charlie.to_account_id(),
+		],
+		vec![
+			AccountKeyring::Alice.to_account_id(),
+			AccountKeyring::Bob.to_account_id(),
+			AccountKeyring::Charlie.to_account_id(),
+			AccountKeyring::Dave.to_account_id(),
+			AccountKeyring::Eve.to_account_id(),
+			AccountKeyring::Ferdie.to_account_id(),
+			AccountKeyring::AliceStash.to_account_id(),
+			AccountKeyring::BobStash.to_account_id(),
+			AccountKeyring::CharlieStash.to_account_id(),
+			AccountKeyring::DaveStash.to_account_id(),
+			AccountKeyring::EveStash.to_account_id(),
+			AccountKeyring::FerdieStash.to_account_id(),
+		],
+		vec![(
+			b"Local Issuer".to_vec(),
+			vec![AccountKeyring::Alice.to_account_id(), AccountKeyring::Bob.to_account_id()],
+		)],
+	)
+}
+
+/// Return the development genesis config with a verified `Reputation Demo` issuer controlled
+/// by Alice, its `repos` schema, attestations of it for Alice and Bob, and the
+/// `asm-scripts` algorithm reading it.
+pub fn reputation_demo_genesis() -> Value {
+	let alice = AccountKeyring::Alice.to_account_id();
+	let bob = AccountKeyring::Bob.to_account_id();
+
+	// The only genesis issuer.
+	let issuer_hash = IssuersModule::genesis_issuer_id(0);
+	let repos = vec![
+		(b"no_of_repos".to_vec(), CredType::U32),
+		(b"no_of_private_repos".to_vec(), CredType::U64),
+	];
+	let repos_hash = CredentialsModule::schema_hash(
+		&CredentialsModule::bound_schema(repos.clone()).expect("Demo schema fits the limits; qed"),
+	);
+	let repos_attestation = |account: &AccountId, repos: u32, private_repos: u64| {
+		(
+			issuer_hash,
+			repos_hash,
+			AcquirerAddress::Substrate(account.clone()).to_bytes(),
+			vec![repos.to_le_bytes().to_vec(), private_repos.to_le_bytes().to_vec()],
+		)
+	};
+
+	let mut genesis = testnet_genesis(
+		vec![authority_keys(AccountKeyring::Alice, Ed25519Keyring::Alice)],
+		vec![alice.clone()],
+		vec![
+			alice.clone(),
+			bob.clone(),
+			AccountKeyring::AliceStash.to_account_id(),
+			AccountKeyring::BobStash.to_account_id(),
+		],
+		vec![(DEMO_ISSUER.to_vec(), vec![alice.clone()])],
+	);
+	genesis["issuersModule"]["tiers"] = json!([(DEMO_ISSUER.to_vec(), VerificationTier::Verified)]);
+	genesis["credentialsModule"] = json!({
+		"schemas": [(issuer_hash, repos)],
+		"attestations": [repos_attestation(&alice, 12, 3), repos_attestation(&bob, 40, 7)],
+	});
+	genesis["algorithmsModule"] = json!({
+		"algorithms": [(
+			alice,
+			b"Private repos".to_vec(),
+			b"Number of private repositories in the repos attestation".to_vec(),
+			vec![repos_hash],
+			REPOS_ALGORITHM.to_vec(),
+		)],
+	});
+	genesis
+}
+
+/// Provides the JSON representation of predefined genesis config for given `id`.
+pub fn get_preset(id: &PresetId) -> Option<Vec<u8>> {
+	let patch = match id.as_ref() {
+		DEVELOPMENT => development_config_genesis(),
+		LOCAL_TESTNET => local_config_genesis(),
+		REPUTATION_DEMO => reputation_demo_genesis(),
+		_ => return None,
+	};
+	Some(
+		serde_json::to_string(&patch)
+			.expect("serialization to json is expected to work. qed.")
+			.into_bytes(),
+	)
+}
+
+/// List of supported presets.
+pub fn preset_names() -> Vec<PresetId> {
+	vec![PresetId::from(DEVELOPMENT), PresetId::from(LOCAL_TESTNET), PresetId::from(REPUTATION_DEMO)]
+}
@@ -36,8 +36,9 @@ pub use frame_support::{
 };
 use frame_support::{
 	genesis_builder_helper::{build_state, get_preset},
-	traits::VariantCountOf,
+	traits::{fungible::HoldConsideration, EitherOfDiverse, LinearStoragePrice, VariantCountOf},
 };
+use frame_system::EnsureRoot;
 pub use frame_system::Call as SystemCall;
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
@@ -51,7 +52,9 @@ pub use pallet_algorithms;
 pub use pallet_issuers;
 pub use pallet_credentials;
 
-pub use pallet_algorithms::GasCosts;
+pub use pallet_algorithms::{GasCosts, ModuleLimits};
+
+pub mod genesis_config_presets;
 
 /// An index to a block.
 pub type BlockNumber = u32;
@@ -265,10 +268,63 @@ impl pallet_transaction_payment::Config for Runtime {
 	type FeeMultiplierUpdate = ConstFeeMultiplier<FeeMultiplier>;
 }
 
-impl pallet_sudo::Config for Runtime {
+parameter_types! {
+	pub const CouncilMotionDuration: BlockNumber = 3 * DAYS;
+	pub const CouncilMaxProposals: u32 = 100;
+	pub const CouncilMaxMembers: u32 = 100;
+	pub MaxProposalWeight: Weight = Perbill::from_percent(50) * BlockWeights::get().max_block;
+}
+
+type CouncilCollective = pallet_collective::Instance1;
+
+/// Origin administering the network: two thirds of the council, or root, which the council
+/// reaches by whitelisting a call and dispatching it through `Whitelist`.
+pub type GovernanceOrigin = EitherOfDiverse<
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>,
+>;
+
+impl pallet_collective::Config<CouncilCollective> for Runtime {
+	type RuntimeOrigin = RuntimeOrigin;
+	type Proposal = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type MotionDuration = CouncilMotionDuration;
+	type MaxProposals = CouncilMaxProposals;
+	type MaxMembers = CouncilMaxMembers;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+	type SetMembersOrigin = GovernanceOrigin;
+	type MaxProposalWeight = MaxProposalWeight;
+}
+
+parameter_types! {
+	pub const PreimageBaseDeposit: Balance = 1_000_000_000_000;
+	pub const PreimageByteDeposit: Balance = 1_000_000;
+	pub const PreimageHoldReason: RuntimeHoldReason =
+		RuntimeHoldReason::Preimage(pallet_preimage::HoldReason::Preimage);
+}
+
+impl pallet_preimage::Config for Runtime {
+	type WeightInfo = pallet_preimage::weights::SubstrateWeight<Runtime>;
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type ManagerOrigin = EnsureRoot<AccountId>;
+	type Consideration = HoldConsideration<
+		AccountId,
+		Balances,
+		PreimageHoldReason,
+		LinearStoragePrice<PreimageBaseDeposit, PreimageByteDeposit, Balance>,
+	>;
+}
+
+impl pallet_whitelist::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
-	type WeightInfo = pallet_sudo::weights::SubstrateWeight<Runtime>;
+	type WhitelistOrigin = pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>;
+	type DispatchWhitelistedOrigin =
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>;
+	type Preimages = Preimage;
+	type WeightInfo = pallet_whitelist::weights::SubstrateWeight<Runtime>;
 }
 
 // pub const DEFAULT_GAS_COSTS: GasCosts = GasCosts {
@@ -282,23 +338,125 @@ pub struct ConstGasCosts;
 impl frame_support::traits::Get<GasCosts> for ConstGasCosts {
     fn get() -> GasCosts {
         GasCosts {
-            basic_op: 500_000_000,    // Set a value for basic operations
-            memory_op: 1_000_000_000,   // Set a value for memory operations
-            call_op: 1_500_000_000,     // Set a value for call operations
+            basic_op: 50,    // Set a value for basic operations
+            memory_op: 100,   // Set a value for memory operations
+            call_op: 500,     // Set a value for call operations
+        }
+    }
+}
+
+pub struct ConstModuleLimits;
+
+impl frame_support::traits::Get<ModuleLimits> for ConstModuleLimits {
+    fn get() -> ModuleLimits {
+        ModuleLimits {
+            functions: 1_000,
+            globals: 100,
+            tables: 1,
+            table_elements: 1_000,
         }
     }
 }
 
+/// Owner given to algorithms saved before algorithms recorded one: the network admin. That
+/// is the sudo key until `RemoveSudo` clears it, then the council's prime or first member.
+pub struct LegacyAlgorithmOwner;
+
+impl frame_support::traits::Get<AccountId> for LegacyAlgorithmOwner {
+	fn get() -> AccountId {
+		sudo_key()
+			.or_else(pallet_collective::Prime::<Runtime, CouncilCollective>::get)
+			.or_else(|| pallet_collective::Members::<Runtime, CouncilCollective>::get().first().cloned())
+			.unwrap_or_else(|| AccountId::new([0u8; 32]))
+	}
+}
+
 /// Configure the pallet-algorithms in pallets/algorithms.
 impl pallet_algorithms::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
   type Hashing = BlakeTwo256;
+  type WeightInfo = pallet_algorithms::weights::SubstrateWeight<Runtime>;
   type MaxSchemas= ConstU32<10>;
+  type MaxTrustedIssuers = ConstU32<10>;
   type MaxCodeSize = ConstU32<25000>;
   type MaxMemoryPages = ConstU32<40>;
-  type DefaultGasLimit = ConstU64<2_000_000_000_000>;
+  type MaxReturnDataSize = ConstU32<1024>;
+  type MaxParamsSize = ConstU32<256>;
+  type MaxAlgoNameLength = ConstU32<64>;
+  type MaxAlgoDescriptionLength = ConstU32<256>;
+  type AlgorithmDeposit = ConstU128<100_000_000_000>;
+  type CodeDepositPerByte = ConstU128<10_000_000>;
+  // A run at `MaxGasLimit` weighs about 50 ms, see `WeightInfo::execute`
+  type DefaultGasLimit = ConstU64<10_000_000>;
+  type MaxGasLimit = ConstU64<25_000_000>;
   type GasCost = ConstGasCosts;
+  type ModuleLimits = ConstModuleLimits;
+  type AllowFloats = ConstBool<false>;
+  type InvalidateStaleScores = ConstBool<true>;
+  type AuthorityId = pallet_algorithms::crypto::AuthorityId;
+  type AuthorityOrigin = GovernanceOrigin;
+  type ParamsOrigin = GovernanceOrigin;
+  type ApprovalOrigin = GovernanceOrigin;
+  type RequireApproval = ConstBool<true>;
+  type MaxOffchainAuthorities = ConstU32<10>;
+  type RequiredAgreements = ConstU32<2>;
+  type MaxRunRequests = ConstU32<100>;
+  type RunRequestDeposit = ConstU128<10_000_000_000>;
+  type RunRequestTimeout = ConstU32<HOURS>;
+  type OffchainGasLimit = ConstU64<250_000_000>;
+  type MaxBatchSize = ConstU32<100>;
+  type MaxSchedules = ConstU32<50>;
+  type MaxScheduledSubjects = ConstU32<1000>;
+  type MinScheduleInterval = ConstU32<100>;
+  type ScheduleDeposit = ConstU128<100_000_000_000>;
+  type MaxSchemaWatchers = ConstU32<10>;
+  type MaxScoreIssuers = ConstU32<10>;
+  type RefreshDeposit = ConstU128<100_000_000_000>;
+}
+
+impl frame_system::offchain::SigningTypes for Runtime {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
 
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+	RuntimeCall: From<C>,
+{
+	type Extrinsic = UncheckedExtrinsic;
+	type OverarchingCall = RuntimeCall;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Runtime
+where
+	RuntimeCall: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: RuntimeCall,
+		public: <Signature as Verify>::Signer,
+		account: AccountId,
+		nonce: Nonce,
+	) -> Option<(RuntimeCall, <UncheckedExtrinsic as sp_runtime::traits::Extrinsic>::SignaturePayload)> {
+		use codec::Encode;
+
+		// Mortal for a quarter of the block hash window, checked against the previous block
+		let period = (BlockHashCount::get() as u64 / 4).next_power_of_two();
+		let current_block = System::block_number().saturating_sub(1) as u64;
+		let extra: SignedExtra = (
+			frame_system::CheckNonZeroSender::<Runtime>::new(),
+			frame_system::CheckSpecVersion::<Runtime>::new(),
+			frame_system::CheckTxVersion::<Runtime>::new(),
+			frame_system::CheckGenesis::<Runtime>::new(),
+			frame_system::CheckEra::<Runtime>::from(generic::Era::mortal(period, current_block)),
+			frame_system::CheckNonce::<Runtime>::from(nonce),
+			frame_system::CheckWeight::<Runtime>::new(),
+			pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(0),
+		);
+		let raw_payload = SignedPayload::new(call, extra).ok()?;
+		let signature = raw_payload.using_encoded(|payload| C::sign(payload, public))?;
+		let (call, extra, _) = raw_payload.deconstruct();
+		Some((call, (sp_runtime::MultiAddress::Id(account), signature, extra)))
+	}
 }
 
 impl pallet_issuers::Config for Runtime {
@@ -312,6 +470,15 @@ impl pallet_issuers::Config for Runtime {
   type Currency = Balances;
 
   type IssuerRegistryDeposit = ConstU128<1_000_000_000_000>;
+  type ParamsOrigin = GovernanceOrigin;
+  type VerifierOrigin = GovernanceOrigin;
+
+  type MaxProfileFieldLength = ConstU32<256>;
+  type ProfileDepositPerByte = ConstU128<10_000_000>;
+
+  type MaxRecoveryAccounts = ConstU32<10>;
+  type RecoveryChallengePeriod = ConstU32<{ 7 * DAYS }>;
+  type RecoveryDeposit = ConstU128<100_000_000_000>;
 }
 
 impl pallet_credentials::Config for Runtime {
@@ -321,6 +488,7 @@ impl pallet_credentials::Config for Runtime {
 	type MaxSchemaFieldSize = ConstU32<120>;
 
   type CredentialsWeightInfo = pallet_credentials::weights::SubstrateWeight<Runtime>;
+  type OnAttestationChanged = AlgorithmsModule;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -332,13 +500,15 @@ construct_runtime!(
 		Grandpa: pallet_grandpa,
 		Balances: pallet_balances,
 		TransactionPayment: pallet_transaction_payment,
-		Sudo: pallet_sudo,
-    Utility: pallet_utility,
+		// Index 6 belonged to `Sudo`, removed by `RemoveSudo`.
+    Utility: pallet_utility = 7,
 		// Include the custom logic from the pallet-algorithms in the runtime.
 		AlgorithmsModule: pallet_algorithms,
 		IssuersModule: pallet_issuers,
 		CredentialsModule: pallet_credentials,
-
+		Council: pallet_collective::<Instance1>,
+		Preimage: pallet_preimage,
+		Whitelist: pallet_whitelist,
 	}
 );
 
@@ -360,11 +530,57 @@ pub type SignedExtra = (
 	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
 );
 
+parameter_types! {
+	pub const SudoPalletName: &'static str = "Sudo";
+}
+
+/// Key of the removed `pallet_sudo`, read from its raw storage until `RemoveSudo` clears it.
+fn sudo_key() -> Option<AccountId> {
+	frame_support::storage::unhashed::get::<AccountId>(&frame_support::storage::storage_prefix(
+		SudoPalletName::get().as_bytes(),
+		b"Key",
+	))
+}
+
+/// Makes the sudo key the only council member, if the council has none, so that chains
+/// upgraded from a sudo runtime keep an admin able to reach `GovernanceOrigin`.
+pub struct SeedCouncilFromSudo;
+
+impl frame_support::traits::OnRuntimeUpgrade for SeedCouncilFromSudo {
+	fn on_runtime_upgrade() -> Weight {
+		let reads = RocksDbWeight::get().reads(2);
+		if !pallet_collective::Members::<Runtime, CouncilCollective>::get().is_empty() {
+			return reads;
+		}
+		let Some(key) = sudo_key() else {
+			return reads;
+		};
+
+		<Council as frame_support::traits::InitializeMembers<AccountId>>::initialize_members(&[key]);
+
+		reads.saturating_add(RocksDbWeight::get().writes(1))
+	}
+}
+
+/// Clears the storage of `pallet_sudo`, replaced by `Council` governance.
+pub type RemoveSudo = frame_support::migrations::RemovePallet<SudoPalletName, RocksDbWeight>;
+
 /// All migrations of the runtime, aside from the ones declared in the pallets.
 ///
 /// This can be a tuple of types, each implementing `OnRuntimeUpgrade`.
 #[allow(unused_parens)]
-type Migrations = ();
+type Migrations = (
+	pallet_algorithms::migrations::v1::MigrateV0ToV1<Runtime, LegacyAlgorithmOwner>,
+	pallet_issuers::migrations::v1::MigrateV0ToV1<Runtime>,
+	// Before the credentials migration, which reads the ids it assigns
+	pallet_issuers::migrations::v2::MigrateV1ToV2<Runtime>,
+	pallet_credentials::migrations::v1::MigrateV0ToV1<Runtime>,
+	// After the credentials migration that resolves legacy issuer ids
+	pallet_issuers::migrations::v3::MigrateV2ToV3<Runtime>,
+	// After the algorithms `MigrateV0ToV1` and `SeedCouncilFromSudo`, which read the sudo key
+	SeedCouncilFromSudo,
+	RemoveSudo,
+);
 
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic =
@@ -390,8 +606,11 @@ mod benches {
 		[frame_system, SystemBench::<Runtime>]
 		[pallet_balances, Balances]
 		[pallet_timestamp, Timestamp]
-		[pallet_sudo, Sudo]
+		[pallet_collective, Council]
+		[pallet_preimage, Preimage]
+		[pallet_whitelist, Whitelist]
 		[pallet_issuers, IssuersModule]
+		[pallet_algorithms, AlgorithmsModule]
 		[pallet_credentials, CredentialsModule]
     [pallet_utility, Utility]
 
@@ -464,6 +683,26 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_algorithms_runtime_api::AlgorithmsApi<Block, Hash, BlockNumber> for Runtime {
+		fn score(
+			algorithm_id: u64,
+			issuer_hash: Hash,
+			account_id: Vec<u8>,
+		) -> Option<pallet_algorithms::Score<Hash, BlockNumber>> {
+			AlgorithmsModule::score_of(algorithm_id, issuer_hash, account_id)
+		}
+	}
+
+	impl pallet_issuers_runtime_api::IssuersApi<Block, AccountId, Hash> for Runtime {
+		fn issuer(issuer_hash: Hash) -> Option<pallet_issuers::IssuerInfo<AccountId, Hash>> {
+			IssuersModule::issuer_info(issuer_hash)
+		}
+
+		fn issuer_by_name(name: Vec<u8>) -> Option<Hash> {
+			IssuersModule::issuer_by_name(&name)
+		}
+	}
+
 	impl sp_consensus_aura::AuraApi<Block, AuraId> for Runtime {
 		fn slot_duration() -> sp_consensus_aura::SlotDuration {
 			sp_consensus_aura::SlotDuration::from_millis(Aura::slot_duration())
@@ -639,11 +878,11 @@ impl_runtime_apis! {
 		}
 
 		fn get_preset(id: &Option<sp_genesis_builder::PresetId>) -> Option<Vec<u8>> {
-			get_preset::<RuntimeGenesisConfig>(id, |_| None)
+			get_preset::<RuntimeGenesisConfig>(id, genesis_config_presets::get_preset)
 		}
 
 		fn preset_names() -> Vec<sp_genesis_builder::PresetId> {
-			vec![]
+			genesis_config_presets::preset_names()
 		}
 	}
 }